@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use napi_derive::napi;
+use serde_json::Value;
+
+use skillinstaller::{
+    detect_providers as detect, install as install_skill, parse_providers_csv,
+    parse_skill as parse_skill_source, parse_skill_str, DetectReport, InstallMethod,
+    InstallOptions, InstallRequest, InstallerError, Scope, SkillSource, Versioned,
+};
+
+/// Thrown errors carry `InstallerError::code()` as their `code` property.
+fn to_napi_err(err: InstallerError) -> napi::Error<String> {
+    napi::Error::new(err.code().to_string(), err.to_string())
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> napi::Result<Value, String> {
+    serde_json::to_value(value)
+        .map_err(|err| napi::Error::new("E_SERIALIZE".to_string(), err.to_string()))
+}
+
+/// Parses `scope`/`method` strings with the same kebab-case names the serde types use.
+fn from_name<T: serde::de::DeserializeOwned>(kind: &str, name: &str) -> napi::Result<T, String> {
+    serde_json::from_value(Value::String(name.to_string())).map_err(|_| {
+        napi::Error::new(
+            "E_INVALID_ARG".to_string(),
+            format!("invalid {kind} '{name}'"),
+        )
+    })
+}
+
+#[napi(object)]
+pub struct NodeInstallRequest {
+    pub source: String,
+    pub providers: Vec<String>,
+    /// `project` (default) or `user`.
+    pub scope: Option<String>,
+    pub project_root: Option<String>,
+    /// `symlink` (default) or `copy`.
+    pub method: Option<String>,
+    pub force: Option<bool>,
+    pub upgrade: Option<bool>,
+    pub include_ignored: Option<bool>,
+    pub allow_hooks: Option<bool>,
+    pub ignore_platform: Option<bool>,
+    pub slugify_name: Option<bool>,
+    pub target_dirs: Option<Vec<String>>,
+    pub variables: Option<HashMap<String, String>>,
+}
+
+#[napi]
+pub fn parse_skill(path: String) -> napi::Result<Value, String> {
+    let skill =
+        parse_skill_source(&SkillSource::LocalPath(PathBuf::from(path))).map_err(to_napi_err)?;
+    to_value(&skill)
+}
+
+#[napi]
+pub fn parse_skill_md(content: String) -> napi::Result<Value, String> {
+    let skill = parse_skill_str(&content).map_err(to_napi_err)?;
+    to_value(&skill)
+}
+
+#[napi]
+pub fn detect_providers(project_root: Option<String>) -> napi::Result<Value, String> {
+    to_value(&Versioned::new(DetectReport {
+        detected: detect(project_root.as_deref().map(Path::new)),
+    }))
+}
+
+#[napi]
+pub fn install(request: NodeInstallRequest) -> napi::Result<Value, String> {
+    let request = InstallRequest {
+        source: SkillSource::LocalPath(PathBuf::from(request.source)),
+        providers: parse_providers_csv(&request.providers.join(",")).map_err(to_napi_err)?,
+        scope: from_name::<Scope>("scope", request.scope.as_deref().unwrap_or("project"))?,
+        project_root: request.project_root.map(PathBuf::from),
+        method: from_name::<InstallMethod>(
+            "method",
+            request.method.as_deref().unwrap_or("symlink"),
+        )?,
+        force: request.force.unwrap_or_default(),
+        options: InstallOptions {
+            upgrade: request.upgrade.unwrap_or_default(),
+            include_ignored: request.include_ignored.unwrap_or_default(),
+            allow_hooks: request.allow_hooks.unwrap_or_default(),
+            ignore_platform: request.ignore_platform.unwrap_or_default(),
+            slugify_name: request.slugify_name.unwrap_or_default(),
+            target_dirs: request
+                .target_dirs
+                .unwrap_or_default()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            variables: request.variables.unwrap_or_default().into_iter().collect(),
+            ..InstallOptions::default()
+        },
+    };
+    to_value(&Versioned::new(
+        install_skill(request).map_err(to_napi_err)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(scope: &str) -> NodeInstallRequest {
+        NodeInstallRequest {
+            source: "./missing-skill".to_string(),
+            providers: vec!["claude".to_string()],
+            scope: Some(scope.to_string()),
+            project_root: None,
+            method: None,
+            force: None,
+            upgrade: None,
+            include_ignored: None,
+            allow_hooks: None,
+            ignore_platform: None,
+            slugify_name: None,
+            target_dirs: None,
+            variables: None,
+        }
+    }
+
+    #[test]
+    fn bindings_return_kebab_case_values_and_coded_errors() {
+        let skill =
+            parse_skill_md("---\nname: demo-skill\ndescription: Demo\n---\nBody.".to_string())
+                .unwrap();
+        assert_eq!(skill["name"], "demo-skill");
+        assert_eq!(skill["description"], "Demo");
+
+        let err = parse_skill_md("no frontmatter".to_string()).unwrap_err();
+        assert!(err.status.starts_with("E_"), "{}", err.status);
+
+        let err = install(request("global")).unwrap_err();
+        assert_eq!(err.status, "E_INVALID_ARG");
+        assert_eq!(err.reason, "invalid scope 'global'");
+
+        let report = detect_providers(None).unwrap();
+        assert!(report["schema-version"].is_number());
+        assert!(report["detected"].is_array());
+    }
+}
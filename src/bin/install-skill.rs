@@ -1,133 +1,1795 @@
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
-use clap::{Parser, Subcommand};
-#[cfg(feature = "interactive")]
-use skillinstaller::install_interactive;
+use clap::{CommandFactory, Parser, Subcommand};
+#[cfg(feature = "registry")]
 use skillinstaller::{
-    detect_providers, print_install_result, supported_providers, InstallSkillArgs, SkillSource,
+    configured_publish_url, detect_installed, install, pack_skill, parse_skill_spec,
+    semver::VersionReq, AvailableUpdate, InstallObserver, InstallOptions, InstalledReport,
+    LogObserver, PackedSkill, ProgressObserver, PublishReport, RegistryClient, RegistryEntry,
+    RegistrySearchReport, UpdateReport,
 };
-#[cfg(not(feature = "interactive"))]
-use skillinstaller::{install, parse_providers_csv, InstallRequest};
+use skillinstaller::{
+    detect_providers, detect_providers_with_versions, list_installed, load_project_config,
+    load_user_providers, parse_provider_path, parse_providers_csv, resolve_install_target,
+    resolve_provider_dir, set_project_path, skill_frontmatter_schema, supported_providers,
+    uninstall, user_dir_env_var, ColorChoice, DetectReport, DoctorCheck, DoctorReport,
+    DoctorStatus, InstallBatchReport, InstallFailure, InstallPlan, InstallResult, InstallSkillArgs,
+    InstalledListReport, InstallerError, JsonLogObserver, OutputFormat, PlanBatchReport,
+    ProviderId, ProviderListReport, ProviderSummary, Scope, SkillSource, UninstallRequest,
+    Verbosity, Versioned,
+};
+#[cfg(feature = "prompts")]
+use skillinstaller::{install_interactive_many, plan_interactive_many, Prompter};
+#[cfg(not(feature = "prompts"))]
+use skillinstaller::{install_many, plan, InstallMethod, InstallRequest};
+#[cfg(all(feature = "registry", feature = "prompts"))]
+use skillinstaller::{InstallMethod, InstallRequest};
 
 #[derive(Debug, Parser)]
 #[command(name = "install-skill")]
 #[command(about = "Developer tooling for installing .skill payloads across providers")]
 struct Cli {
+    /// Output format; `json` and `yaml` print a report on stdout and messages on stderr
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        env = "SKILL_INSTALLER_FORMAT"
+    )]
+    format: OutputFormat,
+
+    /// Print only errors; wins over -v
+    #[arg(short, long, global = true, env = "SKILL_INSTALLER_QUIET")]
+    quiet: bool,
+
+    /// Log each install step to stderr; repeat (-vv) to log every copied file
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = clap::ArgAction::Count,
+        env = "SKILL_INSTALLER_VERBOSE"
+    )]
+    verbose: u8,
+
+    /// Write a JSON-lines log of the run, down to each copied file, e.g. for bug reports
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        env = "SKILL_INSTALLER_LOG_FILE"
+    )]
+    log_file: Option<PathBuf>,
+
+    /// Without one, a terminal gets a menu of the common commands.
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
     /// List supported providers
-    Providers,
+    Providers {
+        /// Also show the install mode, symlink support, and user-path variable
+        #[arg(long, env = "SKILL_INSTALLER_WIDE")]
+        wide: bool,
+
+        /// When to color output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+        color: ColorChoice,
+    },
 
     /// Detect installed providers on this machine
     Detect {
         /// Project root used for project-level detection hints
-        #[arg(long)]
+        #[arg(long, env = "SKILL_INSTALLER_PROJECT_ROOT")]
         project_root: Option<PathBuf>,
+
+        /// Also show each provider's name and every path that matched
+        #[arg(long, env = "SKILL_INSTALLER_WIDE")]
+        wide: bool,
+
+        /// Run each detected provider's `<cli> --version` to report its version. This executes
+        /// the agent binaries found on PATH, so leave it off where those are not trusted
+        #[arg(long, env = "SKILL_INSTALLER_VERSIONS")]
+        versions: bool,
+
+        /// When to color output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+        color: ColorChoice,
     },
 
     /// Install a .skill payload
     Install {
-        /// Path containing .skill/ (or a direct .skill path)
-        #[arg(long)]
-        source: Option<PathBuf>,
+        /// Path containing .skill/ (or a direct .skill path); repeat to install several
+        #[arg(long, env = "SKILL_INSTALLER_SOURCE", value_delimiter = ';')]
+        source: Vec<PathBuf>,
+
+        /// Read more sources from this file, one per line ('-' for stdin)
+        #[arg(long, value_name = "FILE", env = "SKILL_INSTALLER_FROM_FILE")]
+        from_file: Option<PathBuf>,
+
+        /// Skills to download from the registry first, as NAME or NAME@VERSION
+        #[cfg(feature = "registry")]
+        #[arg(value_name = "NAME[@VERSION]")]
+        skills: Vec<String>,
+
+        #[cfg(feature = "registry")]
+        #[command(flatten)]
+        registry: RegistryArgs,
 
         #[command(flatten)]
         args: InstallSkillArgs,
     },
+
+    /// List installed skills across providers and scopes
+    #[command(visible_alias = "status")]
+    List {
+        /// Project root checked alongside the user scope; defaults to current directory
+        #[arg(long, env = "SKILL_INSTALLER_PROJECT_ROOT")]
+        project_root: Option<PathBuf>,
+
+        /// Ask the registry which skills have newer versions
+        #[cfg(feature = "registry")]
+        #[arg(long, env = "SKILL_INSTALLER_OUTDATED")]
+        outdated: bool,
+
+        #[cfg(feature = "registry")]
+        #[command(flatten)]
+        registry: RegistryArgs,
+
+        /// When to color output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+        color: ColorChoice,
+    },
+
+    /// Remove an installed skill, keeping files it did not install
+    Uninstall {
+        /// Skill name (the installed directory name)
+        name: String,
+
+        /// Providers to remove from (comma-separated). Use '*' for all.
+        #[arg(long, default_value = "*", env = "SKILL_INSTALLER_PROVIDERS")]
+        providers: String,
+
+        /// Install scope to remove from
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = Scope::Project,
+            env = "SKILL_INSTALLER_SCOPE"
+        )]
+        scope: Scope,
+
+        /// Project root; defaults to current directory when scope is project
+        #[arg(long, env = "SKILL_INSTALLER_PROJECT_ROOT")]
+        project_root: Option<PathBuf>,
+
+        /// Override a provider's project skills path, as PROVIDER=PATH (repeatable)
+        #[arg(
+            long = "provider-path",
+            value_name = "PROVIDER=PATH",
+            env = "SKILL_INSTALLER_PROVIDER_PATH",
+            value_delimiter = ';'
+        )]
+        provider_paths: Vec<String>,
+
+        /// When to color output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+        color: ColorChoice,
+    },
+
+    /// Search the registry by name, description, or tag
+    #[cfg(feature = "registry")]
+    Search {
+        /// Text to look for; lists every skill when omitted
+        query: Option<String>,
+
+        #[command(flatten)]
+        registry: RegistryArgs,
+
+        /// When to color output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+        color: ColorChoice,
+    },
+
+    /// Reinstall skills that have a newer version in the registry
+    #[cfg(feature = "registry")]
+    Update {
+        /// Skills to update
+        #[arg(required_unless_present = "all")]
+        names: Vec<String>,
+
+        /// Update every installed skill the registry lists, as far as its recorded range allows
+        #[arg(long, conflicts_with = "names", env = "SKILL_INSTALLER_UPDATE_ALL")]
+        all: bool,
+
+        /// Project root checked alongside the user scope; defaults to current directory
+        #[arg(long, env = "SKILL_INSTALLER_PROJECT_ROOT")]
+        project_root: Option<PathBuf>,
+
+        #[command(flatten)]
+        registry: RegistryArgs,
+
+        /// When to color output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+        color: ColorChoice,
+    },
+
+    /// Validate and pack a skill, then upload it or open a pull request against a git index
+    #[cfg(feature = "registry")]
+    Publish {
+        /// Path containing .skill/ (or a direct .skill path)
+        #[arg(default_value = ".")]
+        source: PathBuf,
+
+        /// Upload endpoint; defaults to `publish-url` in config.toml
+        #[arg(long, value_name = "URL", env = "SKILL_INSTALLER_PUBLISH_URL")]
+        endpoint: Option<String>,
+
+        /// Bearer token sent with the upload
+        #[arg(long, env = "SKILL_INSTALLER_REGISTRY_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+
+        /// Instead of uploading, add the skill to this clone of a git-hosted index, push a
+        /// branch, and open a pull request
+        #[arg(
+            long,
+            value_name = "CHECKOUT",
+            conflicts_with = "endpoint",
+            env = "SKILL_INSTALLER_PUBLISH_GIT"
+        )]
+        git: Option<PathBuf>,
+
+        /// Validate and pack without publishing
+        #[arg(long, env = "SKILL_INSTALLER_DRY_RUN")]
+        dry_run: bool,
+
+        /// With --git, push the branch without asking first
+        #[arg(short = 'y', long, env = "SKILL_INSTALLER_YES")]
+        yes: bool,
+
+        /// When to color output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+        color: ColorChoice,
+    },
+
+    /// Check config files, provider detection, and installed skills for problems
+    Doctor {
+        /// Project root checked alongside the user scope; defaults to current directory
+        #[arg(long, env = "SKILL_INSTALLER_PROJECT_ROOT")]
+        project_root: Option<PathBuf>,
+
+        #[cfg(feature = "registry")]
+        #[command(flatten)]
+        registry: RegistryArgs,
+
+        /// When to color output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+        color: ColorChoice,
+    },
+
+    /// Print the JSON Schema for SKILL.md frontmatter
+    Schema,
+}
+
+#[cfg(feature = "registry")]
+#[derive(Debug, clap::Args)]
+struct RegistryArgs {
+    /// Registry index URL or path; defaults to `registry` in config.toml
+    #[arg(long, value_name = "URL", env = "SKILL_INSTALLER_REGISTRY")]
+    registry: Option<String>,
+    /// Accept a registry served over plain http://
+    #[arg(long, env = "SKILL_INSTALLER_ALLOW_HTTP")]
+    allow_http: bool,
+}
+
+#[cfg(feature = "registry")]
+impl RegistryArgs {
+    fn client(&self) -> Result<RegistryClient, InstallerError> {
+        let client = match &self.registry {
+            Some(url) => RegistryClient::new(url),
+            None => RegistryClient::configured()?,
+        };
+        Ok(client.allow_http(self.allow_http))
+    }
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    let command = match cli.command.take() {
+        Some(command) => command,
+        None => match menu_command() {
+            Ok(command) => command,
+            Err(err) => {
+                eprintln!("error: {err}");
+                std::process::exit(err.exit_code());
+            }
+        },
+    };
+
+    if let Some(path) = &cli.log_file {
+        match JsonLogObserver::create(path) {
+            Ok(log) => {
+                let _ = LOG.set(Arc::new(log));
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                std::process::exit(err.exit_code());
+            }
+        }
+        log(
+            "run-started",
+            serde_json::json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "args": std::env::args_os()
+                    .map(|arg| arg.to_string_lossy().into_owned())
+                    .collect::<Vec<_>>(),
+                "cwd": std::env::current_dir().ok(),
+                "os": std::env::consts::OS,
+            }),
+        );
+    }
+
+    // User files first: the project file's settings take precedence over them.
+    let user_config = load_user_providers().err();
+    let project_config = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| load_project_config(&cwd).err());
+    let config_errors = [user_config, project_config]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    for err in &config_errors {
+        log("warning", serde_json::json!({ "message": err.to_string() }));
+        eprintln!("warning: {err}");
+    }
+
+    let format = cli.format;
+    let verbosity = Verbosity::from_flags(cli.quiet, cli.verbose);
+    let color = match &command {
+        Commands::Providers { color, .. }
+        | Commands::Detect { color, .. }
+        | Commands::List { color, .. }
+        | Commands::Uninstall { color, .. }
+        | Commands::Doctor { color, .. } => *color,
+        #[cfg(feature = "registry")]
+        Commands::Search { color, .. }
+        | Commands::Update { color, .. }
+        | Commands::Publish { color, .. } => *color,
+        Commands::Install { args, .. } => args.color,
+        Commands::Schema => ColorChoice::Auto,
+    }
+    .resolve();
+    let result = match command {
+        Commands::Providers { wide, .. } => cmd_providers(format, wide, color),
+        Commands::Detect {
+            project_root,
+            wide,
+            versions,
+            ..
+        } => cmd_detect(project_root, format, wide, versions, color),
+        Commands::Install {
+            source,
+            from_file,
+            #[cfg(feature = "registry")]
+            skills,
+            #[cfg(feature = "registry")]
+            registry,
+            mut args,
+        } => {
+            args.verbosity = verbosity;
+            args.log = LOG.get().cloned();
+            let source = Ok(source);
+            #[cfg(feature = "registry")]
+            let source = source.and_then(|mut source| {
+                if !skills.is_empty() && args.project_root.is_none() {
+                    // Downloads live in the cache; project installs still go to the cwd.
+                    args.project_root = Some(
+                        std::env::current_dir().map_err(|e| format!("failed to read cwd: {e}"))?,
+                    );
+                }
+                source.extend(download_skills(&skills, &registry)?);
+                Ok(source)
+            });
+            source.and_then(|source| cmd_install(source, from_file, args, format, color))
+        }
+        Commands::List {
+            project_root,
+            #[cfg(feature = "registry")]
+            outdated,
+            #[cfg(feature = "registry")]
+            registry,
+            ..
+        } => {
+            #[cfg(feature = "registry")]
+            let result = cmd_list(project_root, outdated.then_some(&registry), format, color);
+            #[cfg(not(feature = "registry"))]
+            let result = cmd_list(project_root, format, color);
+            result
+        }
+        Commands::Uninstall {
+            name,
+            providers,
+            scope,
+            project_root,
+            provider_paths,
+            ..
+        } => cmd_uninstall(
+            name,
+            &providers,
+            scope,
+            project_root,
+            &provider_paths,
+            format,
+            verbosity,
+            color,
+        ),
+        #[cfg(feature = "registry")]
+        Commands::Search {
+            query, registry, ..
+        } => cmd_search(query.as_deref().unwrap_or(""), &registry, format, color),
+        #[cfg(feature = "registry")]
+        Commands::Update {
+            names,
+            all,
+            project_root,
+            registry,
+            ..
+        } => cmd_update(
+            names,
+            all,
+            project_root,
+            &registry,
+            format,
+            verbosity,
+            color,
+        ),
+        #[cfg(feature = "registry")]
+        Commands::Publish {
+            source,
+            endpoint,
+            token,
+            git,
+            dry_run,
+            yes,
+            ..
+        } => cmd_publish(
+            &source,
+            endpoint,
+            token.as_deref(),
+            git.as_deref(),
+            dry_run,
+            yes,
+            format,
+            color,
+        ),
+        Commands::Doctor {
+            project_root,
+            #[cfg(feature = "registry")]
+            registry,
+            ..
+        } => cmd_doctor(
+            project_root,
+            &config_errors,
+            #[cfg(feature = "registry")]
+            &registry,
+            format,
+            color,
+        ),
+        Commands::Schema => cmd_schema(format),
+    };
 
-    let result = match cli.command {
-        Commands::Providers => cmd_providers(),
-        Commands::Detect { project_root } => cmd_detect(project_root),
-        Commands::Install { source, args } => cmd_install(source, args),
+    let (code, error) = match &result {
+        Ok(()) => (0, None),
+        Err(CliError::Reported(code)) => (*code, None),
+        Err(err) => (err.exit_code(), Some(err.to_string())),
     };
+    log(
+        "run-finished",
+        serde_json::json!({ "exit-code": code, "error": error }),
+    );
 
     if let Err(err) = result {
-        eprintln!("error: {err}");
-        std::process::exit(1);
+        if !matches!(err, CliError::Reported(_)) {
+            eprintln!("{} {err}", paint("error:", RED, color));
+        }
+        std::process::exit(err.exit_code());
     }
 }
 
-fn cmd_providers() -> Result<(), String> {
-    for p in supported_providers() {
-        let mode = if p.uses_agents_dir {
-            "shared .agents"
-        } else {
-            "provider-specific"
-        };
-        println!("{}\t{}\t{}", p.id.as_str(), p.display_name, mode);
+static LOG: OnceLock<Arc<JsonLogObserver>> = OnceLock::new();
+
+/// Adds a line to the `--log-file`, if one was given.
+fn log(event: &str, fields: serde_json::Value) {
+    if let Some(log) = LOG.get() {
+        log.record(event, fields);
+    }
+}
+
+/// Asks which command to run when none was given, then parses it as if it had been,
+/// keeping any global flags. Without a terminal, prints the help instead.
+#[cfg(feature = "prompts")]
+fn menu_command() -> Result<Commands, CliError> {
+    use std::io::IsTerminal;
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        missing_command();
+    }
+    #[cfg(feature = "interactive")]
+    let mut prompter = skillinstaller::TerminalPrompter;
+    #[cfg(not(feature = "interactive"))]
+    let mut prompter = skillinstaller::LinePrompter::stdio();
+    menu_command_with(&mut prompter, std::env::args_os().collect())
+}
+
+/// The menu itself; the chosen command is appended to `argv`.
+#[cfg(feature = "prompts")]
+fn menu_command_with(
+    prompter: &mut dyn Prompter,
+    mut argv: Vec<std::ffi::OsString>,
+) -> Result<Commands, CliError> {
+    const CHOICES: [(&str, &str); 5] = [
+        ("Install a skill", "install"),
+        ("List installed skills", "list"),
+        ("Uninstall a skill", "uninstall"),
+        ("Detect installed providers", "detect"),
+        ("Check the setup for problems", "doctor"),
+    ];
+    let labels = CHOICES.map(|(label, _)| label);
+    let choice = prompter
+        .select("◆  What would you like to do?", &labels, 0)
+        .map_err(|err| match err {
+            InstallerError::PromptBack => InstallerError::PromptCancelled,
+            err => err,
+        })?;
+
+    argv.push(CHOICES[choice].1.into());
+    match CHOICES[choice].1 {
+        "install" => {
+            let source = prompter.text("◆  Path to the skill", Some("."))?;
+            argv.extend(["--source".into(), source.into()]);
+        }
+        "uninstall" => loop {
+            let name = prompter.text("◆  Name of the skill to remove", None)?;
+            if !name.trim().is_empty() {
+                argv.push(name.trim().into());
+                break;
+            }
+        },
+        _ => {}
+    }
+    Ok(Cli::parse_from(argv)
+        .command
+        .expect("the menu always picks a subcommand"))
+}
+
+#[cfg(not(feature = "prompts"))]
+fn menu_command() -> Result<Commands, CliError> {
+    missing_command()
+}
+
+fn missing_command() -> ! {
+    eprint!("{}", Cli::command().render_help());
+    std::process::exit(2)
+}
+
+/// An `InstallerError` keeps its `exit_code()`; other failures exit with 1.
+enum CliError {
+    Installer(InstallerError),
+    Other(String),
+    /// Already printed; exit with this code.
+    Reported(i32),
+}
+
+impl CliError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Installer(err) => err.exit_code(),
+            CliError::Other(_) => 1,
+            CliError::Reported(code) => *code,
+        }
+    }
+}
+
+impl From<InstallerError> for CliError {
+    fn from(err: InstallerError) -> Self {
+        CliError::Installer(err)
+    }
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        CliError::Other(message)
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Installer(err) => err.fmt(f),
+            CliError::Other(message) => f.write_str(message),
+            CliError::Reported(code) => write!(f, "exit code {code}"),
+        }
+    }
+}
+
+const BOLD: &str = "1";
+const DIM: &str = "2";
+const RED: &str = "31";
+const YELLOW: &str = "33";
+
+/// `text` wrapped in the ANSI `style` when `color` is on.
+fn paint(text: impl std::fmt::Display, style: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{style}m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Prints `report` as JSON or YAML; `false` for text, which each command prints itself.
+fn print_report<T: serde::Serialize>(format: OutputFormat, report: T) -> bool {
+    match format {
+        OutputFormat::Text => return false,
+        OutputFormat::Json => print!("{}", Versioned::new(report).to_json()),
+        OutputFormat::Yaml => print!("{}", Versioned::new(report).to_yaml()),
     }
+    true
+}
+
+/// Prints `rows` under `headers` with every column but the last padded to its widest cell.
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>, color: bool) {
+    let mut widths = headers.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<String>, style: Option<&str>| {
+        let last = cells.len() - 1;
+        let mut out = String::new();
+        for (i, cell) in cells.into_iter().enumerate() {
+            let pad = widths[i] - cell.chars().count();
+            let style = style.or((i == 0).then_some(BOLD));
+            out.push_str(&match style {
+                Some(style) => paint(&cell, style, color),
+                None => cell,
+            });
+            if i < last {
+                out.push_str(&" ".repeat(pad + 2));
+            }
+        }
+        println!("{}", out.trim_end());
+    };
+    line(headers.iter().map(|h| h.to_string()).collect(), Some(DIM));
+    for row in rows {
+        line(row, None);
+    }
+}
+
+/// `path` with the home directory shown as `~`.
+fn tilde(path: &Path) -> String {
+    match std::env::var_os("HOME").map(PathBuf::from) {
+        Some(home) if !home.as_os_str().is_empty() => match path.strip_prefix(&home) {
+            Ok(rest) => Path::new("~").join(rest).display().to_string(),
+            Err(_) => path.display().to_string(),
+        },
+        _ => path.display().to_string(),
+    }
+}
+
+fn cmd_providers(format: OutputFormat, wide: bool, color: bool) -> Result<(), CliError> {
+    let providers = supported_providers();
+    let report = ProviderListReport {
+        providers: providers.iter().map(ProviderSummary::from).collect(),
+    };
+    if print_report(format, report) {
+        return Ok(());
+    }
+
+    let cwd = std::env::current_dir().ok();
+    let detected = detect_providers(cwd.as_deref());
+    let mut headers = vec!["PROVIDER", "NAME", "PROJECT PATH", "USER PATH", "DETECTED"];
+    if wide {
+        headers.extend(["MODE", "SYMLINKS", "USER PATH VARIABLE"]);
+    }
+    headers.push("NOTES");
+
+    let rows = providers
+        .iter()
+        .map(|p| {
+            let mut notes = Vec::new();
+            if let Some(replacement) = p.deprecated {
+                notes.push(format!("deprecated, use {replacement}"));
+            }
+            let project_path = if p.capabilities.supports_project_scope {
+                p.project_path.to_string()
+            } else {
+                notes.push("user scope only".to_string());
+                "-".to_string()
+            };
+            let user_path = match resolve_provider_dir(p.id, Scope::User, None) {
+                Ok(path) if p.capabilities.supports_user_scope => tilde(&path),
+                _ => {
+                    notes.push("project scope only".to_string());
+                    "-".to_string()
+                }
+            };
+            let status = detected
+                .iter()
+                .find(|d| d.provider == p.id)
+                .map_or("-", |d| d.confidence.as_str());
+            let mut row = vec![
+                p.id.as_str().to_string(),
+                p.display_name.to_string(),
+                project_path,
+                user_path,
+                status.to_string(),
+            ];
+            if wide {
+                let mode = if p.uses_agents_dir {
+                    "shared .agents"
+                } else {
+                    "provider-specific"
+                };
+                let symlinks = if p.capabilities.supports_symlink {
+                    "yes"
+                } else {
+                    "no"
+                };
+                row.extend([
+                    mode.to_string(),
+                    symlinks.to_string(),
+                    user_dir_env_var(p.id),
+                ]);
+            }
+            if notes.is_empty() {
+                row.push(String::new());
+            } else {
+                row.push(paint(notes.join(", "), DIM, color));
+            }
+            row
+        })
+        .collect();
+    print_table(&headers, rows, color);
     Ok(())
 }
 
-fn cmd_detect(project_root: Option<PathBuf>) -> Result<(), String> {
-    let detected = detect_providers(project_root.as_deref());
+fn cmd_detect(
+    project_root: Option<PathBuf>,
+    format: OutputFormat,
+    wide: bool,
+    versions: bool,
+    color: bool,
+) -> Result<(), CliError> {
+    let detected = if versions {
+        detect_providers_with_versions(project_root.as_deref())
+    } else {
+        detect_providers(project_root.as_deref())
+    };
+    log("detected", serde_json::json!({ "detected": &detected }));
+    if print_report(
+        format,
+        DetectReport {
+            detected: detected.clone(),
+        },
+    ) {
+        return Ok(());
+    }
     if detected.is_empty() {
         println!("no providers detected");
         return Ok(());
     }
 
-    for d in detected {
-        println!("{}\t{}", d.provider.as_str(), d.reason);
+    let mut headers = vec!["PROVIDER"];
+    if wide {
+        headers.push("NAME");
+    }
+    headers.push("CONFIDENCE");
+    if versions {
+        headers.push("VERSION");
+    }
+    headers.push("FOUND");
+    let rows = detected
+        .iter()
+        .map(|d| {
+            let mut row = vec![d.provider.as_str().to_string()];
+            if wide {
+                row.push(display_name(d.provider));
+            }
+            let found = d
+                .evidence
+                .iter()
+                .map(|e| format!("{}: {}", e.scope.as_str(), tilde(&e.path)))
+                .collect::<Vec<_>>();
+            let found = match found.as_slice() {
+                [] => String::new(),
+                all if wide => all.join(", "),
+                [first] => first.clone(),
+                [first, rest @ ..] => format!("{first} (+{} more)", rest.len()),
+            };
+            row.push(d.confidence.as_str().to_string());
+            if versions {
+                row.push(d.version.clone().unwrap_or_else(|| "-".to_string()));
+            }
+            row.push(found);
+            row
+        })
+        .collect();
+    print_table(&headers, rows, color);
+    Ok(())
+}
+
+fn display_name(provider: ProviderId) -> String {
+    supported_providers()
+        .into_iter()
+        .find(|p| p.id == provider)
+        .map_or_else(
+            || provider.as_str().to_string(),
+            |p| p.display_name.to_string(),
+        )
+}
+
+/// Runs every check, then fails with exit code 1 if any found an error.
+fn cmd_doctor(
+    project_root: Option<PathBuf>,
+    config_errors: &[InstallerError],
+    #[cfg(feature = "registry")] registry: &RegistryArgs,
+    format: OutputFormat,
+    color: bool,
+) -> Result<(), CliError> {
+    let root = match project_root {
+        Some(root) => root,
+        None => std::env::current_dir().map_err(|e| format!("failed to read cwd: {e}"))?,
+    };
+    let mut checks = Vec::new();
+    let mut check = |name: &str, status: DoctorStatus, message: String| {
+        checks.push(DoctorCheck {
+            check: name.to_string(),
+            status,
+            message,
+        });
+    };
+
+    if config_errors.is_empty() {
+        check("config", DoctorStatus::Ok, "config files parse".to_string());
+    }
+    for err in config_errors {
+        check("config", DoctorStatus::Error, err.to_string());
+    }
+
+    let detected = detect_providers(Some(&root));
+    if detected.is_empty() {
+        check(
+            "providers",
+            DoctorStatus::Warning,
+            "no providers detected; installs need --providers".to_string(),
+        );
+    } else {
+        let names = detected
+            .iter()
+            .map(|d| d.provider.as_str())
+            .collect::<Vec<_>>();
+        check(
+            "providers",
+            DoctorStatus::Ok,
+            format!("detected {}", names.join(", ")),
+        );
     }
 
+    // `list` skips dangling symlinks, so look for them in every skills directory.
+    let mut broken = 0;
+    let mut seen = HashSet::new();
+    for scope in [Scope::User, Scope::Project] {
+        for info in supported_providers() {
+            if !info.capabilities.supports_scope(scope) {
+                continue;
+            }
+            let Ok(target) = resolve_install_target(info.id, scope, Some(&root)) else {
+                continue;
+            };
+            if !seen.insert(target.target_dir.clone()) {
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(&target.target_dir) else {
+                continue;
+            };
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.is_symlink() && !path.exists() {
+                    broken += 1;
+                    let to = std::fs::read_link(&path).unwrap_or_default();
+                    check(
+                        "links",
+                        DoctorStatus::Error,
+                        format!("{} points to missing {}", tilde(&path), tilde(&to)),
+                    );
+                }
+            }
+        }
+    }
+    if broken == 0 {
+        check("links", DoctorStatus::Ok, "no broken symlinks".to_string());
+    }
+
+    match list_installed(Some(&root)) {
+        Ok(skills) => check(
+            "skills",
+            DoctorStatus::Ok,
+            format!("{} installed", skills.len()),
+        ),
+        Err(err) => check("skills", DoctorStatus::Error, err.to_string()),
+    }
+
+    #[cfg(feature = "registry")]
+    match registry.client() {
+        Ok(client) => match client.fetch_index() {
+            Ok(index) => check(
+                "registry",
+                DoctorStatus::Ok,
+                format!("{} lists {} skills", client.index_url(), index.skills.len()),
+            ),
+            Err(err) => check("registry", DoctorStatus::Error, err.to_string()),
+        },
+        Err(_) => check("registry", DoctorStatus::Ok, "none configured".to_string()),
+    }
+
+    let failed = checks.iter().any(|c| c.status == DoctorStatus::Error);
+    let report = DoctorReport { checks };
+    log("doctor", serde_json::json!(&report));
+    if !print_report(format, &report) {
+        let rows = report
+            .checks
+            .iter()
+            .map(|c| {
+                vec![
+                    c.check.clone(),
+                    c.status.as_str().to_string(),
+                    c.message.clone(),
+                ]
+            })
+            .collect();
+        print_table(&["CHECK", "STATUS", "DETAILS"], rows, color);
+    }
+    if failed {
+        return Err(CliError::Reported(1));
+    }
     Ok(())
 }
 
-fn cmd_install(source: Option<PathBuf>, args: InstallSkillArgs) -> Result<(), String> {
-    let cwd = std::env::current_dir().map_err(|e| format!("failed to read cwd: {e}"))?;
-    let source = SkillSource::LocalPath(source.unwrap_or(cwd));
+fn cmd_schema(format: OutputFormat) -> Result<(), CliError> {
+    let schema = match format {
+        OutputFormat::Yaml => serde_yaml::to_string(&skill_frontmatter_schema())
+            .map_err(|e| format!("failed to encode schema: {e}"))?,
+        OutputFormat::Text | OutputFormat::Json => {
+            serde_json::to_string_pretty(&skill_frontmatter_schema())
+                .map_err(|e| format!("failed to encode schema: {e}"))?
+                + "\n"
+        }
+    };
+    print!("{schema}");
+    Ok(())
+}
 
-    #[cfg(feature = "interactive")]
-    {
-        let result = install_interactive(source, &args).map_err(|e| e.to_string())?;
-        print_install_result(&result);
+#[allow(clippy::too_many_arguments)]
+fn cmd_uninstall(
+    name: String,
+    providers: &str,
+    scope: Scope,
+    project_root: Option<PathBuf>,
+    provider_paths: &[String],
+    format: OutputFormat,
+    verbosity: Verbosity,
+    color: bool,
+) -> Result<(), CliError> {
+    for raw in provider_paths {
+        let (provider, path) = parse_provider_path(raw)?;
+        set_project_path(provider, &path)?;
+    }
+    let providers = parse_providers_csv(providers)?;
+    let project_root = match scope {
+        Scope::User => None,
+        Scope::Project => match project_root {
+            Some(root) => Some(root),
+            None => Some(std::env::current_dir().map_err(|e| format!("failed to read cwd: {e}"))?),
+        },
+    };
+
+    let result = uninstall(UninstallRequest {
+        skill_name: name,
+        providers,
+        scope,
+        project_root,
+    })?;
+    log("uninstalled", serde_json::json!({ "result": &result }));
+    if print_report(format, &result) || verbosity == Verbosity::Quiet {
         return Ok(());
     }
 
-    #[cfg(not(feature = "interactive"))]
+    let name = paint(&result.skill_name, BOLD, color);
+    if result.removed.is_empty() {
+        println!("{} {name}", paint("skill not removed:", YELLOW, color));
+    } else {
+        println!("uninstalled skill: {name}");
+        for path in &result.removed {
+            println!("  {}", path.display());
+        }
+    }
+
+    if !result.warnings.is_empty() {
+        println!("{}", paint("warnings:", YELLOW, color));
+        for w in &result.warnings {
+            println!("  - {w}");
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_list(
+    project_root: Option<PathBuf>,
+    #[cfg(feature = "registry")] registry: Option<&RegistryArgs>,
+    format: OutputFormat,
+    color: bool,
+) -> Result<(), CliError> {
+    let root = match project_root {
+        Some(root) => root,
+        None => std::env::current_dir().map_err(|e| format!("failed to read cwd: {e}"))?,
+    };
+    let skills = list_installed(Some(&root))?;
+    #[cfg(feature = "registry")]
+    let (outdated, updates) = match registry {
+        Some(registry) => (true, available_updates(&skills, registry)?),
+        None => (false, Vec::new()),
+    };
+    #[cfg(not(feature = "registry"))]
+    let (outdated, updates) = (false, Vec::new());
+    let report = InstalledListReport { skills, updates };
+    log("listed", serde_json::json!(&report));
+    if print_report(format, &report) {
+        return Ok(());
+    }
+    if report.skills.is_empty() {
+        println!("no skills installed");
+        return Ok(());
+    }
+
+    let mut headers = vec!["NAME", "VERSION", "SCOPE", "PROVIDERS"];
+    if outdated {
+        headers.extend(["WANTED", "LATEST"]);
+    }
+    let mut rows = Vec::new();
+    for skill in &report.skills {
+        for scope in [Scope::Project, Scope::User] {
+            let installs = skill
+                .installed
+                .iter()
+                .filter(|i| i.scope == scope)
+                .collect::<Vec<_>>();
+            if installs.is_empty() {
+                continue;
+            }
+            let mut versions = installs
+                .iter()
+                .map(|i| {
+                    i.version
+                        .as_ref()
+                        .map_or("-".to_string(), ToString::to_string)
+                })
+                .collect::<Vec<_>>();
+            versions.dedup();
+            let providers = installs
+                .iter()
+                .map(|i| i.provider.as_str())
+                .collect::<Vec<_>>();
+            let mut row = vec![
+                skill.skill_name.clone(),
+                versions.join(", "),
+                scope.as_str().to_string(),
+                providers.join(", "),
+            ];
+            if outdated {
+                let update = report
+                    .updates
+                    .iter()
+                    .find(|u| u.skill_name == skill.skill_name);
+                let wanted = update
+                    .and_then(|u| u.wanted.as_ref())
+                    .map_or("-".to_string(), |v| paint(v, YELLOW, color));
+                let latest = update.map_or("-".to_string(), |u| u.latest.to_string());
+                row.extend([wanted, latest]);
+            }
+            rows.push(row);
+        }
+    }
+    print_table(&headers, rows, color);
+    Ok(())
+}
+
+/// Skills with a newer registry release than the highest installed version; `wanted` is
+/// set when a recorded range allows moving to one.
+#[cfg(feature = "registry")]
+fn available_updates(
+    skills: &[InstalledReport],
+    registry: &RegistryArgs,
+) -> Result<Vec<AvailableUpdate>, CliError> {
+    let index = registry.client()?.fetch_index()?;
+    let mut updates = Vec::new();
+    for skill in skills {
+        let Some(latest) = index.resolve(&skill.skill_name, None) else {
+            continue;
+        };
+        let current = skill
+            .installed
+            .iter()
+            .filter_map(|i| i.version.clone())
+            .max();
+        let wanted = skill
+            .installed
+            .iter()
+            .filter_map(|i| index.update_for(&skill.skill_name, i))
+            .map(|e| e.version.clone())
+            .max();
+        if wanted.is_some() || current.as_ref().is_none_or(|v| *v < latest.version) {
+            updates.push(AvailableUpdate {
+                skill_name: skill.skill_name.clone(),
+                current,
+                wanted,
+                latest: latest.version.clone(),
+            });
+        }
+    }
+    Ok(updates)
+}
+
+/// Sources listed in `file` (stdin for `-`), one per line; blank lines and `#` comments
+/// are skipped.
+fn read_sources(file: &Path) -> Result<Vec<PathBuf>, CliError> {
+    let read_err = |err: std::io::Error| InstallerError::IoError {
+        path: file.to_path_buf(),
+        message: format!("failed to read sources: {err}"),
+    };
+    let text = if file == Path::new("-") {
+        std::io::read_to_string(std::io::stdin()).map_err(read_err)?
+    } else {
+        std::fs::read_to_string(file).map_err(read_err)?
+    };
+    let sources = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+    if sources.is_empty() {
+        return Err(format!("no sources listed in {}", file.display()).into());
+    }
+    Ok(sources)
+}
+
+fn cmd_install(
+    mut sources: Vec<PathBuf>,
+    from_file: Option<PathBuf>,
+    args: InstallSkillArgs,
+    format: OutputFormat,
+    color: bool,
+) -> Result<(), CliError> {
+    if let Some(file) = &from_file {
+        sources.extend(read_sources(file)?);
+    }
+    let sources = if sources.is_empty() {
+        vec![std::env::current_dir().map_err(|e| format!("failed to read cwd: {e}"))?]
+    } else {
+        sources
+    };
+    let skills = sources
+        .iter()
+        .cloned()
+        .map(SkillSource::LocalPath)
+        .collect::<Vec<_>>();
+    // `-q` silences the summary; structured output is the point of `--format`.
+    let summary = format != OutputFormat::Text || args.verbosity != Verbosity::Quiet;
+
+    #[cfg(feature = "prompts")]
+    {
+        if args.dry_run {
+            print_plans(plan_interactive_many(skills, &args)?, format, color);
+            return Ok(());
+        }
+        let results = install_interactive_many(skills, &args)?;
+        report_installs(&sources, results, format, summary, color)
+    }
+
+    #[cfg(not(feature = "prompts"))]
     {
-        let all_specified =
-            args.providers.is_some() && args.scope.is_some() && args.method.is_some();
+        let mut args = args.with_configured_defaults()?;
+        if args.yes {
+            if args.providers.is_none() && args.target_dirs.is_empty() {
+                let detected = detect_providers(args.project_root.as_deref());
+                if !detected.is_empty() {
+                    let ids = detected.iter().map(|d| d.provider.as_str());
+                    args.providers = Some(ids.collect::<Vec<_>>().join(","));
+                }
+            }
+            args.scope.get_or_insert(Scope::Project);
+            args.method.get_or_insert(InstallMethod::Symlink);
+            args.force = args.force || !args.upgrade;
+        }
+        let all_specified = (args.providers.is_some() || !args.target_dirs.is_empty())
+            && args.scope.is_some()
+            && args.method.is_some();
         if !all_specified {
             return Err(
-                "interactive mode requires 'interactive' feature; provide --providers, --scope, and --method"
-                    .to_string(),
+                "interactive mode requires the 'prompts' feature; provide --providers, --scope, and --method"
+                    .to_string()
+                    .into(),
             );
         }
 
-        let providers =
-            parse_providers_csv(args.providers.as_deref().unwrap()).map_err(|e| e.to_string())?;
+        args.apply_provider_paths()?;
         let scope = args.scope.unwrap();
+        let providers = args.selected_providers(scope)?;
+        if providers.is_empty() && args.target_dirs.is_empty() {
+            return Err(format!(
+                "no providers left to install to after --exclude and {} scope restrictions",
+                scope.as_str()
+            )
+            .into());
+        }
         let method = args.method.unwrap();
-        let project_root = match scope {
-            skillinstaller::Scope::User => None,
-            skillinstaller::Scope::Project => {
-                Some(args.project_root.unwrap_or_else(|| match &source {
-                    SkillSource::LocalPath(p) => p.clone(),
-                    _ => std::path::PathBuf::from("."),
-                }))
+        let requests = skills
+            .into_iter()
+            .zip(&sources)
+            .map(|(source, path)| InstallRequest {
+                source,
+                providers: providers.clone(),
+                scope,
+                project_root: match scope {
+                    Scope::User => None,
+                    Scope::Project => Some(args.project_root.clone().unwrap_or(path.clone())),
+                },
+                method,
+                force: args.force,
+                options: {
+                    let mut options = args.install_options();
+                    if let Some(progress) = args.progress_observer() {
+                        options.observers.push(progress);
+                    }
+                    options
+                },
+            })
+            .collect::<Vec<_>>();
+        if args.dry_run {
+            let plans = requests
+                .into_iter()
+                .map(plan)
+                .collect::<Result<Vec<_>, _>>()?;
+            print_plans(plans, format, color);
+            return Ok(());
+        }
+        report_installs(&sources, install_many(requests), format, summary, color)
+    }
+}
+
+/// Downloads each `NAME[@VERSION]` from the registry, returning the unpacked sources.
+#[cfg(feature = "registry")]
+fn download_skills(skills: &[String], registry: &RegistryArgs) -> Result<Vec<PathBuf>, CliError> {
+    if skills.is_empty() {
+        return Ok(Vec::new());
+    }
+    let client = registry.client()?;
+    let index = client.fetch_index()?;
+    let mut sources = Vec::new();
+    for spec in skills {
+        let (name, version) = parse_skill_spec(spec)?;
+        let entry = index.resolve(&name, version.as_ref()).ok_or_else(|| {
+            InstallerError::NotInRegistry {
+                name: name.clone(),
+                requirement: version.as_ref().map(ToString::to_string),
             }
-        };
+        })?;
+        let path = client.download(entry, version.as_ref())?;
+        log(
+            "downloaded",
+            serde_json::json!({ "name": &entry.name, "version": &entry.version, "path": &path }),
+        );
+        sources.push(path);
+    }
+    Ok(sources)
+}
 
-        let result = install(InstallRequest {
-            source,
-            providers,
-            scope,
-            project_root,
-            method,
-            force: args.force,
+#[cfg(feature = "registry")]
+fn cmd_search(
+    query: &str,
+    registry: &RegistryArgs,
+    format: OutputFormat,
+    color: bool,
+) -> Result<(), CliError> {
+    let client = registry.client()?;
+    let index = client.fetch_index()?;
+    let found = index.search(query);
+    if print_report(
+        format,
+        RegistrySearchReport {
+            registry: client.index_url().to_string(),
+            skills: found.iter().map(|e| (*e).clone()).collect(),
+        },
+    ) {
+        return Ok(());
+    }
+    if found.is_empty() {
+        println!("no skills match '{query}'");
+        return Ok(());
+    }
+    let rows = found
+        .iter()
+        .map(|e| {
+            vec![
+                e.name.clone(),
+                e.version.to_string(),
+                e.tags.join(", "),
+                e.description.clone(),
+            ]
         })
-        .map_err(|e| e.to_string())?;
+        .collect();
+    print_table(&["NAME", "VERSION", "TAGS", "DESCRIPTION"], rows, color);
+    Ok(())
+}
+
+/// Reinstalls each install of `names` that its recorded range lets move to a newer
+/// registry release, keeping its scope, providers, and method.
+#[cfg(feature = "registry")]
+#[allow(clippy::too_many_arguments)]
+fn cmd_update(
+    names: Vec<String>,
+    all: bool,
+    project_root: Option<PathBuf>,
+    registry: &RegistryArgs,
+    format: OutputFormat,
+    verbosity: Verbosity,
+    color: bool,
+) -> Result<(), CliError> {
+    if names.is_empty() && !all {
+        return Err("name the skills to update, or pass --all"
+            .to_string()
+            .into());
+    }
+    let client = registry.client()?;
+    let index = client.fetch_index()?;
+    let root = match project_root {
+        Some(root) => root,
+        None => std::env::current_dir().map_err(|e| format!("failed to read cwd: {e}"))?,
+    };
+    let skills = if all {
+        list_installed(Some(&root))?
+            .into_iter()
+            .filter(|skill| index.resolve(&skill.skill_name, None).is_some())
+            .map(|skill| (skill.skill_name, skill.installed))
+            .collect::<Vec<_>>()
+    } else {
+        let mut skills = Vec::new();
+        for name in names {
+            let installed = detect_installed(&name, Some(&root))?;
+            if installed.is_empty() {
+                return Err(format!("{name} is not installed").into());
+            }
+            if index.resolve(&name, None).is_none() {
+                return Err(InstallerError::NotInRegistry {
+                    name,
+                    requirement: None,
+                }
+                .into());
+            }
+            skills.push((name, installed));
+        }
+        skills
+    };
+
+    let mut report = UpdateReport {
+        updated: Vec::new(),
+        up_to_date: Vec::new(),
+    };
+    for (name, installed) in skills {
+        // Installs moving to the same release under the same range share a download.
+        type Group<'a> = (&'a RegistryEntry, Option<String>, Scope, InstallMethod);
+        let mut groups: Vec<(Group, Vec<ProviderId>)> = Vec::new();
+        for skill in &installed {
+            let Some(entry) = index.update_for(&name, skill) else {
+                continue;
+            };
+            let requirement = skill.registry.as_ref().and_then(|p| p.requirement.clone());
+            let key = (entry, requirement, skill.scope, skill.method);
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, providers)) => providers.push(skill.provider),
+                None => groups.push((key, vec![skill.provider])),
+            }
+        }
+        if groups.is_empty() {
+            report.up_to_date.push(name);
+            continue;
+        }
+
+        for ((entry, requirement, scope, method), providers) in groups {
+            let requirement = requirement.and_then(|raw| VersionReq::parse(&raw).ok());
+            let source = client.download(entry, requirement.as_ref())?;
+            let mut options = InstallOptions {
+                upgrade: true,
+                ..InstallOptions::default()
+            };
+            if verbosity >= Verbosity::Verbose {
+                options
+                    .observers
+                    .push(Arc::new(LogObserver::new(verbosity)));
+            } else if verbosity == Verbosity::Normal
+                && std::io::IsTerminal::is_terminal(&std::io::stderr())
+            {
+                options
+                    .observers
+                    .push(Arc::new(ProgressObserver::new()) as Arc<dyn InstallObserver>);
+            }
+            if let Some(log) = LOG.get() {
+                options.observers.push(log.clone());
+            }
+            let result = install(InstallRequest {
+                source: SkillSource::LocalPath(source),
+                providers,
+                scope,
+                project_root: (scope == Scope::Project).then(|| root.clone()),
+                method,
+                force: false,
+                options,
+            })?;
+            log("updated", serde_json::json!({ "result": &result }));
+            report.updated.push(result);
+        }
+    }
+
+    if print_report(format, &report) || verbosity == Verbosity::Quiet {
+        return Ok(());
+    }
+    for result in &report.updated {
+        let version = result
+            .skill_version
+            .as_ref()
+            .map(|v| format!(" {v}"))
+            .unwrap_or_default();
+        println!(
+            "updated {}{version} ({} target(s))",
+            paint(&result.skill_name, BOLD, color),
+            result.installed_targets.len()
+        );
+    }
+    for name in &report.up_to_date {
+        println!("{} is up to date", paint(name, BOLD, color));
+    }
+    if report.updated.is_empty() && report.up_to_date.is_empty() {
+        println!("no registry skills installed");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "registry")]
+#[allow(clippy::too_many_arguments)]
+fn cmd_publish(
+    source: &Path,
+    endpoint: Option<String>,
+    token: Option<&str>,
+    git: Option<&Path>,
+    dry_run: bool,
+    yes: bool,
+    format: OutputFormat,
+    color: bool,
+) -> Result<(), CliError> {
+    let packed = pack_skill(source)?;
+    let mut report = PublishReport {
+        skill: packed.entry.clone(),
+        published_to: None,
+        branch: None,
+    };
+    let mut pull_request = None;
+    if dry_run {
+        // Validated and packed; nothing leaves the machine.
+    } else if let Some(checkout) = git {
+        let branch = format!("publish/{}-{}", packed.entry.name, packed.entry.version);
+        let index = publish_to_git(&packed, checkout, &branch, yes)?;
+        pull_request = open_pull_request(checkout, &branch);
+        report.published_to = Some(index.display().to_string());
+        report.branch = Some(branch);
+    } else {
+        let endpoint = endpoint.or_else(configured_publish_url).ok_or_else(|| {
+            "no publish endpoint; set `publish-url` in config.toml, or pass --endpoint or --git"
+                .to_string()
+        })?;
+        report.published_to = Some(packed.upload(&endpoint, token)?);
+    }
+    log("published", serde_json::json!(&report));
+    if print_report(format, &report) {
+        return Ok(());
+    }
+
+    let skill = paint(
+        format!("{} {}", packed.entry.name, packed.entry.version),
+        BOLD,
+        color,
+    );
+    match &report.published_to {
+        None => println!("packed {skill} ({} bytes)", packed.archive.len()),
+        Some(to) => println!("published {skill} to {to}"),
+    }
+    println!("  {}", packed.entry.hash);
+    if let Some(branch) = &report.branch {
+        match pull_request {
+            Some(url) => println!("  pull request: {url}"),
+            None => println!("  pushed branch {branch}; open a pull request for it"),
+        }
+    }
+    Ok(())
+}
+
+/// Commits the skill to `branch`, cut from the remote's default branch, and pushes it.
+/// On any failure, including a declined push, the checkout goes back to the branch it
+/// was on and `branch` is deleted.
+#[cfg(feature = "registry")]
+fn publish_to_git(
+    packed: &PackedSkill,
+    checkout: &Path,
+    branch: &str,
+    yes: bool,
+) -> Result<PathBuf, CliError> {
+    if !run_git(checkout, &["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+        return Err(format!("{} has uncommitted changes", checkout.display()).into());
+    }
+    run_git(checkout, &["fetch", "-q", "origin"])?;
+    if run_git(checkout, &["rev-parse", "-q", "--verify", "origin/HEAD"]).is_err() {
+        run_git(checkout, &["remote", "set-head", "origin", "--auto"])?;
+    }
+    run_git(
+        checkout,
+        &["switch", "-q", "--no-track", "-c", branch, "origin/HEAD"],
+    )?;
+
+    let commit_and_push = || -> Result<PathBuf, CliError> {
+        let index = packed.add_to_index(checkout)?;
+        run_git(checkout, &["add", "index.json", &packed.entry.url])?;
+        let message = format!("Publish {} {}", packed.entry.name, packed.entry.version);
+        run_git(checkout, &["commit", "-q", "-m", &message])?;
+        if !yes && !confirm_push(branch)? {
+            return Err(InstallerError::PromptCancelled.into());
+        }
+        run_git(checkout, &["push", "-q", "-u", "origin", branch])?;
+        Ok(index)
+    };
+    commit_and_push().inspect_err(|_| {
+        let _ = run_git(checkout, &["switch", "-q", "-f", "-"]);
+        let _ = run_git(checkout, &["branch", "-q", "-D", branch]);
+        // The archive is new unless the original branch already tracks it.
+        if run_git(checkout, &["ls-files", &packed.entry.url]).is_ok_and(|f| f.is_empty()) {
+            let _ = std::fs::remove_file(checkout.join(&packed.entry.url));
+        }
+    })
+}
+
+/// Asks on the terminal before pushing; without one, pushing needs `--yes`.
+#[cfg(feature = "registry")]
+fn confirm_push(branch: &str) -> Result<bool, CliError> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    if !std::io::stdin().is_terminal() {
+        return Err(
+            format!("refusing to push {branch} without a terminal to confirm; pass --yes").into(),
+        );
+    }
+    eprint!("push {branch} to origin? [y/N] ");
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .map_err(|e| format!("failed to read answer: {e}"))?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Runs `git` in `dir`, turning a failure into its stderr; returns the trimmed stdout.
+#[cfg(feature = "registry")]
+fn run_git(dir: &Path, args: &[&str]) -> Result<String, CliError> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Opens a pull request for `branch` with the GitHub CLI, returning its URL; `None` when
+/// `gh` is missing or the remote is not on GitHub.
+#[cfg(feature = "registry")]
+fn open_pull_request(dir: &Path, branch: &str) -> Option<String> {
+    let output = std::process::Command::new("gh")
+        .args(["pr", "create", "--fill", "--head", branch])
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// One source prints as before; several get a summary each, and a failed one does not
+/// stop the rest.
+fn report_installs(
+    sources: &[PathBuf],
+    results: Vec<Result<InstallResult, InstallerError>>,
+    format: OutputFormat,
+    summary: bool,
+    color: bool,
+) -> Result<(), CliError> {
+    if let [_] = sources {
+        let result = results.into_iter().next().expect("one result per source")?;
+        log("installed", serde_json::json!({ "result": &result }));
+        if summary {
+            print!("{}", result.render_styled(format, color));
+        }
+        return Ok(());
+    }
+
+    let mut report = InstallBatchReport {
+        installed: Vec::new(),
+        failed: Vec::new(),
+    };
+    let mut exit_code = None;
+    for (source, result) in sources.iter().zip(results) {
+        match result {
+            Ok(result) => report.installed.push(result),
+            Err(err) => {
+                eprintln!(
+                    "{} {}: {err}",
+                    paint("error:", RED, color),
+                    source.display()
+                );
+                exit_code.get_or_insert(err.exit_code());
+                report.failed.push(InstallFailure {
+                    source: source.clone(),
+                    code: err.code().to_string(),
+                    message: err.to_string(),
+                });
+            }
+        }
+    }
+    log("installed-batch", serde_json::json!(&report));
+    if !print_report(format, &report) && summary {
+        for result in &report.installed {
+            print!("{}", result.render_styled(format, color));
+        }
+    }
+    match exit_code {
+        Some(code) => Err(CliError::Reported(code)),
+        None => Ok(()),
+    }
+}
+
+fn print_plans(plans: Vec<InstallPlan>, format: OutputFormat, color: bool) {
+    if plans.len() == 1 || format == OutputFormat::Text {
+        for plan in &plans {
+            print!("{}", plan.render_styled(format, color));
+        }
+    } else {
+        print_report(format, PlanBatchReport { plans });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_flag_has_an_environment_variable() {
+        fn walk(command: &clap::Command, missing: &mut Vec<String>) {
+            for arg in command.get_arguments() {
+                let id = arg.get_id().as_str();
+                if !arg.is_positional()
+                    && id != "help"
+                    && id != "version"
+                    && arg.get_env().is_none()
+                {
+                    missing.push(format!("{} --{id}", command.get_name()));
+                }
+            }
+            for sub in command.get_subcommands() {
+                walk(sub, missing);
+            }
+        }
+
+        let mut missing = Vec::new();
+        walk(&Cli::command(), &mut missing);
+        assert!(missing.is_empty(), "flags without an env var: {missing:?}");
+    }
+
+    #[test]
+    fn user_config_is_read_once() {
+        let first = load_user_providers().map_err(|err| err.to_string());
+
+        let config_home = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(config_home.path().join("skill-installer")).unwrap();
+        std::fs::write(
+            config_home.path().join("skill-installer/config.toml"),
+            "favorites = 3\n",
+        )
+        .unwrap();
+        let previous = std::env::var_os("XDG_CONFIG_HOME");
+        std::env::set_var("XDG_CONFIG_HOME", config_home.path());
+        let second = load_user_providers().map_err(|err| err.to_string());
+        match previous {
+            Some(value) => std::env::set_var("XDG_CONFIG_HOME", value),
+            None => std::env::remove_var("XDG_CONFIG_HOME"),
+        }
+
+        assert_eq!(first, second);
+    }
+
+    /// Picks the menu entry labelled `pick`.
+    #[cfg(feature = "prompts")]
+    struct Menu {
+        pick: &'static str,
+    }
+
+    #[cfg(feature = "prompts")]
+    impl Prompter for Menu {
+        fn select(&mut self, _: &str, options: &[&str], _: usize) -> skillinstaller::Result<usize> {
+            Ok(options.iter().position(|o| *o == self.pick).unwrap())
+        }
+
+        fn multi_select(
+            &mut self,
+            _: &skillinstaller::ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            unreachable!()
+        }
+
+        fn confirm(&mut self, _: &str, _: bool) -> skillinstaller::Result<bool> {
+            unreachable!()
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!()
+        }
+    }
+
+    #[cfg(feature = "prompts")]
+    fn pick(label: &'static str) -> Commands {
+        let argv = vec!["install-skill".into(), "-v".into()];
+        match menu_command_with(&mut Menu { pick: label }, argv) {
+            Ok(command) => command,
+            Err(err) => panic!("{err}"),
+        }
+    }
 
-        print_install_result(&result);
-        Ok(())
+    #[cfg(feature = "prompts")]
+    #[test]
+    fn menu_list_lists_installed_skills() {
+        assert!(matches!(
+            pick("List installed skills"),
+            Commands::List { .. }
+        ));
+        assert!(matches!(
+            pick("Check the setup for problems"),
+            Commands::Doctor { .. }
+        ));
+        assert!(matches!(
+            pick("Detect installed providers"),
+            Commands::Detect { .. }
+        ));
     }
 }
@@ -0,0 +1,78 @@
+//! Helpers for `build.rs` scripts that embed a skill.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{InstallerError, Result};
+use crate::lint::{lint_skill, Severity};
+use crate::parser::parse_skill;
+use crate::types::{EmbeddedSkill, ParsedSkill, SkillSource};
+
+/// Parses and lints the skill directory about to be embedded, so a broken `SKILL.md`
+/// fails the build instead of the user's install.
+///
+/// Lint errors become `InstallerError::LintFailed`; warnings, including the parser's,
+/// are printed as `cargo:warning=` lines. Also emits `cargo:rerun-if-changed` for `path`.
+///
+/// ```no_run
+/// // in build.rs `main`
+/// skillinstaller::build::validate_embedded_dir("skill").unwrap();
+/// ```
+pub fn validate_embedded_dir(path: impl AsRef<Path>) -> Result<ParsedSkill> {
+    let path = path.as_ref();
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let mut files = Vec::new();
+    collect_files(path, path, &mut files)?;
+    let skill = EmbeddedSkill::from_files(&files)?;
+    let payload = skill.skill_md.clone();
+    let parsed = parse_skill(&SkillSource::Embedded(skill))?;
+
+    let diagnostics = lint_skill(&parsed, payload.as_bytes());
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .map(|d| format!("{}: {}", d.rule, d.message))
+        .collect();
+    if !errors.is_empty() {
+        return Err(InstallerError::LintFailed {
+            path: path.join("SKILL.md"),
+            message: errors.join("; "),
+        });
+    }
+
+    for warning in &parsed.warnings {
+        println!("cargo:warning={}: {warning}", parsed.name);
+    }
+    for d in diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Warning)
+    {
+        println!("cargo:warning={}: {} ({})", parsed.name, d.message, d.rule);
+    }
+    Ok(parsed)
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<(PathBuf, Vec<u8>)>) -> Result<()> {
+    let io_err = |path: &Path, err: std::io::Error| InstallerError::IoError {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    };
+    let mut entries = fs::read_dir(dir)
+        .map_err(|err| io_err(dir, err))?
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(|err| io_err(dir, err))?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, out)?;
+        } else {
+            let bytes = fs::read(&path).map_err(|err| io_err(&path, err))?;
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            out.push((relative, bytes));
+        }
+    }
+    Ok(())
+}
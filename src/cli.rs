@@ -0,0 +1,241 @@
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::{InstallerError, Result};
+use crate::observer::{InstallObserver, JsonLogObserver, LogObserver, ProgressObserver};
+use crate::providers::{
+    configured_defaults, parse_provider_path, parse_providers_csv, provider_supports_scope,
+    set_project_path,
+};
+use crate::render::{ColorChoice, Verbosity};
+use crate::types::{InstallMethod, InstallOptions, ProviderId, Scope, SymlinkPolicy};
+
+pub(crate) const PROVIDERS_ENV: &str = "SKILL_INSTALLER_PROVIDERS";
+pub(crate) const SCOPE_ENV: &str = "SKILL_INSTALLER_SCOPE";
+pub(crate) const METHOD_ENV: &str = "SKILL_INSTALLER_METHOD";
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct InstallSkillArgs {
+    /// Providers to target (comma-separated). Use '*' for all.
+    #[arg(long, env = PROVIDERS_ENV)]
+    pub providers: Option<String>,
+
+    /// Providers to leave out (comma-separated), e.g. with --providers '*'
+    #[arg(long, env = "SKILL_INSTALLER_EXCLUDE")]
+    pub exclude: Option<String>,
+
+    /// Install scope
+    #[arg(long, value_enum, env = SCOPE_ENV)]
+    pub scope: Option<Scope>,
+
+    /// Project root; defaults to current directory when scope is project
+    #[arg(long, env = "SKILL_INSTALLER_PROJECT_ROOT")]
+    pub project_root: Option<PathBuf>,
+
+    /// Installation method
+    #[arg(long, value_enum, env = METHOD_ENV)]
+    pub method: Option<InstallMethod>,
+
+    /// Overwrite existing destination skill folders
+    #[arg(long, default_value_t = false, env = "SKILL_INSTALLER_FORCE")]
+    pub force: bool,
+
+    /// Replace existing installs only if the source version is newer
+    #[arg(long, default_value_t = false, env = "SKILL_INSTALLER_UPGRADE")]
+    pub upgrade: bool,
+
+    /// Print what would be installed, overwritten, and normalized, then exit without changes
+    #[arg(long, default_value_t = false, env = "SKILL_INSTALLER_DRY_RUN")]
+    pub dry_run: bool,
+
+    /// Install even if the skill's declared platforms exclude this OS
+    #[arg(long, default_value_t = false, env = "SKILL_INSTALLER_IGNORE_PLATFORM")]
+    pub ignore_platform: bool,
+
+    /// Slugify the skill name for the installed directory (e.g. "My Skill" -> my-skill)
+    #[arg(long, default_value_t = false, env = "SKILL_INSTALLER_SLUGIFY_NAME")]
+    pub slugify_name: bool,
+
+    /// Also copy VCS/editor entries (.git, .hg, .DS_Store, node_modules)
+    #[arg(long, default_value_t = false, env = "SKILL_INSTALLER_INCLUDE_IGNORED")]
+    pub include_ignored: bool,
+
+    /// How symlinks inside the source skill are handled
+    #[arg(long, value_enum, default_value_t = SymlinkPolicy::Follow, env = "SKILL_INSTALLER_SYMLINKS")]
+    pub symlinks: SymlinkPolicy,
+
+    /// Run pre/post install hook scripts declared in the skill's frontmatter
+    #[arg(long, default_value_t = false, env = "SKILL_INSTALLER_ALLOW_HOOKS")]
+    pub allow_hooks: bool,
+
+    /// Seconds before a running hook is killed (default 60)
+    #[arg(long, value_name = "SECS", env = "SKILL_INSTALLER_HOOK_TIMEOUT")]
+    pub hook_timeout: Option<u64>,
+
+    /// Also install into this directory (repeatable), for agents not in the provider list
+    #[arg(
+        long = "target-dir",
+        value_name = "PATH",
+        env = "SKILL_INSTALLER_TARGET_DIR",
+        value_delimiter = ';'
+    )]
+    pub target_dirs: Vec<PathBuf>,
+
+    /// Override a provider's project skills path, e.g. claude-code=.claude/agents/skills (repeatable)
+    #[arg(
+        long = "provider-path",
+        value_name = "PROVIDER=PATH",
+        env = "SKILL_INSTALLER_PROVIDER_PATH",
+        value_delimiter = ';'
+    )]
+    pub provider_paths: Vec<String>,
+
+    /// Value for a skill template variable (repeatable)
+    #[arg(
+        long = "var",
+        value_name = "NAME=VALUE",
+        value_parser = parse_var,
+        env = "SKILL_INSTALLER_VAR",
+        value_delimiter = ','
+    )]
+    pub vars: Vec<(String, String)>,
+
+    /// Skip every prompt: detected providers, project scope, symlink method, and overwrite
+    #[arg(
+        short = 'y',
+        long,
+        default_value_t = false,
+        env = "SKILL_INSTALLER_YES"
+    )]
+    pub yes: bool,
+
+    /// Don't prefill prompts from, or save, the answers of the last interactive install
+    #[arg(long, default_value_t = false, env = "SKILL_INSTALLER_NO_REMEMBER")]
+    pub no_remember: bool,
+
+    /// When to color prompts and output; `auto` honors NO_COLOR and CLICOLOR_FORCE
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto, env = "SKILL_INSTALLER_COLOR")]
+    pub color: ColorChoice,
+
+    /// Set from the global `-q`/`-v` flags; `Verbose` and up log each install step to stderr.
+    #[arg(skip)]
+    pub verbosity: Verbosity,
+
+    /// Set from the global `--log-file` flag; receives every install event.
+    #[arg(skip)]
+    pub log: Option<Arc<JsonLogObserver>>,
+}
+
+fn parse_var(raw: &str) -> std::result::Result<(String, String), String> {
+    raw.split_once('=')
+        .filter(|(name, _)| !name.trim().is_empty())
+        .map(|(name, value)| (name.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{raw}'"))
+}
+
+impl InstallSkillArgs {
+    pub fn apply_provider_paths(&self) -> Result<()> {
+        for raw in &self.provider_paths {
+            let (provider, path) = parse_provider_path(raw)?;
+            set_project_path(provider, &path)?;
+        }
+        Ok(())
+    }
+
+    pub fn excluded_providers(&self) -> Result<Vec<ProviderId>> {
+        match &self.exclude {
+            Some(csv) => parse_providers_csv(csv),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// `--providers` minus `--exclude`. Providers pulled in by `'*'` or `@group` that do
+    /// not support `scope` are dropped; explicitly named ones are kept so install rejects them.
+    pub fn selected_providers(&self, scope: Scope) -> Result<Vec<ProviderId>> {
+        let Some(csv) = self.providers.as_deref() else {
+            return Ok(Vec::new());
+        };
+        let exclude = self.excluded_providers()?;
+        let mut providers = Vec::new();
+        for token in csv.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let expansion = token == "*" || token.starts_with('@');
+            for provider in parse_providers_csv(token)? {
+                if exclude.contains(&provider) || providers.contains(&provider) {
+                    continue;
+                }
+                if expansion && !provider_supports_scope(provider, scope) {
+                    continue;
+                }
+                providers.push(provider);
+            }
+        }
+        Ok(providers)
+    }
+
+    /// Fills a missing `--providers`/`--scope`/`--method` from the `SKILL_INSTALLER_*`
+    /// environment variables, then the config files' `[defaults]`.
+    pub fn with_configured_defaults(&self) -> Result<InstallSkillArgs> {
+        let defaults = configured_defaults();
+        let mut args = self.clone();
+        if args.providers.is_none() {
+            args.providers = env_value(PROVIDERS_ENV).or(defaults.providers);
+        }
+        if args.scope.is_none() {
+            args.scope = env_choice(SCOPE_ENV)?.or(defaults.scope);
+        }
+        if args.method.is_none() {
+            args.method = env_choice(METHOD_ENV)?.or(defaults.method);
+        }
+        Ok(args)
+    }
+
+    pub fn install_options(&self) -> InstallOptions {
+        let mut options = InstallOptions {
+            include_ignored: self.include_ignored,
+            symlinks: self.symlinks,
+            allow_hooks: self.allow_hooks,
+            hook_timeout: self.hook_timeout.map(Duration::from_secs),
+            target_dirs: self.target_dirs.clone(),
+            upgrade: self.upgrade,
+            ignore_platform: self.ignore_platform,
+            slugify_name: self.slugify_name,
+            variables: self.vars.iter().cloned().collect(),
+            ..InstallOptions::default()
+        };
+        if self.verbosity >= Verbosity::Verbose {
+            options
+                .observers
+                .push(Arc::new(LogObserver::new(self.verbosity)));
+        }
+        if let Some(log) = &self.log {
+            options.observers.push(log.clone());
+        }
+        options
+    }
+
+    /// Live per-destination progress on stderr, at the default verbosity when stderr is a
+    /// terminal; `-v` logs steps instead.
+    pub fn progress_observer(&self) -> Option<Arc<dyn InstallObserver>> {
+        (self.verbosity == Verbosity::Normal && std::io::stderr().is_terminal())
+            .then(|| Arc::new(ProgressObserver::new()) as Arc<dyn InstallObserver>)
+    }
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn env_choice<T: clap::ValueEnum>(name: &str) -> Result<Option<T>> {
+    env_value(name)
+        .map(|value| {
+            T::from_str(value.trim(), true).map_err(|_| InstallerError::ConfigError {
+                path: PathBuf::from(name),
+                message: format!("unknown value '{value}'"),
+            })
+        })
+        .transpose()
+}
@@ -1,23 +1,70 @@
-use std::path::PathBuf;
-
+use crate::error::{InstallerError, Result};
 use crate::types::{EmbeddedSkill, SkillSource};
 
 pub use rust_embed;
 pub use rust_embed::Embed;
 
+const SKILL_MD: &str = "SKILL.md";
+
+#[deprecated(note = "panics on a missing or non-UTF-8 SKILL.md; use `try_load_embedded_skill`")]
 pub fn load_embedded_skill<T: rust_embed::RustEmbed>() -> SkillSource {
-    let skill_md_file = T::get("SKILL.md").expect("embedded skill must contain SKILL.md");
-    let skill_md = std::str::from_utf8(skill_md_file.data.as_ref())
-        .expect("SKILL.md must be valid UTF-8")
-        .to_string();
-
-    let files = T::iter()
-        .filter(|path| path.as_ref() != "SKILL.md")
-        .map(|path| {
-            let file = T::get(path.as_ref()).unwrap();
-            (PathBuf::from(path.as_ref()), file.data.to_vec())
+    try_load_embedded_skill::<T>().unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_load_embedded_skill<T: rust_embed::RustEmbed>() -> Result<SkillSource> {
+    let paths = T::iter().map(|path| path.into_owned()).collect::<Vec<_>>();
+    load_skill_at::<T>("", &paths, &[])
+}
+
+/// Loads every skill in a bundle: each directory of the asset tree holding a `SKILL.md`,
+/// ordered by path. Files belong to the nearest skill above them.
+pub fn load_embedded_skills<T: rust_embed::RustEmbed>() -> Result<Vec<SkillSource>> {
+    let mut paths = T::iter().map(|path| path.into_owned()).collect::<Vec<_>>();
+    paths.sort();
+    let roots = paths
+        .iter()
+        .filter_map(|path| {
+            if path == SKILL_MD {
+                Some(String::new())
+            } else {
+                path.strip_suffix(SKILL_MD)
+                    .filter(|dir| dir.ends_with('/'))
+                    .map(str::to_string)
+            }
+        })
+        .collect::<Vec<_>>();
+    if roots.is_empty() {
+        return Err(InstallerError::MissingEmbeddedSkill);
+    }
+
+    roots
+        .iter()
+        .map(|root| load_skill_at::<T>(root, &paths, &roots))
+        .collect()
+}
+
+/// `root` is `""` or a directory prefix ending in `/`.
+fn load_skill_at<T: rust_embed::RustEmbed>(
+    root: &str,
+    paths: &[String],
+    roots: &[String],
+) -> Result<SkillSource> {
+    let skill_md_path = format!("{root}{SKILL_MD}");
+    if T::get(&skill_md_path).is_none() {
+        return Err(InstallerError::MissingEmbeddedSkill);
+    }
+
+    let files = paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(root).map(|relative| (path, relative)))
+        .filter(|(path, _)| {
+            // Skip files owned by a skill nested deeper under this one.
+            !roots
+                .iter()
+                .any(|other| other.len() > root.len() && path.starts_with(other.as_str()))
         })
-        .collect();
+        .filter_map(|(path, relative)| Some((relative, T::get(path)?.data)))
+        .collect::<Vec<_>>();
 
-    SkillSource::Embedded(EmbeddedSkill { skill_md, files })
+    EmbeddedSkill::from_files(&files).map(SkillSource::Embedded)
 }
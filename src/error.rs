@@ -5,10 +5,17 @@ use thiserror::Error;
 pub type Result<T> = std::result::Result<T, InstallerError>;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum InstallerError {
     #[error("invalid source: expected .skill/SKILL.md in {path}")]
     InvalidSource { path: PathBuf },
 
+    #[error("embedded skill assets contain no SKILL.md")]
+    MissingEmbeddedSkill,
+
+    #[error("{path} is not valid UTF-8")]
+    InvalidUtf8 { path: PathBuf },
+
     #[error("invalid frontmatter: {message}")]
     InvalidFrontmatter { message: String },
 
@@ -18,21 +25,154 @@ pub enum InstallerError {
     #[error("invalid skill name: {name}")]
     InvalidName { name: String },
 
+    #[error("{path} failed lint: {message}")]
+    LintFailed { path: PathBuf, message: String },
+
+    #[error("symlink {path} resolves outside the skill root ({target})")]
+    SymlinkEscapesSource { path: PathBuf, target: PathBuf },
+
     #[error("project scope requires --project-root")]
     ProjectRootRequired,
 
     #[error("skill already installed at {path}; use --force to overwrite")]
     AlreadyExists { path: PathBuf },
 
-    #[error("unsupported provider: {provider}")]
-    UnsupportedProvider { provider: String },
+    #[error("{path} collides with existing {existing} on case-insensitive filesystems")]
+    CaseCollision { path: PathBuf, existing: PathBuf },
+
+    #[error("{path} already holds skill '{existing}'; uninstall it first")]
+    NameCollision { path: PathBuf, existing: String },
+
+    #[error("unsupported provider: {provider}{}", did_you_mean(.suggestion))]
+    UnsupportedProvider {
+        provider: String,
+        suggestion: Option<String>,
+    },
+
+    #[error("provider {provider} does not support {scope} scope")]
+    UnsupportedScope { provider: String, scope: String },
+
+    #[error("skill {skill} supports {supported}, not {platform}; use --ignore-platform to install anyway")]
+    UnsupportedPlatform {
+        skill: String,
+        platform: String,
+        supported: String,
+    },
+
+    #[error("skill {skill} needs a value for variable {name}; pass --var {name}=VALUE")]
+    MissingVariable { skill: String, name: String },
+
+    #[error("skill {skill} does not support any of the requested providers")]
+    IncompatibleProviders { skill: String },
+
+    #[error("cannot register provider {provider}: {message}")]
+    InvalidProvider { provider: String, message: String },
+
+    #[error("invalid config {path}: {message}")]
+    ConfigError { path: PathBuf, message: String },
+
+    #[error("{hook} hook failed: {message}")]
+    HookFailed { hook: String, message: String },
 
     #[error("installation cancelled by user")]
     PromptCancelled,
 
+    /// Returned by a `Prompter` to go back to the previous question.
+    #[error("went back to the previous prompt")]
+    PromptBack,
+
     #[error("interactive prompt error: {message}")]
     PromptError { message: String },
 
+    #[error("registry {url}: {message}")]
+    RegistryError { url: String, message: String },
+
+    #[error("{name} is not in the registry{}", at_requirement(.requirement))]
+    NotInRegistry {
+        name: String,
+        requirement: Option<String>,
+    },
+
     #[error("io error at {path}: {message}")]
     IoError { path: PathBuf, message: String },
 }
+
+impl InstallerError {
+    /// Stable machine-readable code, e.g. `E_ALREADY_EXISTS`, to branch on instead of `Display` text.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidSource { .. } => "E_INVALID_SOURCE",
+            Self::MissingEmbeddedSkill => "E_MISSING_EMBEDDED_SKILL",
+            Self::InvalidUtf8 { .. } => "E_INVALID_UTF8",
+            Self::InvalidFrontmatter { .. } => "E_INVALID_FRONTMATTER",
+            Self::MissingName => "E_MISSING_NAME",
+            Self::InvalidName { .. } => "E_INVALID_NAME",
+            Self::LintFailed { .. } => "E_LINT_FAILED",
+            Self::SymlinkEscapesSource { .. } => "E_SYMLINK_ESCAPES_SOURCE",
+            Self::ProjectRootRequired => "E_PROJECT_ROOT_REQUIRED",
+            Self::AlreadyExists { .. } => "E_ALREADY_EXISTS",
+            Self::CaseCollision { .. } => "E_CASE_COLLISION",
+            Self::NameCollision { .. } => "E_NAME_COLLISION",
+            Self::UnsupportedProvider { .. } => "E_UNSUPPORTED_PROVIDER",
+            Self::UnsupportedScope { .. } => "E_UNSUPPORTED_SCOPE",
+            Self::UnsupportedPlatform { .. } => "E_UNSUPPORTED_PLATFORM",
+            Self::MissingVariable { .. } => "E_MISSING_VARIABLE",
+            Self::IncompatibleProviders { .. } => "E_INCOMPATIBLE_PROVIDERS",
+            Self::InvalidProvider { .. } => "E_INVALID_PROVIDER",
+            Self::ConfigError { .. } => "E_INVALID_CONFIG",
+            Self::HookFailed { .. } => "E_HOOK_FAILED",
+            Self::PromptCancelled => "E_CANCELLED",
+            Self::PromptBack => "E_PROMPT_BACK",
+            Self::PromptError { .. } => "E_PROMPT",
+            Self::RegistryError { .. } => "E_REGISTRY",
+            Self::NotInRegistry { .. } => "E_NOT_IN_REGISTRY",
+            Self::IoError { .. } => "E_IO",
+        }
+    }
+
+    /// Process exit code for the CLI, by category: 2 invalid source, 3 already exists,
+    /// 4 unsupported provider, 5 cancelled, 6 no way to prompt, 7 invalid configuration,
+    /// 8 hook failed, 9 registry unreachable or missing the skill, 1 anything else.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::InvalidSource { .. }
+            | Self::MissingEmbeddedSkill
+            | Self::InvalidUtf8 { .. }
+            | Self::InvalidFrontmatter { .. }
+            | Self::MissingName
+            | Self::InvalidName { .. }
+            | Self::LintFailed { .. }
+            | Self::SymlinkEscapesSource { .. } => 2,
+            Self::AlreadyExists { .. }
+            | Self::CaseCollision { .. }
+            | Self::NameCollision { .. } => 3,
+            Self::UnsupportedProvider { .. }
+            | Self::UnsupportedScope { .. }
+            | Self::UnsupportedPlatform { .. }
+            | Self::IncompatibleProviders { .. } => 4,
+            Self::PromptCancelled | Self::PromptBack => 5,
+            Self::PromptError { .. } => 6,
+            Self::ProjectRootRequired
+            | Self::MissingVariable { .. }
+            | Self::InvalidProvider { .. }
+            | Self::ConfigError { .. } => 7,
+            Self::HookFailed { .. } => 8,
+            Self::RegistryError { .. } | Self::NotInRegistry { .. } => 9,
+            Self::IoError { .. } => 1,
+        }
+    }
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map(|s| format!(" (did you mean `{s}`?)"))
+        .unwrap_or_default()
+}
+
+fn at_requirement(requirement: &Option<String>) -> String {
+    requirement
+        .as_ref()
+        .map(|r| format!(" at {r}"))
+        .unwrap_or_default()
+}
@@ -0,0 +1,80 @@
+#[cfg(feature = "native")]
+use std::fs;
+#[cfg(feature = "native")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "native")]
+use crate::error::{InstallerError, Result};
+#[cfg(feature = "native")]
+use crate::parser::skill_dir_name;
+#[cfg(feature = "native")]
+use crate::types::InstallStats;
+use crate::types::ParsedSkill;
+
+pub(crate) const FLAT_MARKER: &str = "<!-- installed by skillinstaller";
+
+#[cfg(feature = "native")]
+/// Where a flat-layout provider gets the skill: `<target_dir>/<skill-name>.md`.
+pub(crate) fn flat_destination(target_dir: &Path, skill_name: &str) -> PathBuf {
+    target_dir.join(format!("{}.md", skill_dir_name(skill_name)))
+}
+
+/// The skill a file rendered by `render_flat_skill` came from.
+#[cfg(feature = "native")]
+pub(crate) fn flat_skill_name(rendered: &str) -> Option<&str> {
+    let rest = rendered
+        .strip_prefix(FLAT_MARKER)?
+        .strip_prefix(" from skill '")?;
+    rest.split_once("';").map(|(name, _)| name)
+}
+
+/// Renders a skill as a single instructions file for agents without skills directories.
+pub fn render_flat_skill(skill: &ParsedSkill) -> String {
+    let mut out = format!(
+        "{FLAT_MARKER} from skill '{}'; edits are overwritten on reinstall -->\n\n# {}\n\n",
+        skill.name,
+        skill.display_name.as_deref().unwrap_or(&skill.name)
+    );
+    if let Some(description) = &skill.description {
+        out.push_str(description.trim());
+        out.push_str("\n\n");
+    }
+    out.push_str(skill.body.trim());
+    out.push('\n');
+    out
+}
+
+#[cfg(feature = "native")]
+pub(crate) fn write_flat_skill(
+    skill: &ParsedSkill,
+    destination: &Path,
+    overwritten: bool,
+) -> Result<InstallStats> {
+    let content = render_flat_skill(skill);
+    let stats = InstallStats {
+        files: 1,
+        bytes: content.len() as u64,
+        overwritten,
+        up_to_date: false,
+    };
+
+    if overwritten && fs::read_to_string(destination).is_ok_and(|existing| existing == content) {
+        return Ok(InstallStats {
+            overwritten: false,
+            up_to_date: true,
+            ..stats
+        });
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|err| InstallerError::IoError {
+            path: parent.to_path_buf(),
+            message: err.to_string(),
+        })?;
+    }
+    fs::write(destination, content).map_err(|err| InstallerError::IoError {
+        path: destination.to_path_buf(),
+        message: err.to_string(),
+    })?;
+    Ok(stats)
+}
@@ -0,0 +1,168 @@
+use std::fs;
+use std::io;
+use std::path::{Component, Path};
+
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::error::{InstallerError, Result};
+use crate::install::{check_symlink_escape, is_ignored_entry};
+use crate::parser::resolve_local_skill_root;
+use crate::receipt::RECEIPT_FILE_NAME;
+use crate::types::{InstallOptions, SkillSource, SymlinkPolicy};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TreeDigest {
+    pub digest: String,
+    pub files: usize,
+    pub bytes: u64,
+    pub paths: Vec<String>,
+}
+
+enum Entry<'a> {
+    File(&'a Path),
+    Link(&'a Path),
+    Bytes(&'a [u8]),
+}
+
+pub(crate) fn hash_source(source: &SkillSource, options: &InstallOptions) -> Result<TreeDigest> {
+    match source {
+        SkillSource::LocalPath(path) => {
+            let root = resolve_local_skill_root(path)?;
+            hash_dir(&root, options)
+        }
+        SkillSource::Embedded(embedded) => {
+            let mut entries = vec![("SKILL.md".to_string(), embedded.skill_md.as_bytes())];
+            for (relative, bytes) in &embedded.files {
+                entries.push((relative_key(relative), bytes.as_slice()));
+            }
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+            let mut digest = TreeHasher::default();
+            for (key, bytes) in entries {
+                digest.add(&key, Entry::Bytes(bytes))?;
+            }
+            Ok(digest.finish())
+        }
+    }
+}
+
+pub(crate) fn hash_dir(root: &Path, options: &InstallOptions) -> Result<TreeDigest> {
+    let preserve_links = options.symlinks == SymlinkPolicy::Preserve;
+    let canonical_root = fs::canonicalize(root).map_err(|err| InstallerError::IoError {
+        path: root.to_path_buf(),
+        message: err.to_string(),
+    })?;
+    let walker = WalkDir::new(root)
+        .follow_links(!preserve_links)
+        .into_iter()
+        .filter_entry(|entry| options.include_ignored || !is_ignored_entry(entry));
+
+    let mut entries = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(|err| InstallerError::IoError {
+            path: root.to_path_buf(),
+            message: err.to_string(),
+        })?;
+        check_symlink_escape(&entry, &canonical_root, options)?;
+        if entry.file_type().is_dir() || is_receipt_entry(&entry) {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .map_err(|err| InstallerError::IoError {
+                path: entry.path().to_path_buf(),
+                message: err.to_string(),
+            })?;
+        entries.push((relative_key(relative), entry));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut digest = TreeHasher::default();
+    for (key, entry) in &entries {
+        if entry.file_type().is_symlink() {
+            digest.add(key, Entry::Link(entry.path()))?;
+        } else {
+            digest.add(key, Entry::File(entry.path()))?;
+        }
+    }
+    Ok(digest.finish())
+}
+
+fn is_receipt_entry(entry: &walkdir::DirEntry) -> bool {
+    entry.depth() == 1 && entry.file_name() == RECEIPT_FILE_NAME
+}
+
+fn relative_key(path: &Path) -> String {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[derive(Default)]
+struct TreeHasher {
+    hasher: Sha256,
+    files: usize,
+    bytes: u64,
+    paths: Vec<String>,
+}
+
+impl TreeHasher {
+    fn add(&mut self, key: &str, entry: Entry<'_>) -> Result<()> {
+        self.hasher.update(key.as_bytes());
+        self.hasher.update([0]);
+        match entry {
+            Entry::Bytes(bytes) => {
+                self.hasher.update(b"f");
+                self.hasher.update((bytes.len() as u64).to_le_bytes());
+                self.hasher.update(bytes);
+                self.bytes += bytes.len() as u64;
+            }
+            Entry::File(path) => {
+                let io_err = |err: io::Error| InstallerError::IoError {
+                    path: path.to_path_buf(),
+                    message: err.to_string(),
+                };
+                let len = fs::metadata(path).map_err(io_err)?.len();
+                self.hasher.update(b"f");
+                self.hasher.update(len.to_le_bytes());
+                let mut file = fs::File::open(path).map_err(io_err)?;
+                io::copy(&mut file, &mut self.hasher).map_err(io_err)?;
+                self.bytes += len;
+            }
+            Entry::Link(path) => {
+                let target = fs::read_link(path).map_err(|err| InstallerError::IoError {
+                    path: path.to_path_buf(),
+                    message: err.to_string(),
+                })?;
+                let target = target.to_string_lossy();
+                self.hasher.update(b"l");
+                self.hasher.update((target.len() as u64).to_le_bytes());
+                self.hasher.update(target.as_bytes());
+            }
+        }
+        self.files += 1;
+        self.paths.push(key.to_string());
+        Ok(())
+    }
+
+    fn finish(self) -> TreeDigest {
+        let digest = self
+            .hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        TreeDigest {
+            digest: format!("sha256:{digest}"),
+            files: self.files,
+            bytes: self.bytes,
+            paths: self.paths,
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::{InstallerError, Result};
+use crate::types::{ParsedSkill, ProviderId, Scope};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HookStage {
+    PreInstall,
+    PostInstall,
+}
+
+impl HookStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookStage::PreInstall => "pre-install",
+            HookStage::PostInstall => "post-install",
+        }
+    }
+}
+
+pub(crate) struct HookContext<'a> {
+    pub skill: &'a ParsedSkill,
+    pub provider: ProviderId,
+    pub scope: Scope,
+    pub destination: &'a Path,
+}
+
+pub(crate) const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+const MAX_OUTPUT_LINES: usize = 20;
+
+/// How long to keep reading output once the hook is gone. A descendant that left the hook's
+/// process group can hold the pipes open indefinitely.
+const OUTPUT_DRAIN_GRACE: Duration = Duration::from_millis(500);
+
+/// Variables passed through from the installer's environment; everything else is dropped.
+const INHERITED_ENV: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USERPROFILE",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "LANG",
+    "LC_ALL",
+    "SYSTEMROOT",
+    "COMSPEC",
+    "PATHEXT",
+];
+
+/// Runs `script` (relative to `skill_dir`) sandboxed: the working directory is
+/// pinned to `skill_dir`, the environment is reduced to [`INHERITED_ENV`] plus
+/// `SKILL_INSTALLER_*` variables, and the hook (with anything it spawned) is
+/// killed after `timeout`. Returns the captured output, one entry per line.
+pub(crate) fn run_hook(
+    stage: HookStage,
+    script: &str,
+    skill_dir: &Path,
+    context: &HookContext<'_>,
+    timeout: Duration,
+) -> Result<Vec<String>> {
+    trace_span!(DEBUG, "hook", stage = stage.as_str(), script);
+    let hook_error = |message: String| InstallerError::HookFailed {
+        hook: stage.as_str().to_string(),
+        message,
+    };
+
+    let script_path = skill_dir.join(script);
+    if !script_path.is_file() {
+        return Err(hook_error(format!(
+            "script not found: {}",
+            script_path.display()
+        )));
+    }
+
+    let mut command = hook_command(&script_path);
+    command
+        .current_dir(skill_dir)
+        .env_clear()
+        .envs(
+            INHERITED_ENV
+                .iter()
+                .filter_map(|key| Some((*key, std::env::var_os(key)?))),
+        )
+        .env("SKILL_INSTALLER_HOOK", stage.as_str())
+        .env("SKILL_INSTALLER_SKILL_NAME", &context.skill.name)
+        .env("SKILL_INSTALLER_PROVIDER", context.provider.as_str())
+        .env("SKILL_INSTALLER_SCOPE", context.scope.as_str())
+        .env("SKILL_INSTALLER_DESTINATION", context.destination)
+        .env("SKILL_INSTALLER_SKILL_DIR", skill_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    isolate_process_group(&mut command);
+
+    let mut child = command.spawn().map_err(|err| hook_error(err.to_string()))?;
+    let (sender, receiver) = mpsc::channel();
+    let stdout = child
+        .stdout
+        .take()
+        .map(|pipe| forward_lines(pipe, sender.clone()));
+    let stderr = child.stderr.take().map(|pipe| forward_lines(pipe, sender));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child
+            .try_wait()
+            .map_err(|err| hook_error(err.to_string()))?
+        {
+            Some(status) => break Some(status),
+            None if Instant::now() >= deadline => break None,
+            None => thread::sleep(Duration::from_millis(20)),
+        }
+    };
+
+    // Whatever the hook left running in the background goes down with it.
+    kill_process_group(&mut child);
+    let _ = child.wait();
+    let readers = [stdout, stderr].into_iter().flatten().collect::<Vec<_>>();
+    let drain_deadline = Instant::now() + OUTPUT_DRAIN_GRACE;
+    while readers.iter().any(|reader| !reader.is_finished()) && Instant::now() < drain_deadline {
+        thread::sleep(Duration::from_millis(10));
+    }
+    // Readers still blocked on a pipe held open by an escaped descendant are left detached;
+    // they exit once it closes, and their later lines go nowhere.
+    drop(readers);
+    let output = receiver
+        .try_iter()
+        .filter(|line| !line.trim().is_empty())
+        .take(MAX_OUTPUT_LINES)
+        .map(|line| format!("{} hook: {line}", stage.as_str()))
+        .collect::<Vec<_>>();
+
+    match status {
+        None => Err(hook_error(format!(
+            "{script} timed out after {}s",
+            timeout.as_secs()
+        ))),
+        Some(status) if !status.success() => {
+            let mut message = format!("{script} exited with {status}");
+            for line in &output {
+                message.push_str("\n  ");
+                message.push_str(line);
+            }
+            Err(hook_error(message))
+        }
+        Some(_) => Ok(output),
+    }
+}
+
+fn forward_lines(
+    pipe: impl Read + Send + 'static,
+    sender: mpsc::Sender<String>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(std::io::Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+#[cfg(unix)]
+fn isolate_process_group(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(windows)]
+fn isolate_process_group(_command: &mut Command) {}
+
+#[cfg(unix)]
+fn kill_process_group(child: &mut Child) {
+    // The child leads its own group (see `isolate_process_group`), so its pid is the group id.
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+#[cfg(windows)]
+fn kill_process_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(unix)]
+fn hook_command(script_path: &Path) -> Command {
+    use std::os::unix::fs::PermissionsExt;
+
+    let executable = std::fs::metadata(script_path)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false);
+    if executable {
+        Command::new(script_path)
+    } else {
+        let mut command = Command::new("sh");
+        command.arg(script_path);
+        command
+    }
+}
+
+#[cfg(windows)]
+fn hook_command(script_path: &Path) -> Command {
+    Command::new(script_path)
+}
@@ -1,16 +1,34 @@
-use std::collections::HashSet;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use walkdir::WalkDir;
 
 use crate::error::{InstallerError, Result};
-use crate::parser::{parse_skill, resolve_local_skill_root};
-use crate::providers::{normalize_providers, resolve_provider_dir};
+use crate::flat::{flat_destination, flat_skill_name, write_flat_skill};
+use crate::hash::{hash_dir, hash_source, TreeDigest};
+use crate::hooks::{run_hook, HookContext, HookStage, DEFAULT_HOOK_TIMEOUT};
+use crate::installed::installed_at;
+use crate::lint::provider_limit_warnings;
+use crate::observer::{InstallEvent, SkipReason};
+use crate::parser::{parse_skill, parse_source, resolve_local_skill_root, skill_dir_name};
+use crate::providers::{
+    deprecation_warnings, normalize_providers, provider_capabilities, resolve_provider_dir,
+};
+use crate::receipt::{read_receipt, write_receipt, InstallReceipt};
+use crate::render::OutputFormat;
 use crate::types::{
-    EmbeddedSkill, InstallMethod, InstallRequest, InstallResult, InstallTarget, ProviderId, Scope,
-    SkillSource,
+    EmbeddedSkill, InstallMethod, InstallOptions, InstallPlan, InstallRequest, InstallResult,
+    InstallStats, InstallTarget, ParsedSkill, PlannedAction, PlannedTarget, ProviderId, Scope,
+    SkillHooks, SkillSource, SymlinkPolicy,
 };
+use crate::variables::{render_skill_md, render_variables, resolve_variables};
+
+type StagingHook<'a> = &'a dyn Fn(&Path) -> Result<()>;
+
+const IGNORED_ENTRIES: &[&str] = &[".git", ".hg", ".DS_Store", "node_modules"];
 
 pub fn resolve_install_target(
     requested_provider: ProviderId,
@@ -28,34 +46,297 @@ pub fn resolve_install_target(
         requested_provider,
         target_provider,
         target_dir,
+        stats: InstallStats::default(),
     })
 }
 
+fn collect_targets(
+    request: &InstallRequest,
+    providers: Vec<ProviderId>,
+) -> Result<Vec<InstallTarget>> {
+    // Checked against what was asked for, since `.agents` providers are normalized away.
+    if let Some(provider) = request
+        .providers
+        .iter()
+        .find(|p| !provider_capabilities(**p).supports_scope(request.scope))
+    {
+        return Err(InstallerError::UnsupportedScope {
+            provider: provider.as_str().to_string(),
+            scope: request.scope.as_str().to_string(),
+        });
+    }
+
+    let mut targets = Vec::new();
+    for provider in providers {
+        targets.push(resolve_install_target(
+            provider,
+            request.scope,
+            request.project_root.as_deref(),
+        )?);
+    }
+    targets.extend(request.options.target_dirs.iter().map(|dir| InstallTarget {
+        requested_provider: ProviderId::TargetDir,
+        target_provider: ProviderId::TargetDir,
+        target_dir: dir.clone(),
+        stats: InstallStats::default(),
+    }));
+    Ok(targets)
+}
+
 pub fn print_install_result(result: &InstallResult) {
-    println!("installed skill: {}", result.skill_name);
-
-    for target in &result.installed_targets {
-        println!(
-            "  {} -> {} ({})",
-            target.requested_provider.as_str(),
-            target.target_provider.as_str(),
-            target.target_dir.display()
-        );
+    print!("{}", result.render(OutputFormat::Text));
+}
+
+pub fn install(request: InstallRequest) -> Result<InstallResult> {
+    trace_span!(
+        INFO,
+        "install",
+        scope = request.scope.as_str(),
+        method = ?request.method,
+        providers = request.providers.len()
+    );
+    let started = Instant::now();
+    execute(plan(request)?, started, &mut HashSet::new())
+}
+
+/// Resolves what `install` would do without writing anything, e.g. for a confirmation step.
+pub fn plan(request: InstallRequest) -> Result<InstallPlan> {
+    let parsed = parse_source(&request.source, request.options.slugify_name)?;
+    plan_parsed(request, parsed)
+}
+
+/// Installs a plan's `targets` as they are, so a caller can drop or change targets first.
+/// An `Overwrite` stands; destinations that appeared after planning are re-checked.
+pub fn execute_plan(plan: InstallPlan) -> Result<InstallResult> {
+    execute(plan, Instant::now(), &mut HashSet::new())
+}
+
+pub fn install_many(requests: Vec<InstallRequest>) -> Vec<Result<InstallResult>> {
+    trace_span!(INFO, "install_many", requests = requests.len());
+    let mut parsed_sources: HashMap<(PathBuf, bool), ParsedSkill> = HashMap::new();
+    let mut claimed = HashSet::new();
+
+    requests
+        .into_iter()
+        .map(|request| {
+            let started = Instant::now();
+            let slugify = request.options.slugify_name;
+            let parsed = match &request.source {
+                SkillSource::LocalPath(path) => {
+                    let key = (resolve_local_skill_root(path)?, slugify);
+                    match parsed_sources.get(&key) {
+                        Some(parsed) => parsed.clone(),
+                        None => {
+                            let parsed = parse_source(&request.source, slugify)?;
+                            parsed_sources.insert(key, parsed.clone());
+                            parsed
+                        }
+                    }
+                }
+                SkillSource::Embedded(_) => parse_source(&request.source, slugify)?,
+            };
+            execute(plan_parsed(request, parsed)?, started, &mut claimed)
+        })
+        .collect()
+}
+
+pub(crate) fn plan_parsed(
+    mut request: InstallRequest,
+    mut parsed: ParsedSkill,
+) -> Result<InstallPlan> {
+    let mut warnings = Vec::new();
+    if !parsed.supports_current_platform() {
+        if !request.options.ignore_platform {
+            return Err(InstallerError::UnsupportedPlatform {
+                skill: parsed.name,
+                platform: std::env::consts::OS.to_string(),
+                supported: parsed.platforms.join(", "),
+            });
+        }
+        warnings.push(format!(
+            "skill '{}' supports {}, not {}; installed anyway",
+            parsed.name,
+            parsed.platforms.join(", "),
+            std::env::consts::OS
+        ));
+    }
+    // Resolved values replace the caller's, so every target renders the same text.
+    request.options.variables = resolve_variables(&parsed, &request.options)?;
+    parsed.body = render_variables(&parsed.body, &request.options.variables);
+
+    let requested = request.providers.len();
+    request.providers.retain(|provider| {
+        let allowed = parsed.providers.allows(*provider);
+        if !allowed {
+            warnings.push(format!(
+                "skill '{}' declares provider '{}' unsupported; skipped",
+                parsed.name,
+                provider.as_str()
+            ));
+        }
+        allowed
+    });
+    if requested > 0 && request.providers.is_empty() && request.options.target_dirs.is_empty() {
+        return Err(InstallerError::IncompatibleProviders { skill: parsed.name });
+    }
+    warnings.extend(provider_limit_warnings(&parsed, &request.providers));
+
+    let (providers, normalized_providers) = normalize_providers(&request.providers);
+    let mut targets = Vec::new();
+    let mut seen = HashSet::new();
+    let mut shared = None;
+    if request.method == InstallMethod::Symlink {
+        let universal = resolve_install_target(
+            ProviderId::Universal,
+            request.scope,
+            request.project_root.as_deref(),
+        )?;
+        let destination = universal.target_dir.join(skill_dir_name(&parsed.name));
+        check_collisions(&destination, &parsed.name)?;
+        let action = planned_action(&request, &parsed, &destination, PlannedAction::Create)?;
+        seen.insert(destination.clone());
+        shared = Some(destination.clone());
+        targets.push(PlannedTarget {
+            requested_provider: ProviderId::Universal,
+            target_provider: ProviderId::Universal,
+            destination,
+            action,
+        });
+    }
+
+    for target in collect_targets(&request, providers)? {
+        let provider = target.requested_provider;
+        let capabilities = provider_capabilities(provider);
+        let destination = if capabilities.flat_layout {
+            flat_destination(&target.target_dir, &parsed.name)
+        } else {
+            target.target_dir.join(skill_dir_name(&parsed.name))
+        };
+        if shared.as_ref() == Some(&destination) {
+            continue;
+        }
+
+        let action = if !seen.insert(destination.clone()) {
+            PlannedAction::SkipDuplicate
+        } else {
+            check_collisions(&destination, &parsed.name)?;
+            let fresh = if copies_payload(&request, provider) {
+                PlannedAction::Create
+            } else {
+                PlannedAction::Link
+            };
+            planned_action(&request, &parsed, &destination, fresh)?
+        };
+        targets.push(PlannedTarget {
+            requested_provider: provider,
+            target_provider: target.target_provider,
+            destination,
+            action,
+        });
     }
 
-    if !result.warnings.is_empty() {
-        println!("warnings:");
-        for w in &result.warnings {
-            println!("  - {w}");
+    let conflicts = targets
+        .iter()
+        .filter(|t| t.action == PlannedAction::Conflict)
+        .map(|t| t.destination.clone())
+        .collect();
+    let mut create_dirs = BTreeSet::new();
+    for target in &targets {
+        if !matches!(target.action, PlannedAction::Create | PlannedAction::Link) {
+            continue;
+        }
+        for dir in target.destination.ancestors().skip(1) {
+            if dir.as_os_str().is_empty() || dir.exists() {
+                break;
+            }
+            create_dirs.insert(dir.to_path_buf());
         }
     }
+
+    let mut preview = warnings.clone();
+    preview.extend(parsed.warnings.iter().cloned());
+    preview.extend(skipped_hooks_warning(&parsed, &request.options));
+    preview.extend(deprecation_warnings(&request.providers));
+    preview.extend(normalized_warnings(&normalized_providers));
+
+    Ok(InstallPlan {
+        skill_name: parsed.name.clone(),
+        skill_version: parsed.version.clone(),
+        targets,
+        conflicts,
+        normalized_providers,
+        create_dirs: create_dirs.into_iter().collect(),
+        warnings: preview,
+        request,
+        skill: parsed,
+        setup_warnings: warnings,
+    })
 }
 
-pub fn install(request: InstallRequest) -> Result<InstallResult> {
-    match request.method {
-        InstallMethod::Copy => install_copy(request),
-        InstallMethod::Symlink => install_symlink(request),
+fn planned_action(
+    request: &InstallRequest,
+    parsed: &ParsedSkill,
+    destination: &Path,
+    fresh: PlannedAction,
+) -> Result<PlannedAction> {
+    if fs::symlink_metadata(destination).is_err() {
+        return Ok(fresh);
     }
+    match keep_existing(request, parsed, destination) {
+        Ok(None) => Ok(PlannedAction::Overwrite),
+        Ok(Some(_)) => Ok(PlannedAction::Keep),
+        Err(InstallerError::AlreadyExists { .. }) => Ok(PlannedAction::Conflict),
+        Err(err) => Err(err),
+    }
+}
+
+fn execute(
+    plan: InstallPlan,
+    started: Instant,
+    claimed: &mut HashSet<PathBuf>,
+) -> Result<InstallResult> {
+    let InstallPlan {
+        request,
+        skill: parsed,
+        targets,
+        setup_warnings: mut warnings,
+        ..
+    } = plan;
+    // One walk of the source serves the file count, the receipt, and up-to-date checks.
+    let digest = hash_source(&request.source, &request.options)?;
+    request
+        .options
+        .observers
+        .emit(InstallEvent::SourceResolved {
+            skill: parsed.name.clone(),
+            version: parsed.version.clone(),
+            files: digest.files,
+        });
+
+    let observers = request.options.observers.clone();
+    let mut result = match request.method {
+        InstallMethod::Copy => install_copy(request, parsed, targets, &digest, started, claimed)?,
+        InstallMethod::Symlink => {
+            install_symlink(request, parsed, targets, &digest, started, claimed)?
+        }
+    };
+    warnings.append(&mut result.warnings);
+    result.warnings = warnings;
+    trace_event!(
+        info,
+        skill = %result.skill_name,
+        targets = result.installed_targets.len(),
+        skipped = result.skipped_duplicates.len(),
+        warnings = result.warnings.len(),
+        elapsed_ms = result.elapsed.as_millis() as u64,
+        "installed skill"
+    );
+    observers.emit(InstallEvent::Completed {
+        skill: result.skill_name.clone(),
+        targets: result.installed_targets.len(),
+        elapsed: result.elapsed,
+    });
+    Ok(result)
 }
 
 pub fn find_existing_destinations(
@@ -65,125 +346,320 @@ pub fn find_existing_destinations(
     project_root: Option<&Path>,
 ) -> Result<Vec<PathBuf>> {
     let parsed = parse_skill(source)?;
+    existing_destinations(&parsed.name, providers, scope, project_root)
+}
+
+pub(crate) fn existing_destinations(
+    skill_name: &str,
+    providers: &[ProviderId],
+    scope: Scope,
+    project_root: Option<&Path>,
+) -> Result<Vec<PathBuf>> {
     let (targets, _) = normalize_providers(providers);
 
     let mut existing = Vec::new();
     let mut seen = HashSet::new();
 
     for provider in targets {
-        let target = resolve_install_target(provider, scope, project_root)?;
-        let destination = target.target_dir.join(&parsed.name);
-        if seen.insert(destination.clone()) && destination.exists() {
-            existing.push(destination);
+        if let Some((destination, _)) = installed_at(provider, scope, project_root, skill_name)? {
+            if seen.insert(destination.clone()) {
+                existing.push(destination);
+            }
         }
     }
 
     Ok(existing)
 }
 
-fn install_copy(request: InstallRequest) -> Result<InstallResult> {
-    let parsed = parse_skill(&request.source)?;
-    let (providers, normalized_providers) = normalize_providers(&request.providers);
+fn install_copy(
+    request: InstallRequest,
+    parsed: ParsedSkill,
+    targets: Vec<PlannedTarget>,
+    source_digest: &TreeDigest,
+    started: Instant,
+    claimed: &mut HashSet<PathBuf>,
+) -> Result<InstallResult> {
+    let (_, normalized_providers) = normalize_providers(&request.providers);
 
     let mut installed_targets = Vec::new();
     let mut skipped_duplicates = Vec::new();
     let mut warnings = Vec::new();
     let mut seen_paths = HashSet::new();
+    warnings.extend(parsed.warnings.iter().cloned());
+    warnings.extend(skipped_hooks_warning(&parsed, &request.options));
+    warnings.extend(deprecation_warnings(&request.providers));
+    let receipt = InstallReceipt::new(&parsed, &request.source, source_digest);
 
-    for provider in providers {
-        let target =
-            resolve_install_target(provider, request.scope, request.project_root.as_deref())?;
-        let destination = target.target_dir.join(&parsed.name);
+    for target in targets {
+        let provider = target.requested_provider;
+        let flat = provider_capabilities(provider).flat_layout;
+        let destination = target.destination.clone();
+        trace_span!(
+            DEBUG,
+            "install_target",
+            provider = provider.as_str(),
+            destination = %destination.display()
+        );
+        let observers = &request.options.observers;
+        observers.emit(InstallEvent::ProviderResolved {
+            provider,
+            destination: destination.clone(),
+        });
 
-        if !seen_paths.insert(destination.clone()) {
+        if target.action == PlannedAction::SkipDuplicate || !seen_paths.insert(destination.clone())
+        {
+            observers.emit(skipped(provider, &destination, SkipReason::Duplicate));
+            skipped_duplicates.push(destination);
+            continue;
+        }
+        if !claimed.insert(destination.clone()) {
+            observers.emit(skipped(provider, &destination, SkipReason::ClaimedByBatch));
+            warnings.push(claimed_warning(&destination));
             skipped_duplicates.push(destination);
             continue;
         }
 
-        if destination.exists() && !request.force {
-            return Err(InstallerError::AlreadyExists { path: destination });
+        check_collisions(&destination, &parsed.name)?;
+        let overwritten = destination.exists();
+        if overwritten {
+            if let Some(reason) = keep_planned(&request, &parsed, &target)? {
+                observers.emit(skipped(provider, &destination, SkipReason::NotNewer));
+                warnings.push(reason);
+                installed_targets.push(kept_target(&target));
+                continue;
+            }
         }
 
-        copy_source_to_destination(&request.source, &destination)?;
+        if flat {
+            installed_targets.push(install_flat(
+                &parsed,
+                &target,
+                overwritten,
+                source_digest,
+                &mut warnings,
+            )?);
+            continue;
+        }
 
-        installed_targets.push(InstallTarget {
-            requested_provider: provider,
-            target_provider: target.target_provider,
-            target_dir: destination,
-        });
-    }
+        if overwritten {
+            if let Some(stats) = unchanged_payload(&request, &destination, source_digest) {
+                observers.emit(skipped(provider, &destination, SkipReason::UpToDate));
+                ensure_receipt(&destination, &receipt)?;
+                installed_targets.push(installed_target(&target, stats));
+                continue;
+            }
+        }
 
-    for (from, to) in &normalized_providers {
-        warnings.push(format!(
-            "provider '{}' normalized to '{}' shared .agents target",
-            from.as_str(),
-            to.as_str()
+        let stats = copy_for_provider(
+            &request,
+            &parsed,
+            provider,
+            &destination,
+            &receipt,
+            &mut warnings,
+        )?;
+
+        installed_targets.push(installed_target(
+            &target,
+            InstallStats {
+                overwritten,
+                ..stats
+            },
         ));
     }
 
+    warnings.extend(normalized_warnings(&normalized_providers));
+
     Ok(InstallResult {
         skill_name: parsed.name,
+        skill_version: parsed.version,
         installed_targets,
         normalized_providers,
         skipped_duplicates,
         warnings,
+        elapsed: started.elapsed(),
     })
 }
 
-fn install_symlink(request: InstallRequest) -> Result<InstallResult> {
-    let parsed = parse_skill(&request.source)?;
-    let universal_target = resolve_install_target(
-        ProviderId::Universal,
-        request.scope,
-        request.project_root.as_deref(),
-    )?;
-    let universal_destination = universal_target.target_dir.join(&parsed.name);
+fn install_symlink(
+    request: InstallRequest,
+    parsed: ParsedSkill,
+    mut targets: Vec<PlannedTarget>,
+    source_digest: &TreeDigest,
+    started: Instant,
+    claimed: &mut HashSet<PathBuf>,
+) -> Result<InstallResult> {
     let (providers, normalized_providers) = normalize_providers(&request.providers);
 
     let mut installed_targets = Vec::new();
     let mut skipped_duplicates = Vec::new();
     let mut warnings = Vec::new();
     let mut seen_paths = HashSet::new();
+    warnings.extend(parsed.warnings.iter().cloned());
+    warnings.extend(skipped_hooks_warning(&parsed, &request.options));
+    warnings.extend(deprecation_warnings(&request.providers));
 
-    if universal_destination.exists() {
-        if !request.force {
-            return Err(InstallerError::AlreadyExists {
-                path: universal_destination.clone(),
-            });
+    let receipt = InstallReceipt::new(&parsed, &request.source, source_digest);
+
+    // The plan puts the shared copy first. A plan edited to drop it gets a copy per target.
+    let shared = match targets.first() {
+        Some(first) if first.requested_provider == ProviderId::Universal => Some(targets.remove(0)),
+        _ => None,
+    };
+    let mut payload_stats = InstallStats::default();
+    if let Some(shared) = &shared {
+        let universal_destination = &shared.destination;
+        // A shared copy installed by an earlier request in the same batch is
+        // linked to as-is rather than rewritten.
+        let universal_claimed = !claimed.insert(universal_destination.clone());
+        if !universal_claimed {
+            check_collisions(universal_destination, &parsed.name)?;
         }
-        remove_path(&universal_destination)?;
-    }
+        let universal_overwritten = universal_destination.exists();
+        let universal_kept = if universal_overwritten && !universal_claimed {
+            keep_planned(&request, &parsed, shared)?
+        } else {
+            None
+        };
 
-    copy_source_to_destination(&request.source, &universal_destination)?;
+        let unchanged = if universal_claimed {
+            warnings.push(claimed_warning(universal_destination));
+            skipped_duplicates.push(universal_destination.clone());
+            Some(InstallStats::default())
+        } else if let Some(reason) = universal_kept {
+            warnings.push(reason);
+            Some(InstallStats {
+                up_to_date: true,
+                ..InstallStats::default()
+            })
+        } else if universal_overwritten {
+            unchanged_payload(&request, universal_destination, source_digest)
+        } else {
+            None
+        };
+        payload_stats = match unchanged {
+            Some(stats) if universal_claimed => stats,
+            Some(stats) => {
+                ensure_receipt(universal_destination, &receipt)?;
+                stats
+            }
+            None => {
+                if universal_overwritten {
+                    remove_path(universal_destination)?;
+                }
+                let stats = copy_for_provider(
+                    &request,
+                    &parsed,
+                    ProviderId::Universal,
+                    universal_destination,
+                    &receipt,
+                    &mut warnings,
+                )?;
+                InstallStats {
+                    overwritten: universal_overwritten,
+                    ..stats
+                }
+            }
+        };
 
-    seen_paths.insert(universal_destination.clone());
+        seen_paths.insert(universal_destination.clone());
+        if providers.contains(&ProviderId::Universal) {
+            installed_targets.push(installed_target(shared, payload_stats));
+        }
+    }
 
-    for provider in providers {
-        let target =
-            resolve_install_target(provider, request.scope, request.project_root.as_deref())?;
-        let destination = target.target_dir.join(&parsed.name);
-
-        if destination == universal_destination {
-            installed_targets.push(InstallTarget {
-                requested_provider: provider,
-                target_provider: target.target_provider,
-                target_dir: destination,
-            });
+    for target in targets {
+        let provider = target.requested_provider;
+        let flat = provider_capabilities(provider).flat_layout;
+        let destination = target.destination.clone();
+        trace_span!(
+            DEBUG,
+            "install_target",
+            provider = provider.as_str(),
+            destination = %destination.display()
+        );
+        let observers = &request.options.observers;
+        observers.emit(InstallEvent::ProviderResolved {
+            provider,
+            destination: destination.clone(),
+        });
+
+        if target.action == PlannedAction::SkipDuplicate || !seen_paths.insert(destination.clone())
+        {
+            observers.emit(skipped(provider, &destination, SkipReason::Duplicate));
+            skipped_duplicates.push(destination);
             continue;
         }
-
-        if !seen_paths.insert(destination.clone()) {
+        if !claimed.insert(destination.clone()) {
+            observers.emit(skipped(provider, &destination, SkipReason::ClaimedByBatch));
+            warnings.push(claimed_warning(&destination));
             skipped_duplicates.push(destination);
             continue;
         }
 
-        if destination.exists() {
-            if !request.force {
-                return Err(InstallerError::AlreadyExists { path: destination });
+        // The plan says whether a new target links or copies; an existing one copies when
+        // its payload is transformed or its provider cannot follow links.
+        let link_to = match target.action {
+            PlannedAction::Create => None,
+            PlannedAction::Link => shared.as_ref(),
+            _ if copies_payload(&request, provider) => None,
+            _ => shared.as_ref(),
+        }
+        .map(|shared| &shared.destination);
+        check_collisions(&destination, &parsed.name)?;
+        let overwritten = fs::symlink_metadata(&destination).is_ok();
+        if overwritten {
+            if let Some(reason) = keep_planned(&request, &parsed, &target)? {
+                observers.emit(skipped(provider, &destination, SkipReason::NotNewer));
+                warnings.push(reason);
+                installed_targets.push(kept_target(&target));
+                continue;
+            }
+            if !flat
+                && link_to.is_some_and(|shared| {
+                    fs::read_link(&destination).is_ok_and(|link| link == *shared)
+                })
+            {
+                // The existing link already points at the shared copy, whose
+                // stats describe what changed behind it.
+                installed_targets.push(installed_target(&target, payload_stats));
+                continue;
             }
-            remove_path(&destination)?;
+            if !flat {
+                remove_path(&destination)?;
+            }
+        }
+
+        if flat {
+            installed_targets.push(install_flat(
+                &parsed,
+                &target,
+                overwritten,
+                source_digest,
+                &mut warnings,
+            )?);
+            continue;
         }
 
+        let Some(shared) = link_to else {
+            let stats = copy_for_provider(
+                &request,
+                &parsed,
+                provider,
+                &destination,
+                &receipt,
+                &mut warnings,
+            )?;
+            installed_targets.push(installed_target(
+                &target,
+                InstallStats {
+                    overwritten,
+                    ..stats
+                },
+            ));
+            continue;
+        };
+
         if let Some(parent) = destination.parent() {
             fs::create_dir_all(parent).map_err(|err| InstallerError::IoError {
                 path: parent.to_path_buf(),
@@ -191,32 +667,313 @@ fn install_symlink(request: InstallRequest) -> Result<InstallResult> {
             })?;
         }
 
-        create_dir_symlink(&universal_destination, &destination)?;
-
-        installed_targets.push(InstallTarget {
-            requested_provider: provider,
-            target_provider: target.target_provider,
-            target_dir: destination,
+        create_dir_symlink(shared, &destination)?;
+        observers.emit(InstallEvent::Linked {
+            provider,
+            link: destination.clone(),
+            target: shared.clone(),
         });
-    }
 
-    for (from, to) in &normalized_providers {
-        warnings.push(format!(
-            "provider '{}' normalized to '{}' shared .agents target",
-            from.as_str(),
-            to.as_str()
+        installed_targets.push(installed_target(
+            &target,
+            InstallStats {
+                overwritten,
+                up_to_date: false,
+                ..payload_stats
+            },
         ));
     }
 
+    warnings.extend(normalized_warnings(&normalized_providers));
+
     Ok(InstallResult {
         skill_name: parsed.name,
+        skill_version: parsed.version,
         installed_targets,
         normalized_providers,
         skipped_duplicates,
         warnings,
+        elapsed: started.elapsed(),
+    })
+}
+
+/// Transformed payloads and providers that cannot follow links get a real copy.
+fn copies_payload(request: &InstallRequest, provider: ProviderId) -> bool {
+    let capabilities = provider_capabilities(provider);
+    request.method == InstallMethod::Copy
+        || capabilities.flat_layout
+        || !capabilities.supports_symlink
+        || request.options.transforms.get(provider).is_some()
+}
+
+/// Rejects destinations that already hold a different skill, e.g. `acme/review` where
+/// `acme--review` is installed, and names that differ only in case from an existing entry,
+/// which case-insensitive filesystems (macOS, Windows) would treat as the same skill.
+fn check_collisions(destination: &Path, skill_name: &str) -> Result<()> {
+    let existing = if destination.is_file() {
+        fs::read_to_string(destination)
+            .ok()
+            .and_then(|rendered| flat_skill_name(&rendered).map(String::from))
+    } else {
+        read_receipt(destination)
+            .ok()
+            .flatten()
+            .map(|receipt| receipt.name)
+    };
+    if let Some(existing) = existing.filter(|existing| existing != skill_name) {
+        return Err(InstallerError::NameCollision {
+            path: destination.to_path_buf(),
+            existing,
+        });
+    }
+
+    let (Some(parent), Some(name)) = (destination.parent(), destination.file_name()) else {
+        return Ok(());
+    };
+    let Ok(entries) = fs::read_dir(parent) else {
+        return Ok(());
+    };
+
+    let name = name.to_string_lossy();
+    let folded = name.to_lowercase();
+    for entry in entries.flatten() {
+        let existing = entry.file_name();
+        let existing = existing.to_string_lossy();
+        if existing != name && existing.to_lowercase() == folded {
+            return Err(InstallerError::CaseCollision {
+                path: destination.to_path_buf(),
+                existing: entry.path(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn install_flat(
+    parsed: &ParsedSkill,
+    target: &PlannedTarget,
+    overwritten: bool,
+    source_digest: &TreeDigest,
+    warnings: &mut Vec<String>,
+) -> Result<InstallTarget> {
+    let destination = &target.destination;
+    if overwritten && !destination.is_file() {
+        remove_path(destination)?;
+    }
+    let stats = write_flat_skill(parsed, destination, overwritten)?;
+    if source_digest.files > 1 {
+        warnings.push(format!(
+            "provider '{}' reads a single instructions file; only the SKILL.md content was installed",
+            target.requested_provider.as_str()
+        ));
+    }
+    Ok(installed_target(target, stats))
+}
+
+fn normalized_warnings(
+    normalized: &[(ProviderId, ProviderId)],
+) -> impl Iterator<Item = String> + '_ {
+    normalized.iter().map(|(from, to)| {
+        format!(
+            "provider '{}' normalized to '{}' shared .agents target",
+            from.as_str(),
+            to.as_str()
+        )
+    })
+}
+
+fn skipped(provider: ProviderId, destination: &Path, reason: SkipReason) -> InstallEvent {
+    InstallEvent::Skipped {
+        provider,
+        destination: destination.to_path_buf(),
+        reason,
+    }
+}
+
+fn claimed_warning(destination: &Path) -> String {
+    format!(
+        "{} already installed by an earlier request in this batch",
+        destination.display()
+    )
+}
+
+fn copy_for_provider(
+    request: &InstallRequest,
+    parsed: &ParsedSkill,
+    provider: ProviderId,
+    destination: &Path,
+    receipt: &InstallReceipt,
+    warnings: &mut Vec<String>,
+) -> Result<InstallStats> {
+    let transform = request.options.transforms.get(provider);
+    let hooks = if request.options.allow_hooks {
+        &parsed.hooks
+    } else {
+        &SkillHooks::default()
+    };
+    let context = HookContext {
+        skill: parsed,
+        provider,
+        scope: request.scope,
+        destination,
+    };
+
+    let timeout = request.options.hook_timeout.unwrap_or(DEFAULT_HOOK_TIMEOUT);
+    let hook_output = RefCell::new(Vec::new());
+
+    let templated = !parsed.variables.is_empty();
+    let adapt = |staging: &Path| -> Result<()> {
+        if templated {
+            render_skill_md(staging, &request.options.variables)?;
+        }
+        if let Some(transform) = transform {
+            transform.transform(provider, staging, parsed)?;
+        }
+        if let Some(script) = &hooks.pre_install {
+            let output = run_hook(HookStage::PreInstall, script, staging, &context, timeout)?;
+            hook_output.borrow_mut().extend(output);
+        }
+        Ok(())
+    };
+    let stats = copy_source_to_destination(
+        &request.source,
+        provider,
+        destination,
+        &request.options,
+        Some(&adapt),
+    )?;
+
+    if templated || transform.is_some() || hooks.pre_install.is_some() {
+        // The receipt describes what was actually installed, not the canonical payload.
+        let digest = hash_dir(destination, &request.options)?;
+        write_receipt(
+            destination,
+            &InstallReceipt {
+                hash: digest.digest,
+                files: digest.paths,
+                ..receipt.clone()
+            },
+        )?;
+    } else {
+        write_receipt(destination, receipt)?;
+    }
+
+    warnings.extend(hook_output.into_inner());
+    if let Some(script) = &hooks.post_install {
+        match run_hook(
+            HookStage::PostInstall,
+            script,
+            destination,
+            &context,
+            timeout,
+        ) {
+            Ok(output) => warnings.extend(output),
+            Err(err) => warnings.push(err.to_string()),
+        }
+    }
+
+    Ok(stats)
+}
+
+fn skipped_hooks_warning(parsed: &ParsedSkill, options: &InstallOptions) -> Option<String> {
+    (!options.allow_hooks && !parsed.hooks.is_empty()).then(|| {
+        format!(
+            "skill '{}' declares install hooks; they were not run (use --allow-hooks)",
+            parsed.name
+        )
+    })
+}
+
+fn unchanged_payload(
+    request: &InstallRequest,
+    destination: &Path,
+    source_digest: &TreeDigest,
+) -> Option<InstallStats> {
+    let is_real_dir = fs::symlink_metadata(destination).is_ok_and(|m| m.is_dir());
+    if !is_real_dir {
+        return None;
+    }
+
+    let unchanged = hash_dir(destination, &request.options)
+        .is_ok_and(|existing| existing.digest == source_digest.digest);
+
+    unchanged.then_some(InstallStats {
+        files: source_digest.files,
+        bytes: source_digest.bytes,
+        overwritten: false,
+        up_to_date: true,
+    })
+}
+
+/// Decides what happens to an existing destination: `Ok(None)` replaces it, `Ok(Some(reason))`
+/// keeps it because `upgrade` found it already at the source version or newer.
+fn keep_existing(
+    request: &InstallRequest,
+    parsed: &ParsedSkill,
+    destination: &Path,
+) -> Result<Option<String>> {
+    if request.force {
+        return Ok(None);
+    }
+    if !request.options.upgrade {
+        return Err(InstallerError::AlreadyExists {
+            path: destination.to_path_buf(),
+        });
+    }
+
+    let installed = read_receipt(destination)
+        .ok()
+        .flatten()
+        .and_then(|receipt| receipt.version)
+        .and_then(|raw| semver::Version::parse(&raw).ok());
+    Ok(match (installed, &parsed.version) {
+        (Some(installed), Some(available)) if installed >= *available => Some(format!(
+            "{} is already at {installed}; not replaced by {available}",
+            destination.display()
+        )),
+        _ => None,
     })
 }
 
+/// Whether to leave what is at a planned destination. The plan's `Overwrite` stands;
+/// anything else is re-checked, since the destination may have appeared after planning.
+fn keep_planned(
+    request: &InstallRequest,
+    parsed: &ParsedSkill,
+    target: &PlannedTarget,
+) -> Result<Option<String>> {
+    if target.action == PlannedAction::Overwrite {
+        return Ok(None);
+    }
+    keep_existing(request, parsed, &target.destination)
+}
+
+fn installed_target(target: &PlannedTarget, stats: InstallStats) -> InstallTarget {
+    InstallTarget {
+        requested_provider: target.requested_provider,
+        target_provider: target.target_provider,
+        target_dir: target.destination.clone(),
+        stats,
+    }
+}
+
+fn kept_target(target: &PlannedTarget) -> InstallTarget {
+    installed_target(
+        target,
+        InstallStats {
+            up_to_date: true,
+            ..InstallStats::default()
+        },
+    )
+}
+
+fn ensure_receipt(skill_dir: &Path, receipt: &InstallReceipt) -> Result<()> {
+    if matches!(read_receipt(skill_dir), Ok(Some(_))) {
+        return Ok(());
+    }
+    write_receipt(skill_dir, receipt)
+}
+
 fn remove_path(path: &Path) -> Result<()> {
     let metadata = fs::symlink_metadata(path).map_err(|err| InstallerError::IoError {
         path: path.to_path_buf(),
@@ -260,7 +1017,13 @@ fn create_dir_symlink(source: &Path, destination: &Path) -> Result<()> {
     })
 }
 
-fn copy_source_to_destination(source: &SkillSource, destination: &Path) -> Result<()> {
+fn copy_source_to_destination(
+    source: &SkillSource,
+    provider: ProviderId,
+    destination: &Path,
+    options: &InstallOptions,
+    adapt: Option<StagingHook<'_>>,
+) -> Result<InstallStats> {
     let parent = destination
         .parent()
         .ok_or_else(|| InstallerError::IoError {
@@ -294,13 +1057,25 @@ fn copy_source_to_destination(source: &SkillSource, destination: &Path) -> Resul
         message: err.to_string(),
     })?;
 
-    match source {
+    let on_file = |file: &Path, bytes: u64| {
+        options.observers.emit(InstallEvent::Copying {
+            provider,
+            file: file.to_path_buf(),
+            bytes,
+        })
+    };
+    let stats = match source {
         SkillSource::LocalPath(path) => {
             let root = resolve_local_skill_root(path)?;
-            copy_dir_recursive(&root, &staging)?;
+            copy_dir_recursive(&root, &staging, options, &on_file)?
         }
-        SkillSource::Embedded(embedded) => {
-            write_embedded(embedded, &staging)?;
+        SkillSource::Embedded(embedded) => write_embedded(embedded, &staging, &on_file)?,
+    };
+
+    if let Some(adapt) = adapt {
+        if let Err(err) = adapt(&staging) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(err);
         }
     }
 
@@ -311,15 +1086,26 @@ fn copy_source_to_destination(source: &SkillSource, destination: &Path) -> Resul
         })?;
     }
 
+    // Staging is a sibling of the destination, so this rename never crosses a
+    // filesystem boundary even when the provider directory is a mount.
     fs::rename(&staging, destination).map_err(|err| InstallerError::IoError {
         path: destination.to_path_buf(),
         message: err.to_string(),
     })?;
-
-    Ok(())
+    Ok(stats)
 }
 
-fn write_embedded(embedded: &EmbeddedSkill, destination: &Path) -> Result<()> {
+fn write_embedded(
+    embedded: &EmbeddedSkill,
+    destination: &Path,
+    on_file: &dyn Fn(&Path, u64),
+) -> Result<InstallStats> {
+    on_file(Path::new("SKILL.md"), embedded.skill_md.len() as u64);
+    let mut stats = InstallStats {
+        files: 1,
+        bytes: embedded.skill_md.len() as u64,
+        ..InstallStats::default()
+    };
     fs::write(destination.join("SKILL.md"), embedded.skill_md.as_bytes()).map_err(|err| {
         InstallerError::IoError {
             path: destination.join("SKILL.md"),
@@ -337,6 +1123,7 @@ fn write_embedded(embedded: &EmbeddedSkill, destination: &Path) -> Result<()> {
             });
         }
 
+        on_file(relative_path, bytes.len() as u64);
         let file_path = destination.join(relative_path);
         if let Some(parent) = file_path.parent() {
             fs::create_dir_all(parent).map_err(|err| InstallerError::IoError {
@@ -348,13 +1135,29 @@ fn write_embedded(embedded: &EmbeddedSkill, destination: &Path) -> Result<()> {
             path: file_path,
             message: err.to_string(),
         })?;
+        stats.files += 1;
+        stats.bytes += bytes.len() as u64;
     }
 
-    Ok(())
+    Ok(stats)
 }
 
-fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<()> {
-    for entry in WalkDir::new(source) {
+fn copy_dir_recursive(
+    source: &Path,
+    destination: &Path,
+    options: &InstallOptions,
+    on_file: &dyn Fn(&Path, u64),
+) -> Result<InstallStats> {
+    let mut stats = InstallStats::default();
+    let canonical_root = fs::canonicalize(source).map_err(|err| InstallerError::IoError {
+        path: source.to_path_buf(),
+        message: err.to_string(),
+    })?;
+    let walker = WalkDir::new(source)
+        .follow_links(options.symlinks != SymlinkPolicy::Preserve)
+        .into_iter()
+        .filter_entry(|entry| options.include_ignored || !is_ignored_entry(entry));
+    for entry in walker {
         let entry = entry.map_err(|err| InstallerError::IoError {
             path: source.to_path_buf(),
             message: err.to_string(),
@@ -373,8 +1176,24 @@ fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<()> {
             continue;
         }
 
+        check_symlink_escape(&entry, &canonical_root, options)?;
+
         let target = destination.join(relative);
-        if entry.file_type().is_dir() {
+        if entry.file_type().is_file() {
+            on_file(relative, entry.metadata().map(|m| m.len()).unwrap_or(0));
+        } else if !entry.file_type().is_dir() {
+            on_file(relative, 0);
+        }
+        if entry.file_type().is_symlink() {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|err| InstallerError::IoError {
+                    path: parent.to_path_buf(),
+                    message: err.to_string(),
+                })?;
+            }
+            recreate_symlink(entry.path(), &target)?;
+            stats.files += 1;
+        } else if entry.file_type().is_dir() {
             fs::create_dir_all(&target).map_err(|err| InstallerError::IoError {
                 path: target,
                 message: err.to_string(),
@@ -386,12 +1205,68 @@ fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<()> {
                     message: err.to_string(),
                 })?;
             }
-            fs::copy(entry.path(), &target).map_err(|err| InstallerError::IoError {
-                path: target,
-                message: err.to_string(),
-            })?;
+            stats.bytes +=
+                fs::copy(entry.path(), &target).map_err(|err| InstallerError::IoError {
+                    path: target,
+                    message: err.to_string(),
+                })?;
+            stats.files += 1;
         }
     }
 
+    Ok(stats)
+}
+
+pub(crate) fn check_symlink_escape(
+    entry: &walkdir::DirEntry,
+    canonical_root: &Path,
+    options: &InstallOptions,
+) -> Result<()> {
+    if !entry.path_is_symlink() || options.symlinks != SymlinkPolicy::RejectEscaping {
+        return Ok(());
+    }
+    let resolved = fs::canonicalize(entry.path()).map_err(|err| InstallerError::IoError {
+        path: entry.path().to_path_buf(),
+        message: err.to_string(),
+    })?;
+    if !resolved.starts_with(canonical_root) {
+        return Err(InstallerError::SymlinkEscapesSource {
+            path: entry.path().to_path_buf(),
+            target: resolved,
+        });
+    }
     Ok(())
 }
+
+fn recreate_symlink(link: &Path, destination: &Path) -> Result<()> {
+    let original = fs::read_link(link).map_err(|err| InstallerError::IoError {
+        path: link.to_path_buf(),
+        message: err.to_string(),
+    })?;
+
+    #[cfg(unix)]
+    let created = std::os::unix::fs::symlink(&original, destination);
+    #[cfg(windows)]
+    let created = if link.is_dir() {
+        std::os::windows::fs::symlink_dir(&original, destination)
+    } else {
+        std::os::windows::fs::symlink_file(&original, destination)
+    };
+
+    created.map_err(|err| InstallerError::IoError {
+        path: destination.to_path_buf(),
+        message: format!(
+            "failed to create symlink '{}' -> '{}': {err}",
+            destination.display(),
+            original.display()
+        ),
+    })
+}
+
+pub(crate) fn is_ignored_entry(entry: &walkdir::DirEntry) -> bool {
+    entry.depth() > 0
+        && entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| IGNORED_ENTRIES.contains(&name))
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::flat::{flat_destination, flat_skill_name};
+use crate::install::resolve_install_target;
+use crate::parser::skill_dir_name;
+use crate::providers::{provider_capabilities, supported_providers};
+use crate::receipt::read_receipt;
+use crate::schema::InstalledReport;
+use crate::types::{InstallMethod, InstalledSkill, ProviderId, Scope};
+
+/// Lists every provider and scope where `skill_name` is installed. Project scope is only
+/// checked when `project_root` is given.
+pub fn detect_installed(
+    skill_name: &str,
+    project_root: Option<&Path>,
+) -> Result<Vec<InstalledSkill>> {
+    let mut scopes = vec![Scope::User];
+    if project_root.is_some() {
+        scopes.push(Scope::Project);
+    }
+
+    let mut installed = Vec::new();
+    for scope in scopes {
+        for info in supported_providers() {
+            if !info.capabilities.supports_scope(scope) {
+                continue;
+            }
+            if let Some((path, method)) = installed_at(info.id, scope, project_root, skill_name)? {
+                let receipt = read_receipt(&path).ok().flatten();
+                let version = receipt
+                    .as_ref()
+                    .and_then(|receipt| receipt.version.as_deref())
+                    .and_then(|raw| semver::Version::parse(raw).ok());
+                installed.push(InstalledSkill {
+                    provider: info.id,
+                    scope,
+                    path,
+                    method,
+                    version,
+                    registry: receipt.and_then(|receipt| receipt.registry),
+                });
+            }
+        }
+    }
+    Ok(installed)
+}
+
+/// Every skill installed for any provider, in the user scope and, when `project_root` is
+/// given, the project, sorted by name.
+pub fn list_installed(project_root: Option<&Path>) -> Result<Vec<InstalledReport>> {
+    let mut scopes = vec![Scope::User];
+    if project_root.is_some() {
+        scopes.push(Scope::Project);
+    }
+
+    let mut names = BTreeSet::new();
+    for scope in scopes {
+        for info in supported_providers() {
+            if !info.capabilities.supports_scope(scope) {
+                continue;
+            }
+            let target = resolve_install_target(info.id, scope, project_root)?;
+            let Ok(entries) = fs::read_dir(&target.target_dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if info.capabilities.flat_layout {
+                    let rendered = fs::read_to_string(&path).unwrap_or_default();
+                    if let Some(skill) = flat_skill_name(&rendered) {
+                        names.insert(skill.to_string());
+                    }
+                } else if path.join("SKILL.md").is_file() && !name.ends_with(".bak") {
+                    // Namespaced skills are only recognizable from their receipt.
+                    let receipt = read_receipt(&path).ok().flatten();
+                    names.insert(receipt.map_or(name, |receipt| receipt.name));
+                }
+            }
+        }
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            Ok(InstalledReport {
+                installed: detect_installed(&name, project_root)?,
+                skill_name: name,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn installed_at(
+    provider: ProviderId,
+    scope: Scope,
+    project_root: Option<&Path>,
+    skill_name: &str,
+) -> Result<Option<(PathBuf, InstallMethod)>> {
+    let target = resolve_install_target(provider, scope, project_root)?;
+    let destination = if provider_capabilities(provider).flat_layout {
+        flat_destination(&target.target_dir, skill_name)
+    } else {
+        target.target_dir.join(skill_dir_name(skill_name))
+    };
+
+    let Ok(metadata) = fs::symlink_metadata(&destination) else {
+        return Ok(None);
+    };
+    let method = if metadata.file_type().is_symlink() {
+        InstallMethod::Symlink
+    } else {
+        InstallMethod::Copy
+    };
+    Ok(Some((destination, method)))
+}
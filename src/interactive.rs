@@ -1,44 +1,137 @@
 use std::collections::HashSet;
-use std::io::{self, IsTerminal};
-use std::path::Path;
-
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
-use ratatui::backend::CrosstermBackend;
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
-use ratatui::{Terminal, TerminalOptions, Viewport};
+use std::fmt::Write;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use crate::cli::{InstallSkillArgs, METHOD_ENV, PROVIDERS_ENV, SCOPE_ENV};
 use crate::error::{InstallerError, Result};
-use crate::install::{find_existing_destinations, install};
+use crate::install::{execute_plan, existing_destinations, plan_parsed, resolve_install_target};
+use crate::last_used::LastUsed;
+#[cfg(not(feature = "interactive"))]
+use crate::line_prompter::LinePrompter;
+use crate::locale::{fill, messages, set_locale, Locale};
+use crate::observer::InstallObserver;
+use crate::parser::{parse_source, skill_dir_name};
+use crate::prompter::{Prompter, ProviderPrompt};
 use crate::providers::{
-    detect_providers, is_agents_provider, parse_providers_csv, supported_providers,
+    configured_defaults, detect_providers, favorite_providers, is_agents_provider,
+    parse_providers_csv, provider_capabilities, provider_groups, rank_providers,
+    supported_providers, ProviderGroup,
 };
+#[cfg(feature = "interactive")]
+use crate::tui::TerminalPrompter;
 use crate::types::{
-    InstallMethod, InstallRequest, InstallResult, InstallSkillArgs, ProviderId, Scope, SkillSource,
+    DetectedProvider, DetectionConfidence, InstallMethod, InstallPlan, InstallRequest,
+    InstallResult, ParsedSkill, PlannedAction, PlannedTarget, ProviderId, Scope, SkillSource,
 };
+use crate::variables::configured_variable;
 
+/// Built with `InteractiveProviderSelectionOptions::new()` and its chained setters.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct InteractiveProviderSelectionOptions<'a> {
     pub project_root: Option<&'a Path>,
     pub candidates: Option<Vec<ProviderId>>,
     pub defaults: Option<Vec<ProviderId>>,
+    /// Providers never offered, even when they are candidates or detected.
+    pub exclude: Vec<ProviderId>,
+    /// When known, providers that cannot install at this scope are hidden.
+    pub scope: Option<Scope>,
+    /// Shown greyed out and not selectable, e.g. providers the skill declares unsupported.
+    pub unsupported: Vec<ProviderId>,
     pub message: &'a str,
+    /// Offer only the providers that would be preselected (detected, favorites, or `defaults`).
+    pub preselected_only: bool,
+    /// Heading of the always-included `.agents` section.
+    pub locked_label: &'a str,
+    /// Skill shown in a preview pane so users can check what they are installing.
+    pub preview: Option<&'a ParsedSkill>,
+    /// Use the alternate screen, with room for the whole preview and detection details.
+    pub fullscreen: bool,
 }
 
 impl<'a> Default for InteractiveProviderSelectionOptions<'a> {
     fn default() -> Self {
+        let text = messages();
         Self {
             project_root: None,
             candidates: None,
             defaults: None,
-            message: "Select providers to install to",
+            exclude: Vec::new(),
+            scope: None,
+            unsupported: Vec::new(),
+            message: text.select_providers,
+            preselected_only: false,
+            locked_label: text.universal_label,
+            preview: None,
+            fullscreen: false,
         }
     }
 }
 
+impl<'a> InteractiveProviderSelectionOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn project_root(mut self, project_root: &'a Path) -> Self {
+        self.project_root = Some(project_root);
+        self
+    }
+
+    pub fn candidates(mut self, candidates: Vec<ProviderId>) -> Self {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    pub fn defaults(mut self, defaults: Vec<ProviderId>) -> Self {
+        self.defaults = Some(defaults);
+        self
+    }
+
+    pub fn exclude(mut self, exclude: Vec<ProviderId>) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
+    pub fn scope(mut self, scope: Scope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    pub fn unsupported(mut self, unsupported: Vec<ProviderId>) -> Self {
+        self.unsupported = unsupported;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn preselected_only(mut self, preselected_only: bool) -> Self {
+        self.preselected_only = preselected_only;
+        self
+    }
+
+    pub fn locked_label(mut self, locked_label: &'a str) -> Self {
+        self.locked_label = locked_label;
+        self
+    }
+
+    pub fn preview(mut self, skill: &'a ParsedSkill) -> Self {
+        self.preview = Some(skill);
+        self
+    }
+
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct InteractiveProviderSelection {
     pub universal_locked: Vec<ProviderId>,
@@ -46,15 +139,8 @@ pub struct InteractiveProviderSelection {
     pub selected: Vec<ProviderId>,
 }
 
-#[derive(Debug)]
-struct UiState {
-    query: String,
-    cursor: usize,
-    selected: HashSet<ProviderId>,
-    scroll_offset: usize,
-}
-
-pub fn prompt_provider_selection(
+pub fn prompt_provider_selection_with(
+    prompter: &mut dyn Prompter,
     options: InteractiveProviderSelectionOptions<'_>,
 ) -> Result<InteractiveProviderSelection> {
     let candidates = resolve_candidates(&options);
@@ -63,7 +149,7 @@ pub fn prompt_provider_selection(
         .copied()
         .filter(|p| is_agents_provider(*p))
         .collect::<Vec<_>>();
-    let selectable = candidates
+    let mut selectable = candidates
         .iter()
         .copied()
         .filter(|p| !is_agents_provider(*p))
@@ -82,721 +168,943 @@ pub fn prompt_provider_selection(
         });
     }
 
-    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
-        return Err(InstallerError::PromptError {
-            message: "interactive provider selection requires a TTY".to_string(),
-        });
+    let detected = detect_providers(options.project_root);
+    let detected_ids = detected.iter().map(|d| d.provider).collect::<Vec<_>>();
+    rank_providers(&mut selectable, &detected_ids);
+    let mut defaults = resolve_defaults(&options, &selectable, detected.clone())
+        .into_iter()
+        .filter(|p| !options.unsupported.contains(p))
+        .collect::<Vec<_>>();
+    defaults.sort_by_key(|p| p.as_str());
+    if options.preselected_only {
+        selectable.retain(|p| defaults.contains(p));
     }
-
-    let default_selected = resolve_defaults(&options, &selectable);
-
-    println!("{}", options.message);
-
-    let mut state = UiState {
-        query: String::new(),
-        cursor: 0,
-        selected: default_selected,
-        scroll_offset: 0,
-    };
-
-    let mut terminal =
-        setup_terminal(VIEWPORT_HEIGHT).map_err(|err| InstallerError::PromptError {
-            message: err.to_string(),
-        })?;
-
-    let mut viewport_bottom = VIEWPORT_HEIGHT;
-    let result = run_ui_loop(
-        &mut terminal,
-        &universal_locked,
-        &selectable,
-        &mut state,
-        &mut viewport_bottom,
-    );
-
-    restore_terminal(&mut terminal).map_err(|err| InstallerError::PromptError {
-        message: err.to_string(),
+    let presets = resolve_presets(&selectable);
+
+    let mut selected = prompter.multi_select(&ProviderPrompt {
+        message: options.message,
+        locked: &universal_locked,
+        locked_label: options.locked_label,
+        choices: &selectable,
+        groups: &presets,
+        defaults: &defaults,
+        disabled: &options.unsupported,
+        preview: options.preview,
+        detected: &detected,
+        fullscreen: options.fullscreen,
     })?;
-    move_cursor_below_viewport(viewport_bottom);
+    if !universal_locked.is_empty() {
+        selected.push(ProviderId::Universal);
+    }
+    Ok(InteractiveProviderSelection {
+        universal_locked,
+        selectable,
+        selected,
+    })
+}
 
-    match result {
-        Ok(mut selected) => {
-            if !universal_locked.is_empty() {
-                selected.push(ProviderId::Universal);
-            }
-            Ok(InteractiveProviderSelection {
-                universal_locked,
-                selectable,
-                selected,
+/// Groups shown above the provider list, limited to members the picker offers.
+fn resolve_presets(selectable: &[ProviderId]) -> Vec<ProviderGroup> {
+    provider_groups()
+        .into_iter()
+        .filter_map(|group| {
+            let providers = group
+                .providers
+                .into_iter()
+                .filter(|p| selectable.contains(p))
+                .collect::<Vec<_>>();
+            (!providers.is_empty()).then_some(ProviderGroup {
+                name: group.name,
+                providers,
             })
+        })
+        .collect()
+}
+
+fn resolve_candidates(options: &InteractiveProviderSelectionOptions<'_>) -> Vec<ProviderId> {
+    let mut candidates = match &options.candidates {
+        Some(candidates) => dedupe_non_universal(candidates),
+        None => {
+            let all = supported_providers()
+                .iter()
+                .filter(|p| p.deprecated.is_none())
+                .map(|p| p.id)
+                .collect::<Vec<_>>();
+            dedupe_non_universal(&all)
         }
-        Err(e) => Err(e),
+    };
+    candidates.retain(|p| !options.exclude.contains(p));
+    if let Some(scope) = options.scope {
+        candidates.retain(|p| provider_capabilities(*p).supports_scope(scope));
     }
+    candidates
 }
 
-fn run_ui_loop(
-    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
-    universal_locked: &[ProviderId],
+fn resolve_defaults(
+    options: &InteractiveProviderSelectionOptions<'_>,
     selectable: &[ProviderId],
-    state: &mut UiState,
-    viewport_bottom: &mut u16,
-) -> Result<Vec<ProviderId>> {
-    loop {
-        let filtered = filtered_items(selectable, &state.query);
-        if state.cursor >= filtered.len() && !filtered.is_empty() {
-            state.cursor = filtered.len() - 1;
+    detected: Vec<DetectedProvider>,
+) -> HashSet<ProviderId> {
+    let base = if let Some(defaults) = &options.defaults {
+        defaults.clone()
+    } else {
+        // Project-only hits are weak evidence; use them only when nothing stronger matched.
+        let strong = detected
+            .iter()
+            .any(|d| d.confidence >= DetectionConfidence::Medium);
+        let mut detected = detected
+            .into_iter()
+            .filter(|d| !strong || d.confidence >= DetectionConfidence::Medium)
+            .map(|d| d.provider)
+            .collect::<Vec<_>>();
+        if detected.is_empty() {
+            selectable.to_vec()
+        } else {
+            detected.extend(favorite_providers());
+            detected
         }
+    };
 
-        let term_width = terminal.size().map(|s| s.width).unwrap_or(80);
-        let viewport_height = terminal
-            .size()
-            .map(|s| s.height.min(VIEWPORT_HEIGHT))
-            .unwrap_or(VIEWPORT_HEIGHT);
-        let viewport_area = Rect::new(0, 0, term_width, viewport_height);
-        let list_height = compute_layout(viewport_area, universal_locked.len())[6].height as usize;
-        adjust_scroll(state, filtered.len(), list_height);
-
-        let completed = terminal
-            .draw(|frame| draw_ui(frame, universal_locked, &filtered, state))
-            .map_err(|err| InstallerError::PromptError {
-                message: err.to_string(),
-            })?;
-        *viewport_bottom = completed.area.bottom();
-
-        let event = event::read().map_err(|err| InstallerError::PromptError {
-            message: err.to_string(),
-        })?;
-
-        let Event::Key(key) = event else {
-            continue;
-        };
+    base.into_iter()
+        .filter(|p| selectable.contains(p))
+        .collect::<HashSet<_>>()
+}
 
-        if key.kind != KeyEventKind::Press {
+fn dedupe_non_universal(input: &[ProviderId]) -> Vec<ProviderId> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for provider in input {
+        if *provider == ProviderId::Universal {
             continue;
         }
-
-        match key.code {
-            KeyCode::Up => state.cursor = state.cursor.saturating_sub(1),
-            KeyCode::Down => {
-                if !filtered.is_empty() {
-                    state.cursor = (state.cursor + 1).min(filtered.len() - 1);
-                }
-            }
-            KeyCode::Char(' ') => {
-                if let Some(provider) = filtered.get(state.cursor).copied() {
-                    if state.selected.contains(&provider) {
-                        state.selected.remove(&provider);
-                    } else {
-                        state.selected.insert(provider);
-                    }
-                }
-            }
-            KeyCode::Backspace => {
-                state.query.pop();
-                state.cursor = 0;
-                state.scroll_offset = 0;
-            }
-            KeyCode::Enter => {
-                if state.selected.is_empty() && universal_locked.is_empty() {
-                    continue;
-                }
-                let mut selected = state.selected.iter().copied().collect::<Vec<_>>();
-                selected.sort_by_key(|p| p.as_str());
-                return Ok(selected);
-            }
-            KeyCode::Esc => return Err(InstallerError::PromptCancelled),
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                return Err(InstallerError::PromptCancelled)
-            }
-            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
-                state.query.push(c);
-                state.cursor = 0;
-                state.scroll_offset = 0;
-            }
-            _ => {}
+        if seen.insert(*provider) {
+            out.push(*provider);
         }
     }
+    out
 }
 
-fn make_divider(label: &str, suffix: &str, width: u16) -> Line<'static> {
-    let prefix = "── ";
-    let tail = if suffix.is_empty() {
-        " ".to_string()
-    } else {
-        format!(" {} ", suffix)
-    };
-    let used = prefix.len() + label.len() + tail.len();
-    let remaining = (width as usize).saturating_sub(used);
-    let fill = "─".repeat(remaining);
-
-    Line::from(vec![
-        Span::styled(prefix.to_string(), Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            label.to_string(),
-            Style::default().add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(
-            format!("{}{}", tail, fill),
-            Style::default().fg(Color::DarkGray),
-        ),
-    ])
+// ── Interactive install orchestration ────────────────────────────────────────
+
+/// Wording and steps of `install_interactive`, built with `InteractiveInstallOptions::new()`
+/// and its chained setters.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct InteractiveInstallOptions<'a> {
+    pub providers_message: &'a str,
+    pub scope_message: &'a str,
+    pub project_scope_label: &'a str,
+    pub user_scope_label: &'a str,
+    pub method_message: &'a str,
+    pub symlink_label: &'a str,
+    pub copy_label: &'a str,
+    /// Highlighted first in the scope prompt, and used when that prompt is hidden.
+    pub default_scope: Scope,
+    /// Highlighted first in the method prompt, and used when that prompt is hidden.
+    pub default_method: InstallMethod,
+    pub ask_scope: bool,
+    pub ask_method: bool,
+    /// When hidden, existing destinations fail with `AlreadyExists` unless `--force` is set.
+    pub ask_overwrite: bool,
+    /// Show every destination and overwrite, then wait for Enter before installing.
+    pub confirm_summary: bool,
+    /// Prefill prompts from the last interactive install and save these answers for the next;
+    /// `--no-remember` turns it off too.
+    pub remember: bool,
+    /// Run the provider picker full-screen instead of inline.
+    pub fullscreen: bool,
+    /// Applied with `set_locale` when the install starts.
+    pub locale: Option<Locale>,
 }
 
-fn adjust_scroll(state: &mut UiState, total_items: usize, visible_height: usize) {
-    if total_items == 0 || visible_height == 0 {
-        state.scroll_offset = 0;
-        return;
+impl<'a> Default for InteractiveInstallOptions<'a> {
+    fn default() -> Self {
+        let text = messages();
+        Self {
+            providers_message: text.providers_prompt,
+            scope_message: text.scope_prompt,
+            project_scope_label: text.project_scope,
+            user_scope_label: text.user_scope,
+            method_message: text.method_prompt,
+            symlink_label: text.symlink,
+            copy_label: text.copy,
+            default_scope: Scope::Project,
+            default_method: InstallMethod::Symlink,
+            ask_scope: true,
+            ask_method: true,
+            ask_overwrite: true,
+            confirm_summary: true,
+            remember: true,
+            fullscreen: false,
+            locale: None,
+        }
     }
+}
 
-    let max_scroll = total_items.saturating_sub(1);
-    state.scroll_offset = state.scroll_offset.min(max_scroll);
-
-    if state.cursor < state.scroll_offset {
-        state.scroll_offset = state.cursor;
+impl<'a> InteractiveInstallOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    loop {
-        let top_lines = if state.scroll_offset > 0 { 1usize } else { 0 };
-        let items_space = visible_height.saturating_sub(top_lines);
-        let remaining = total_items - state.scroll_offset;
-        let bottom_lines = if remaining > items_space { 1usize } else { 0 };
-        let visible_count = items_space.saturating_sub(bottom_lines).max(1);
-
-        if state.cursor < state.scroll_offset + visible_count {
-            break;
-        }
+    pub fn providers_message(mut self, message: &'a str) -> Self {
+        self.providers_message = message;
+        self
+    }
 
-        state.scroll_offset += 1;
-        if state.scroll_offset >= total_items {
-            state.scroll_offset = total_items.saturating_sub(1);
-            break;
-        }
+    pub fn scope_message(mut self, message: &'a str) -> Self {
+        self.scope_message = message;
+        self
     }
-}
 
-fn draw_ui(
-    frame: &mut ratatui::Frame,
-    universal_locked: &[ProviderId],
-    filtered: &[ProviderId],
-    state: &UiState,
-) {
-    let size = frame.area();
-    let width = size.width;
-    let chunks = compute_layout(size, universal_locked.len());
-
-    render_locked(frame, chunks[0], universal_locked, width);
-    render_additional_header(frame, chunks[2], width);
-    render_search(frame, chunks[3], state);
-    render_instructions(frame, chunks[4]);
-    render_selectable(frame, chunks[6], filtered, state);
-
-    let summary = selected_summary(universal_locked, &state.selected);
-    let footer = Paragraph::new(Line::from(vec![
-        Span::styled(
-            "Selected: ",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(summary),
-    ]));
-    frame.render_widget(footer, chunks[8]);
-}
-
-fn render_locked(
-    frame: &mut ratatui::Frame,
-    area: Rect,
-    universal_locked: &[ProviderId],
-    width: u16,
-) {
-    let lines = if universal_locked.is_empty() {
-        let label = "Universal (.agents/skills) — none";
-        let prefix = "── ";
-        let used = prefix.len() + label.len() + 1;
-        let remaining = (width as usize).saturating_sub(used);
-        let fill = "─".repeat(remaining);
-        vec![Line::from(Span::styled(
-            format!("{}{} {}", prefix, label, fill),
-            Style::default().fg(Color::DarkGray),
-        ))]
-    } else {
-        let mut out = Vec::with_capacity(universal_locked.len() + 1);
-        out.push(make_divider(
-            "Universal (.agents/skills)",
-            "— always included",
-            width,
-        ));
-        for provider in universal_locked {
-            out.push(Line::from(vec![
-                Span::styled("  ● ", Style::default().fg(Color::Green)),
-                Span::raw(provider_display_name(*provider)),
-            ]));
-        }
-        out
-    };
+    pub fn scope_labels(mut self, project: &'a str, user: &'a str) -> Self {
+        self.project_scope_label = project;
+        self.user_scope_label = user;
+        self
+    }
 
-    frame.render_widget(Paragraph::new(lines), area);
-}
+    pub fn method_message(mut self, message: &'a str) -> Self {
+        self.method_message = message;
+        self
+    }
 
-fn render_additional_header(frame: &mut ratatui::Frame, area: Rect, width: u16) {
-    let divider = make_divider("Additional agents", "", width);
-    frame.render_widget(Paragraph::new(vec![divider]), area);
-}
+    pub fn method_labels(mut self, symlink: &'a str, copy: &'a str) -> Self {
+        self.symlink_label = symlink;
+        self.copy_label = copy;
+        self
+    }
 
-fn render_search(frame: &mut ratatui::Frame, area: Rect, state: &UiState) {
-    let search = Paragraph::new(Line::from(vec![
-        Span::styled("Search: ", Style::default().fg(Color::DarkGray)),
-        Span::raw(&state.query),
-        Span::styled("█", Style::default().fg(Color::DarkGray)),
-    ]));
-    frame.render_widget(search, area);
-}
+    pub fn default_scope(mut self, scope: Scope) -> Self {
+        self.default_scope = scope;
+        self
+    }
 
-fn render_instructions(frame: &mut ratatui::Frame, area: Rect) {
-    let hint = Paragraph::new(Line::from(Span::styled(
-        "↑↓ move, space select, enter confirm",
-        Style::default().fg(Color::DarkGray),
-    )));
-    frame.render_widget(hint, area);
-}
+    pub fn default_method(mut self, method: InstallMethod) -> Self {
+        self.default_method = method;
+        self
+    }
 
-fn render_selectable(
-    frame: &mut ratatui::Frame,
-    area: Rect,
-    filtered: &[ProviderId],
-    state: &UiState,
-) {
-    let height = area.height as usize;
-    let mut lines = Vec::new();
+    pub fn ask_scope(mut self, ask: bool) -> Self {
+        self.ask_scope = ask;
+        self
+    }
 
-    if filtered.is_empty() {
-        lines.push(Line::from(Span::styled(
-            "No matches found",
-            Style::default().fg(Color::DarkGray),
-        )));
-    } else {
-        let total = filtered.len();
-        let offset = state.scroll_offset;
-
-        let has_top = offset > 0;
-        let top_lines = if has_top { 1 } else { 0 };
-        let items_space = height.saturating_sub(top_lines);
-        let remaining = total - offset;
-        let has_bottom = remaining > items_space;
-        let bottom_lines = if has_bottom { 1 } else { 0 };
-        let visible_count = items_space.saturating_sub(bottom_lines).max(1);
-
-        if has_top {
-            lines.push(Line::from(Span::styled(
-                format!("↑ {} more", offset),
-                Style::default().fg(Color::DarkGray),
-            )));
-        }
+    pub fn ask_method(mut self, ask: bool) -> Self {
+        self.ask_method = ask;
+        self
+    }
 
-        let end = (offset + visible_count).min(total);
-        for i in offset..end {
-            let provider = filtered[i];
-            let is_cursor = i == state.cursor;
-            let is_selected = state.selected.contains(&provider);
-
-            let marker = if is_selected { "●" } else { "○" };
-            let prefix = if is_cursor { ">" } else { " " };
-            let path = provider_project_path(provider);
-
-            lines.push(Line::from(vec![
-                Span::styled(
-                    format!("{} {} ", prefix, marker),
-                    Style::default().fg(if is_selected {
-                        Color::Green
-                    } else {
-                        Color::DarkGray
-                    }),
-                ),
-                Span::styled(provider_display_name(provider), Style::default()),
-                Span::styled(format!(" ({})", path), Style::default().fg(Color::DarkGray)),
-            ]));
-        }
+    pub fn ask_overwrite(mut self, ask: bool) -> Self {
+        self.ask_overwrite = ask;
+        self
+    }
 
-        if has_bottom {
-            let below = total - end;
-            lines.push(Line::from(Span::styled(
-                format!("↓ {} more", below),
-                Style::default().fg(Color::DarkGray),
-            )));
-        }
+    pub fn confirm_summary(mut self, confirm: bool) -> Self {
+        self.confirm_summary = confirm;
+        self
     }
 
-    frame.render_widget(Paragraph::new(lines), area);
-}
+    pub fn remember(mut self, remember: bool) -> Self {
+        self.remember = remember;
+        self
+    }
 
-fn selected_summary(universal_locked: &[ProviderId], selected: &HashSet<ProviderId>) -> String {
-    let mut names = universal_locked
-        .iter()
-        .map(|p| provider_display_name(*p))
-        .collect::<Vec<_>>();
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
 
-    let mut selected_names = selected
-        .iter()
-        .map(|p| provider_display_name(*p))
-        .collect::<Vec<_>>();
-    selected_names.sort();
-    names.extend(selected_names);
+    /// Prompts in `locale`. Resets the messages and labels to its catalog, so reword
+    /// individual ones after this call.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        let text = locale.messages();
+        self.providers_message = text.providers_prompt;
+        self.scope_message = text.scope_prompt;
+        self.project_scope_label = text.project_scope;
+        self.user_scope_label = text.user_scope;
+        self.method_message = text.method_prompt;
+        self.symlink_label = text.symlink;
+        self.copy_label = text.copy;
+        self.locale = Some(locale);
+        self
+    }
 
-    if names.is_empty() {
-        return "(none)".to_string();
+    /// The scope to use without prompting, if any.
+    fn preset_scope(&self, args: &InstallSkillArgs) -> Option<Scope> {
+        args.scope
+            .or((!self.ask_scope).then_some(self.default_scope))
     }
-    if names.len() <= 4 {
-        return names.join(", ");
+
+    fn preset_method(&self, args: &InstallSkillArgs) -> Option<InstallMethod> {
+        args.method
+            .or((!self.ask_method).then_some(self.default_method))
     }
-    format!("{} +{} more", names[..4].join(", "), names.len() - 4)
 }
 
-const VIEWPORT_HEIGHT: u16 = 24;
+/// Without a terminal, missing `--providers`/`--scope`/`--method` come from the
+/// `SKILL_INSTALLER_*` environment variables, then the config file's `[defaults]`.
+/// `--yes` takes the same fallbacks, then accepts every prompt's default and overwrites.
+pub fn install_interactive(source: SkillSource, args: &InstallSkillArgs) -> Result<InstallResult> {
+    install_interactive_with_options(source, args, &InteractiveInstallOptions::default(), None)
+}
 
-fn compute_layout(area: Rect, locked_count: usize) -> std::rc::Rc<[Rect]> {
-    let locked_len = if locked_count == 0 {
-        1
-    } else {
-        1 + locked_count as u16
-    };
-    Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(locked_len), // 0: locked section
-            Constraint::Length(1),          // 1: spacer
-            Constraint::Length(1),          // 2: additional agents header
-            Constraint::Length(1),          // 3: search
-            Constraint::Length(1),          // 4: instructions
-            Constraint::Length(1),          // 5: spacer
-            Constraint::Min(1),             // 6: selectable list
-            Constraint::Length(1),          // 7: spacer
-            Constraint::Length(1),          // 8: footer
-        ])
-        .split(area)
-}
-
-fn setup_terminal(height: u16) -> io::Result<Terminal<CrosstermBackend<std::io::Stdout>>> {
-    enable_raw_mode()?;
-    let backend = CrosstermBackend::new(io::stdout());
-    Terminal::with_options(
-        backend,
-        TerminalOptions {
-            viewport: Viewport::Inline(height),
-        },
+/// `install_interactive` with custom wording and steps; `prompter` defaults to the
+/// terminal UI (or `LinePrompter` without the `interactive` feature), or to headless
+/// defaults when there is no TTY.
+pub fn install_interactive_with_options(
+    source: SkillSource,
+    args: &InstallSkillArgs,
+    options: &InteractiveInstallOptions<'_>,
+    prompter: Option<&mut dyn Prompter>,
+) -> Result<InstallResult> {
+    with_prompter(source, args, options, prompter, run_install)
+}
+
+/// `install_interactive` for several skills: providers, scope, and method are asked once,
+/// for the first, and reused for the rest; one summary covers them all. Each skill's
+/// install succeeds or fails on its own.
+pub fn install_interactive_many(
+    sources: Vec<SkillSource>,
+    args: &InstallSkillArgs,
+) -> Result<Vec<Result<InstallResult>>> {
+    with_prompter(
+        sources,
+        args,
+        &InteractiveInstallOptions::default(),
+        None,
+        run_install_many,
     )
 }
 
-fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> io::Result<()> {
-    disable_raw_mode()?;
-    terminal.show_cursor()
+/// Asks the same questions as `install_interactive` and returns the plan without touching
+/// the filesystem, for `--dry-run`. Existing destinations show up as conflicts instead of
+/// being asked about, unless `--force` or `--upgrade` decides them.
+pub fn plan_interactive(source: SkillSource, args: &InstallSkillArgs) -> Result<InstallPlan> {
+    plan_interactive_many(vec![source], args).map(|mut plans| plans.remove(0))
 }
 
-fn move_cursor_below_viewport(viewport_bottom: u16) {
-    crossterm::execute!(
-        io::stdout(),
-        crossterm::cursor::MoveTo(0, viewport_bottom),
-        crossterm::cursor::MoveToNextLine(1)
+/// `plan_interactive` for several skills, sharing answers like `install_interactive_many`.
+pub fn plan_interactive_many(
+    sources: Vec<SkillSource>,
+    args: &InstallSkillArgs,
+) -> Result<Vec<InstallPlan>> {
+    let options = InteractiveInstallOptions::default().ask_overwrite(false);
+    with_prompter(
+        sources,
+        args,
+        &options,
+        None,
+        |sources, args, options, prompter| {
+            let prepared = prepare_many(sources, args, options, prompter)?;
+            prepared
+                .into_iter()
+                .map(|prepared| prepared.map(|prepared| prepared.plan))
+                .collect()
+        },
     )
-    .ok();
 }
 
-// ── Generic single-select prompt ─────────────────────────────────────────────
-
-pub fn prompt_select(message: &str, options: &[&str], default: usize) -> Result<usize> {
-    if options.is_empty() {
-        return Err(InstallerError::PromptError {
-            message: "no options provided".to_string(),
-        });
+type Run<S, T> =
+    fn(S, &InstallSkillArgs, &InteractiveInstallOptions<'_>, &mut dyn Prompter) -> Result<T>;
+
+fn with_prompter<S, T>(
+    source: S,
+    args: &InstallSkillArgs,
+    options: &InteractiveInstallOptions<'_>,
+    prompter: Option<&mut dyn Prompter>,
+    run: Run<S, T>,
+) -> Result<T> {
+    if let Some(prompter) = prompter {
+        return run(source, args, options, prompter);
     }
+    if args.yes {
+        let mut args = args.with_configured_defaults()?;
+        args.force = args.force || !args.upgrade;
+        let options = options.clone().confirm_summary(false).remember(false);
+        let mut prompter = HeadlessPrompter {
+            yes: true,
+            progress: args.progress_observer(),
+        };
+        return run(source, &args, &options, &mut prompter);
+    }
+    #[cfg(feature = "interactive")]
+    if std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+        return run(source, args, options, &mut TerminalPrompter);
+    }
+    #[cfg(not(feature = "interactive"))]
+    if std::io::stdin().is_terminal() {
+        return run(source, args, options, &mut LinePrompter::stdio());
+    }
+    let args = headless_args(args, options)?;
+    let options = options.clone().confirm_summary(false);
+    let mut prompter = HeadlessPrompter {
+        yes: false,
+        progress: args.progress_observer(),
+    };
+    run(source, &args, &options, &mut prompter)
+}
 
-    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+fn headless_args(
+    args: &InstallSkillArgs,
+    options: &InteractiveInstallOptions<'_>,
+) -> Result<InstallSkillArgs> {
+    let args = args.with_configured_defaults()?;
+    let missing = [
+        ("--providers", PROVIDERS_ENV, args.providers.is_none()),
+        ("--scope", SCOPE_ENV, options.preset_scope(&args).is_none()),
+        (
+            "--method",
+            METHOD_ENV,
+            options.preset_method(&args).is_none(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, _, missing)| *missing)
+    .map(|(flag, env, _)| format!("{flag} (or {env})"))
+    .collect::<Vec<_>>();
+    if !missing.is_empty() {
         return Err(InstallerError::PromptError {
-            message: "interactive selection requires a TTY".to_string(),
+            message: format!("no TTY to prompt on; pass {}", missing.join(", ")),
         });
     }
+    Ok(args)
+}
 
-    println!("{}", message);
-
-    let mut cursor = default.min(options.len() - 1);
-    // hint + options
-    let viewport_height = 1 + options.len() as u16;
-
-    let mut terminal =
-        setup_terminal(viewport_height).map_err(|err| InstallerError::PromptError {
-            message: err.to_string(),
-        })?;
-
-    let mut viewport_bottom = viewport_height;
-    let result = run_select_loop(&mut terminal, options, &mut cursor, &mut viewport_bottom);
-
-    restore_terminal(&mut terminal).map_err(|err| InstallerError::PromptError {
-        message: err.to_string(),
-    })?;
-    move_cursor_below_viewport(viewport_bottom);
-
-    result
-}
-
-fn run_select_loop(
-    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
-    options: &[&str],
-    cursor: &mut usize,
-    viewport_bottom: &mut u16,
-) -> Result<usize> {
-    loop {
-        let cur = *cursor;
-        let completed = terminal
-            .draw(|frame| draw_select(frame, options, cur))
-            .map_err(|err| InstallerError::PromptError {
-                message: err.to_string(),
-            })?;
-        *viewport_bottom = completed.area.bottom();
-
-        let event = event::read().map_err(|err| InstallerError::PromptError {
-            message: err.to_string(),
-        })?;
-
-        let Event::Key(key) = event else { continue };
-        if key.kind != KeyEventKind::Press {
-            continue;
-        }
+/// Answers for runs without a terminal: defaults where a prompt has one, errors otherwise.
+/// With `yes` (`--yes`), every prompt takes its default and confirmations are accepted.
+struct HeadlessPrompter {
+    yes: bool,
+    progress: Option<Arc<dyn InstallObserver>>,
+}
 
-        match key.code {
-            KeyCode::Up => *cursor = cursor.saturating_sub(1),
-            KeyCode::Down => *cursor = (*cursor + 1).min(options.len().saturating_sub(1)),
-            KeyCode::Enter => return Ok(*cursor),
-            KeyCode::Esc => return Err(InstallerError::PromptCancelled),
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                return Err(InstallerError::PromptCancelled)
-            }
-            _ => {}
+impl HeadlessPrompter {
+    fn unanswerable(message: &str) -> InstallerError {
+        InstallerError::PromptError {
+            message: format!(
+                "no TTY to answer '{}'",
+                message.trim_start_matches('◆').trim()
+            ),
         }
     }
 }
 
-fn draw_select(frame: &mut ratatui::Frame, options: &[&str], cursor: usize) {
-    let size = frame.area();
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(1), // instructions
-            Constraint::Min(1),    // options
-        ])
-        .split(size);
-
-    let hint = Paragraph::new(Line::from(Span::styled(
-        "↑↓ move, enter confirm",
-        Style::default().fg(Color::DarkGray),
-    )));
-    frame.render_widget(hint, chunks[0]);
-
-    let mut lines = Vec::with_capacity(options.len());
-    for (idx, label) in options.iter().enumerate() {
-        let is_cursor = idx == cursor;
-        let marker = if is_cursor { "●" } else { "○" };
-
-        lines.push(Line::from(vec![
-            Span::styled(
-                format!("{} ", marker),
-                Style::default().fg(if is_cursor {
-                    Color::Green
-                } else {
-                    Color::DarkGray
-                }),
-            ),
-            Span::styled(*label, Style::default()),
-        ]));
+impl Prompter for HeadlessPrompter {
+    fn select(&mut self, message: &str, _: &[&str], default: usize) -> Result<usize> {
+        if self.yes {
+            return Ok(default);
+        }
+        Err(Self::unanswerable(message))
     }
 
-    frame.render_widget(Paragraph::new(lines), chunks[1]);
-}
+    fn multi_select(&mut self, prompt: &ProviderPrompt<'_>) -> Result<Vec<ProviderId>> {
+        if self.yes {
+            return Ok(prompt
+                .defaults
+                .iter()
+                .copied()
+                .filter(|p| !prompt.disabled.contains(p))
+                .collect());
+        }
+        Err(Self::unanswerable(prompt.message))
+    }
 
-// ── Provider helpers ─────────────────────────────────────────────────────────
+    fn confirm(&mut self, message: &str, _: bool) -> Result<bool> {
+        if self.yes {
+            return Ok(true);
+        }
+        Err(Self::unanswerable(message))
+    }
 
-fn filtered_items(items: &[ProviderId], query: &str) -> Vec<ProviderId> {
-    if query.trim().is_empty() {
-        return items.to_vec();
+    fn text(&mut self, message: &str, default: Option<&str>) -> Result<String> {
+        default
+            .map(str::to_string)
+            .ok_or_else(|| Self::unanswerable(message))
     }
-    let q = query.to_lowercase();
-    items
-        .iter()
-        .copied()
-        .filter(|p| {
-            provider_display_name(*p).to_lowercase().contains(&q) || p.as_str().contains(&q)
-        })
-        .collect()
-}
 
-fn resolve_candidates(options: &InteractiveProviderSelectionOptions<'_>) -> Vec<ProviderId> {
-    if let Some(candidates) = &options.candidates {
-        return dedupe_non_universal(candidates);
+    fn progress(
+        &mut self,
+        _: &[PlannedTarget],
+        install: &mut dyn FnMut(Option<Arc<dyn InstallObserver>>) -> Result<InstallResult>,
+    ) -> Result<InstallResult> {
+        install(self.progress.clone())
     }
+}
 
-    let all = supported_providers()
-        .iter()
-        .map(|p| p.id)
-        .collect::<Vec<_>>();
-    dedupe_non_universal(&all)
+/// `install_interactive` with questions routed to `prompter`, e.g. a GUI or a scripted test double.
+pub fn install_interactive_with(
+    source: SkillSource,
+    args: &InstallSkillArgs,
+    prompter: &mut dyn Prompter,
+) -> Result<InstallResult> {
+    run_install(
+        source,
+        args,
+        &InteractiveInstallOptions::default(),
+        prompter,
+    )
 }
 
-fn resolve_defaults(
-    options: &InteractiveProviderSelectionOptions<'_>,
-    selectable: &[ProviderId],
-) -> HashSet<ProviderId> {
-    let base = if let Some(defaults) = &options.defaults {
-        defaults.clone()
-    } else {
-        let detected = detect_providers(options.project_root)
-            .into_iter()
-            .map(|d| d.provider)
-            .collect::<Vec<_>>();
-        if detected.is_empty() {
-            selectable.to_vec()
-        } else {
-            detected
-        }
-    };
+/// A plan with the answers that only matter once it runs.
+struct PreparedInstall {
+    plan: InstallPlan,
+    /// Providers as chosen, before existing destinations were skipped.
+    chosen: Vec<ProviderId>,
+    /// Existing destinations to rename before installing, and where to.
+    backups: Vec<(PathBuf, PathBuf)>,
+    /// Answers to save after a successful install.
+    remember: Option<LastUsed>,
+}
 
-    base.into_iter()
-        .filter(|p| selectable.contains(p))
-        .collect::<HashSet<_>>()
+fn run_install(
+    source: SkillSource,
+    args: &InstallSkillArgs,
+    interactive: &InteractiveInstallOptions<'_>,
+    prompter: &mut dyn Prompter,
+) -> Result<InstallResult> {
+    run_install_many(vec![source], args, interactive, prompter)?.remove(0)
 }
 
-fn dedupe_non_universal(input: &[ProviderId]) -> Vec<ProviderId> {
-    let mut seen = HashSet::new();
-    let mut out = Vec::new();
-    for provider in input {
-        if *provider == ProviderId::Universal {
-            continue;
+fn run_install_many(
+    sources: Vec<SkillSource>,
+    args: &InstallSkillArgs,
+    interactive: &InteractiveInstallOptions<'_>,
+    prompter: &mut dyn Prompter,
+) -> Result<Vec<Result<InstallResult>>> {
+    let prepared = prepare_many(sources, args, interactive, prompter)?;
+    let plans = prepared.iter().flatten().collect::<Vec<_>>();
+    if interactive.confirm_summary && !plans.is_empty() {
+        let summary = plans
+            .iter()
+            .map(|prepared| plan_summary(&prepared.plan))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !prompter.confirm(&summary, true)? {
+            return Err(InstallerError::PromptCancelled);
         }
-        if seen.insert(*provider) {
-            out.push(*provider);
+    }
+
+    let mut remember = None;
+    let mut results = Vec::new();
+    for prepared in prepared {
+        results.push(prepared.and_then(|prepared| {
+            remember = remember.take().or(prepared.remember);
+            execute_prepared(prepared.plan, &prepared.backups, prompter)
+        }));
+    }
+    if let Some(remembered) = remember {
+        if let Some(result) = results.iter_mut().find_map(|result| result.as_mut().ok()) {
+            if let Err(err) = remembered.save() {
+                result
+                    .warnings
+                    .push(format!("could not remember these answers: {err}"));
+            }
         }
     }
-    out
+    Ok(results)
 }
 
-fn provider_display_name(provider: ProviderId) -> &'static str {
-    supported_providers()
-        .iter()
-        .find(|p| p.id == provider)
-        .map(|p| p.display_name)
-        .unwrap_or(provider.as_str())
+fn execute_prepared(
+    plan: InstallPlan,
+    backups: &[(PathBuf, PathBuf)],
+    prompter: &mut dyn Prompter,
+) -> Result<InstallResult> {
+    for (path, backup) in backups {
+        fs::rename(path, backup).map_err(|err| InstallerError::IoError {
+            path: path.clone(),
+            message: format!("failed to back up to {}: {err}", backup.display()),
+        })?;
+    }
+    let targets = plan.targets.clone();
+    let mut plan = Some(plan);
+    let mut result = prompter.progress(&targets, &mut |observer| {
+        let mut plan = plan.take().ok_or_else(|| InstallerError::PromptError {
+            message: "install already ran".to_string(),
+        })?;
+        if let Some(observer) = observer {
+            plan.request.options.observers.push(observer);
+        }
+        execute_plan(plan)
+    })?;
+    for (path, backup) in backups {
+        result.warnings.push(format!(
+            "backed up {} to {}",
+            path.display(),
+            backup.display()
+        ));
+    }
+    Ok(result)
 }
 
-fn provider_project_path(provider: ProviderId) -> &'static str {
-    supported_providers()
-        .iter()
-        .find(|p| p.id == provider)
-        .map(|p| p.project_path)
-        .unwrap_or(".agents/skills")
+/// Prepares each source in turn; later sources reuse the providers, scope, and method
+/// chosen for the first that prepared instead of asking again. A source that fails on its
+/// own, e.g. an invalid skill, does not stop the others; prompt errors stop them all.
+fn prepare_many(
+    sources: Vec<SkillSource>,
+    args: &InstallSkillArgs,
+    interactive: &InteractiveInstallOptions<'_>,
+    prompter: &mut dyn Prompter,
+) -> Result<Vec<Result<PreparedInstall>>> {
+    let mut args = args.clone();
+    let mut prepared = Vec::new();
+    for source in sources {
+        let next = match prepare_install(source, &args, interactive, prompter) {
+            Err(
+                err @ (InstallerError::PromptCancelled
+                | InstallerError::PromptBack
+                | InstallerError::PromptError { .. }),
+            ) => return Err(err),
+            next => next,
+        };
+        if let Ok(next) = &next {
+            let ids = next.chosen.iter().map(|p| p.as_str()).collect::<Vec<_>>();
+            args.providers = Some(ids.join(","));
+            args.exclude = None;
+            args.scope = Some(next.plan.request.scope);
+            args.method = Some(next.plan.request.method);
+        }
+        prepared.push(next);
+    }
+    Ok(prepared)
 }
 
-// ── Interactive install orchestration ────────────────────────────────────────
-
-pub fn install_interactive(source: SkillSource, args: &InstallSkillArgs) -> Result<InstallResult> {
+/// Asks every question and plans the install, without changing anything on disk.
+fn prepare_install(
+    source: SkillSource,
+    args: &InstallSkillArgs,
+    interactive: &InteractiveInstallOptions<'_>,
+    prompter: &mut dyn Prompter,
+) -> Result<PreparedInstall> {
     let cwd = std::env::current_dir().map_err(|err| InstallerError::IoError {
         path: std::path::PathBuf::from("."),
         message: format!("failed to read cwd: {err}"),
     })?;
 
-    let providers = match &args.providers {
-        Some(csv) => parse_providers_csv(csv)?,
-        None => {
-            let selection = prompt_provider_selection(InteractiveProviderSelectionOptions {
-                project_root: args.project_root.as_deref().or(Some(&cwd)),
-                candidates: None,
-                defaults: None,
-                message: "◆  Select providers to install to",
-            })?;
-            if selection.selected.is_empty() {
-                return Err(InstallerError::PromptError {
-                    message: "no providers selected".to_string(),
-                });
-            }
-            selection.selected
-        }
-    };
+    args.apply_provider_paths()?;
+    let configured = configured_defaults();
+    #[cfg(feature = "interactive")]
+    crate::theme::set_color_choice(args.color.or_configured(configured.color));
+    if let Some(locale) = interactive.locale {
+        set_locale(locale);
+    }
+    let skill = parse_source(&source, args.slugify_name)?;
+    let exclude = args.excluded_providers()?;
+    let unsupported = supported_providers()
+        .into_iter()
+        .map(|p| p.id)
+        .filter(|p| !skill.providers.allows(*p))
+        .collect::<Vec<_>>();
 
-    let scope = match args.scope {
-        Some(s) => s,
-        None => {
-            print_prompt_spacing();
-            let labels = [
-                "Project (Install in current directory (committed with your project))",
-                "Global",
-            ];
-            let idx = prompt_select("◆  Installation scope", &labels, 0)?;
-            if idx == 0 {
-                Scope::Project
-            } else {
-                Scope::User
-            }
-        }
+    // Providers, scope, then method; `PromptBack` returns to the previous step that asks.
+    let asks = [
+        args.providers.is_none(),
+        interactive.preset_scope(args).is_none(),
+        interactive.preset_method(args).is_none(),
+    ];
+    let remember = interactive.remember && !args.no_remember && asks.contains(&true);
+    let last = if remember {
+        LastUsed::load()
+    } else {
+        LastUsed::default()
     };
-
-    let method = match args.method {
-        Some(m) => m,
-        None => {
-            print_prompt_spacing();
-            let labels = [
-                "Symlink (Recommended) (Single source of truth, easy updates)",
-                "Copy to all agents",
-            ];
-            let idx = prompt_select("◆  Installation method", &labels, 0)?;
-            if idx == 0 {
-                InstallMethod::Symlink
-            } else {
-                InstallMethod::Copy
+    let mut picked: Option<Vec<ProviderId>> = None;
+    let mut scope = interactive.preset_scope(args);
+    let mut method = interactive.preset_method(args);
+    let mut step = 0;
+    while step < asks.len() {
+        if !asks[step] {
+            step += 1;
+            continue;
+        }
+        let answered = match step {
+            0 => {
+                let mut selection_options = InteractiveProviderSelectionOptions::new()
+                    .project_root(args.project_root.as_deref().unwrap_or(&cwd))
+                    .exclude(exclude.clone())
+                    .unsupported(unsupported.clone())
+                    .message(interactive.providers_message)
+                    .preview(&skill)
+                    .fullscreen(interactive.fullscreen);
+                if let Some(scope) = interactive.preset_scope(args) {
+                    selection_options = selection_options.scope(scope);
+                }
+                if let Some(previous) = &picked {
+                    selection_options = selection_options.defaults(previous.clone());
+                } else if !last.providers.is_empty() {
+                    selection_options = selection_options.defaults(last.provider_ids());
+                } else if let Some(providers) = &configured.providers {
+                    selection_options = selection_options.defaults(parse_providers_csv(providers)?);
+                }
+                prompt_provider_selection_with(prompter, selection_options).and_then(|selection| {
+                    if selection.selected.is_empty() {
+                        return Err(InstallerError::PromptError {
+                            message: "no providers selected".to_string(),
+                        });
+                    }
+                    picked = Some(selection.selected);
+                    Ok(())
+                })
+            }
+            1 => {
+                let labels = [
+                    interactive.project_scope_label,
+                    interactive.user_scope_label,
+                ];
+                let default = scope
+                    .or(last.scope)
+                    .or(configured.scope)
+                    .unwrap_or(interactive.default_scope);
+                prompter
+                    .select(
+                        interactive.scope_message,
+                        &labels,
+                        usize::from(default == Scope::User),
+                    )
+                    .map(|idx| {
+                        scope = Some(if idx == 0 {
+                            Scope::Project
+                        } else {
+                            Scope::User
+                        });
+                    })
+            }
+            _ => {
+                let labels = [interactive.symlink_label, interactive.copy_label];
+                let default = method
+                    .or(last.method)
+                    .or(configured.method)
+                    .unwrap_or(interactive.default_method);
+                prompter
+                    .select(
+                        interactive.method_message,
+                        &labels,
+                        usize::from(default == InstallMethod::Copy),
+                    )
+                    .map(|idx| {
+                        method = Some(if idx == 0 {
+                            InstallMethod::Symlink
+                        } else {
+                            InstallMethod::Copy
+                        });
+                    })
             }
+        };
+        match answered {
+            Ok(()) => step += 1,
+            Err(InstallerError::PromptBack) => {
+                step = (0..step)
+                    .rev()
+                    .find(|&previous| asks[previous])
+                    .ok_or(InstallerError::PromptCancelled)?;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    let scope = scope.expect("scope is preset or asked");
+    let method = method.expect("method is preset or asked");
+
+    let providers = match picked {
+        // The picker ran before the scope was known when `--scope` is absent.
+        Some(mut providers) => {
+            providers.retain(|p| provider_capabilities(*p).supports_scope(scope));
+            providers
         }
+        None => args.selected_providers(scope)?,
     };
+    if providers.is_empty() {
+        return Err(InstallerError::PromptError {
+            message: format!("no providers support {} scope", scope.as_str()),
+        });
+    }
+    let chosen = providers.clone();
 
     let project_root = match scope {
         Scope::User => None,
         Scope::Project => Some(args.project_root.clone().unwrap_or(cwd)),
     };
 
-    let force = if args.force {
-        true
+    // `--upgrade` decides per destination from the recorded versions instead of asking.
+    let mut options = args.install_options();
+    for variable in &skill.variables {
+        if options.variables.contains_key(&variable.name) {
+            continue;
+        }
+        let default = configured_variable(&variable.name).or_else(|| variable.default.clone());
+        let label = match &variable.description {
+            Some(description) => fill(
+                messages().variable_described,
+                &[("description", description), ("name", &variable.name)],
+            ),
+            None => fill(messages().variable_prompt, &[("name", &variable.name)]),
+        };
+        let value = prompter.text(&label, default.as_deref())?;
+        options.variables.insert(variable.name.clone(), value);
+    }
+
+    let mut providers = providers;
+    let mut backups = Vec::new();
+    let back_up = configured.backups.unwrap_or(false);
+    let force = if args.force || args.upgrade || !interactive.ask_overwrite {
+        if args.force && back_up {
+            for path in
+                existing_destinations(&skill.name, &providers, scope, project_root.as_deref())?
+            {
+                let backup = backup_path(&path);
+                backups.push((path, backup));
+            }
+        }
+        args.force
     } else {
         let existing =
-            find_existing_destinations(&source, &providers, scope, project_root.as_deref())?;
-        if existing.is_empty() {
-            false
-        } else {
-            print_prompt_spacing();
-            let msg = if existing.len() == 1 {
-                format!(
-                    "◆  Skill already exists at {}. Overwrite?",
-                    existing[0].display()
-                )
-            } else {
-                format!(
-                    "◆  Skill already exists in {} locations. Overwrite?",
-                    existing.len()
-                )
-            };
-            let idx = prompt_select(&msg, &["Yes", "No"], 1)?;
-            if idx == 0 {
+            existing_destinations(&skill.name, &providers, scope, project_root.as_deref())?;
+        match existing.as_slice() {
+            [] => false,
+            [path] => {
+                let msg = fill(messages().overwrite_prompt, &[("path", &path.display())]);
+                if !prompter.confirm(&msg, false)? {
+                    return Err(InstallerError::PromptCancelled);
+                }
+                if back_up {
+                    backups.push((path.clone(), backup_path(path)));
+                }
                 true
-            } else {
-                return Err(InstallerError::PromptCancelled);
+            }
+            _ => {
+                let shared = match method {
+                    InstallMethod::Symlink => Some(shared_destination(
+                        &skill.name,
+                        scope,
+                        project_root.as_deref(),
+                    )?),
+                    InstallMethod::Copy => None,
+                };
+                let skipped = resolve_conflicts(
+                    prompter,
+                    &existing,
+                    shared.as_deref(),
+                    back_up,
+                    &mut backups,
+                )?;
+                let mut kept = Vec::new();
+                for provider in providers {
+                    let at = existing_destinations(
+                        &skill.name,
+                        &[provider],
+                        scope,
+                        project_root.as_deref(),
+                    )?;
+                    if !at.iter().any(|path| skipped.contains(path)) {
+                        kept.push(provider);
+                    }
+                }
+                providers = kept;
+                if providers.is_empty() {
+                    return Err(InstallerError::PromptError {
+                        message: "every existing destination was skipped".to_string(),
+                    });
+                }
+                skipped.len() < existing.len()
             }
         }
     };
 
-    install(InstallRequest {
-        source,
-        providers,
-        scope,
-        project_root,
-        method,
-        force,
+    let remembered = LastUsed {
+        providers: dedupe_non_universal(&providers)
+            .iter()
+            .map(|p| p.as_str().to_string())
+            .collect(),
+        scope: Some(scope),
+        method: Some(method),
+    };
+    let plan = plan_parsed(
+        InstallRequest {
+            source,
+            providers,
+            scope,
+            project_root,
+            method,
+            force,
+            options,
+        },
+        skill,
+    )?;
+    Ok(PreparedInstall {
+        plan,
+        chosen,
+        backups,
+        remember: remember.then_some(remembered),
     })
 }
 
-fn print_prompt_spacing() {
-    // Two-line separation between interactive steps.
-    println!();
-    println!();
+/// Asks what to do with each existing destination, collecting the backups to make and
+/// returning the paths to skip. The `shared` copy symlinks point at cannot be skipped.
+/// With `back_up`, backing up is the default choice.
+fn resolve_conflicts(
+    prompter: &mut dyn Prompter,
+    existing: &[PathBuf],
+    shared: Option<&Path>,
+    back_up: bool,
+    backups: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<Vec<PathBuf>> {
+    let text = messages();
+    let mut skipped = Vec::new();
+    for path in existing {
+        let message = fill(text.conflict_prompt, &[("path", &path.display())]);
+        let choices: &[&str] = if shared == Some(path.as_path()) {
+            &[text.overwrite, text.back_up]
+        } else {
+            &[text.overwrite, text.skip, text.back_up]
+        };
+        // Default to the choice that loses nothing.
+        let default = if back_up { choices.len() - 1 } else { 1 };
+        match choices[prompter.select(&message, choices, default)?] {
+            choice if choice == text.skip => skipped.push(path.clone()),
+            choice if choice == text.overwrite => {}
+            _ => backups.push((path.clone(), backup_path(path))),
+        }
+    }
+    Ok(skipped)
+}
+
+fn shared_destination(
+    skill_name: &str,
+    scope: Scope,
+    project_root: Option<&Path>,
+) -> Result<PathBuf> {
+    let target = resolve_install_target(ProviderId::Universal, scope, project_root)?;
+    Ok(target.target_dir.join(skill_dir_name(skill_name)))
+}
+
+/// `<path>.bak`, or `<path>.bak.N` when earlier backups exist.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    let mut backup = path.with_file_name(&name);
+    let mut n = 1;
+    while fs::symlink_metadata(&backup).is_ok() {
+        backup = path.with_file_name(format!("{}.{n}", name.to_string_lossy()));
+        n += 1;
+    }
+    backup
+}
+
+fn plan_summary(plan: &InstallPlan) -> String {
+    let skill = match &plan.skill_version {
+        Some(version) => format!("{} {version}", plan.skill_name),
+        None => plan.skill_name.clone(),
+    };
+    let mut out = fill(messages().install_prompt, &[("skill", &skill)]);
+    for target in &plan.targets {
+        let note = match target.action {
+            PlannedAction::Create | PlannedAction::Link => String::new(),
+            PlannedAction::Overwrite => format!(" ({})", messages().overwrites_existing),
+            action => format!(" ({})", action.as_str()),
+        };
+        write!(
+            out,
+            "\n   {} -> {}{note}",
+            target.requested_provider.as_str(),
+            target.destination.display()
+        )
+        .unwrap();
+    }
+    out
 }
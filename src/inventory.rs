@@ -0,0 +1,133 @@
+#[cfg(feature = "native")]
+use std::fs;
+#[cfg(feature = "native")]
+use std::io::Read;
+use std::path::Path;
+
+#[cfg(feature = "native")]
+use walkdir::WalkDir;
+
+#[cfg(feature = "native")]
+use crate::error::InstallerError;
+use crate::error::Result;
+#[cfg(feature = "native")]
+use crate::install::is_ignored_entry;
+#[cfg(feature = "native")]
+use crate::receipt::RECEIPT_FILE_NAME;
+use crate::types::{SkillFile, SkillFileKind, SkillSource};
+
+/// Bytes inspected for a shebang or NUL bytes.
+const SNIFF_LEN: usize = 8 * 1024;
+
+const SCRIPT_EXTENSIONS: &[&str] = &[
+    "sh", "bash", "zsh", "fish", "py", "js", "mjs", "cjs", "ts", "rb", "pl", "php", "ps1", "bat",
+    "cmd",
+];
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "svg", "webp", "ico", "bmp"];
+
+/// Payload files of `source`, including `SKILL.md`, with sizes and kinds. Each file is
+/// opened to sniff its kind, so this walks the whole tree; `parse_skill` calls it once.
+/// `include_ignored` lists `.git`, `node_modules`, and the like, as
+/// `InstallOptions::include_ignored` copies them.
+#[cfg_attr(not(feature = "native"), allow(unused_variables))]
+pub fn skill_files(source: &SkillSource, include_ignored: bool) -> Result<Vec<SkillFile>> {
+    let mut files = match source {
+        #[cfg(feature = "native")]
+        SkillSource::LocalPath(path) => dir_files(
+            &crate::parser::resolve_local_skill_root(path)?,
+            include_ignored,
+        )?,
+        SkillSource::Embedded(embedded) => {
+            let mut files = vec![SkillFile {
+                path: "SKILL.md".to_string(),
+                size: embedded.skill_md.len() as u64,
+                kind: SkillFileKind::Markdown,
+            }];
+            for (path, bytes) in &embedded.files {
+                let path = path.to_string_lossy().replace('\\', "/");
+                files.push(SkillFile {
+                    kind: file_kind(&path, bytes),
+                    size: bytes.len() as u64,
+                    path,
+                });
+            }
+            files
+        }
+        #[cfg(not(feature = "native"))]
+        SkillSource::LocalPath(_) => Vec::new(),
+    };
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+#[cfg(feature = "native")]
+fn dir_files(root: &Path, include_ignored: bool) -> Result<Vec<SkillFile>> {
+    let io_err = |path: &Path, message: String| InstallerError::IoError {
+        path: path.to_path_buf(),
+        message,
+    };
+    let walker = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| include_ignored || !is_ignored_entry(entry));
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(|err| io_err(root, err.to_string()))?;
+        if entry.file_type().is_dir()
+            || (entry.depth() == 1 && entry.file_name() == RECEIPT_FILE_NAME)
+        {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .map_err(|err| io_err(entry.path(), err.to_string()))?
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        if entry.file_type().is_symlink() {
+            files.push(SkillFile {
+                path: relative,
+                size: 0,
+                kind: SkillFileKind::Symlink,
+            });
+            continue;
+        }
+
+        let size = entry
+            .metadata()
+            .map_err(|err| io_err(entry.path(), err.to_string()))?
+            .len();
+        let mut head = Vec::with_capacity(SNIFF_LEN);
+        fs::File::open(entry.path())
+            .and_then(|file| file.take(SNIFF_LEN as u64).read_to_end(&mut head))
+            .map_err(|err| io_err(entry.path(), err.to_string()))?;
+        files.push(SkillFile {
+            kind: file_kind(&relative, &head),
+            path: relative,
+            size,
+        });
+    }
+    Ok(files)
+}
+
+fn file_kind(path: &str, bytes: &[u8]) -> SkillFileKind {
+    let head = &bytes[..bytes.len().min(SNIFF_LEN)];
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        SkillFileKind::Image
+    } else if head.contains(&0) {
+        SkillFileKind::Binary
+    } else if head.starts_with(b"#!") || SCRIPT_EXTENSIONS.contains(&extension.as_str()) {
+        SkillFileKind::Script
+    } else if matches!(extension.as_str(), "md" | "markdown" | "mdx") {
+        SkillFileKind::Markdown
+    } else {
+        SkillFileKind::Text
+    }
+}
@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::{InstallerError, Result};
+use crate::providers::parse_providers_csv;
+use crate::types::{InstallMethod, ProviderId, Scope};
+
+/// Answers from the last interactive install, offered as the defaults of the next one.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub(crate) struct LastUsed {
+    pub providers: Vec<String>,
+    pub scope: Option<Scope>,
+    pub method: Option<InstallMethod>,
+}
+
+/// `$XDG_STATE_HOME/skill-installer/last-install.toml`, falling back to `~/.local/state`.
+pub fn last_used_path() -> Option<PathBuf> {
+    let state_home = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state"))
+        })?;
+    Some(state_home.join("skill-installer/last-install.toml"))
+}
+
+impl LastUsed {
+    /// A missing or unreadable file just means there is nothing to suggest.
+    pub(crate) fn load() -> Self {
+        last_used_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| toml::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self) -> Result<()> {
+        let Some(path) = last_used_path() else {
+            return Ok(());
+        };
+        let io_err = |err: std::io::Error| InstallerError::IoError {
+            path: path.clone(),
+            message: err.to_string(),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_err)?;
+        }
+        let raw = toml::to_string(self).map_err(|err| InstallerError::ConfigError {
+            path: path.clone(),
+            message: err.to_string(),
+        })?;
+        fs::write(&path, raw).map_err(io_err)
+    }
+
+    /// Remembered providers that are still known; ids of removed custom providers are dropped.
+    pub(crate) fn provider_ids(&self) -> Vec<ProviderId> {
+        self.providers
+            .iter()
+            .filter_map(|id| parse_providers_csv(id).ok())
+            .flatten()
+            .collect()
+    }
+}
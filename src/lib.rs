@@ -1,30 +1,146 @@
+#[macro_use]
+#[cfg_attr(not(feature = "native"), allow(unused_macros))]
+mod trace;
+
+#[cfg(feature = "native")]
+pub mod build;
+#[cfg(feature = "cli")]
+mod cli;
 #[cfg(feature = "interactive")]
 mod embed;
 mod error;
+mod flat;
+#[cfg(feature = "native")]
+mod hash;
+#[cfg(feature = "native")]
+mod hooks;
+#[cfg(feature = "native")]
 mod install;
-#[cfg(feature = "interactive")]
+#[cfg(feature = "native")]
+mod installed;
+#[cfg(feature = "prompts")]
 mod interactive;
+mod inventory;
+#[cfg(feature = "prompts")]
+mod last_used;
+#[cfg(feature = "prompts")]
+mod line_prompter;
+mod lint;
+#[cfg(feature = "prompts")]
+mod locale;
+mod observer;
 mod parser;
+#[cfg(feature = "prompts")]
+mod prompter;
 mod providers;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "native")]
+mod receipt;
+#[cfg(feature = "native")]
+mod references;
+#[cfg(feature = "registry")]
+mod registry;
+mod render;
+mod schema;
+#[cfg(feature = "interactive")]
+mod theme;
+mod transform;
+#[cfg(feature = "interactive")]
+mod tui;
 mod types;
+#[cfg(feature = "native")]
+mod uninstall;
+#[cfg(feature = "native")]
+mod variables;
 
+#[cfg(feature = "cli")]
+pub use cli::InstallSkillArgs;
 #[cfg(feature = "interactive")]
-pub use embed::{load_embedded_skill, rust_embed, Embed};
+#[allow(deprecated)]
+pub use embed::load_embedded_skill;
+#[cfg(feature = "interactive")]
+pub use embed::{load_embedded_skills, rust_embed, try_load_embedded_skill, Embed};
 pub use error::{InstallerError, Result};
+pub use flat::render_flat_skill;
+#[cfg(feature = "native")]
 pub use install::{
-    find_existing_destinations, install, print_install_result, resolve_install_target,
+    execute_plan, find_existing_destinations, install, install_many, plan, print_install_result,
+    resolve_install_target,
 };
-#[cfg(feature = "interactive")]
+#[cfg(feature = "native")]
+pub use installed::{detect_installed, list_installed};
+#[cfg(feature = "prompts")]
 pub use interactive::{
-    install_interactive, prompt_provider_selection, prompt_select, InteractiveProviderSelection,
+    install_interactive, install_interactive_many, install_interactive_with,
+    install_interactive_with_options, plan_interactive, plan_interactive_many,
+    prompt_provider_selection_with, InteractiveInstallOptions, InteractiveProviderSelection,
     InteractiveProviderSelectionOptions,
 };
+pub use inventory::skill_files;
+#[cfg(feature = "prompts")]
+pub use last_used::last_used_path;
+#[cfg(feature = "prompts")]
+pub use line_prompter::LinePrompter;
+pub use lint::{lint_skill, lint_skill_for_providers, Diagnostic, Severity, Span};
+#[cfg(feature = "prompts")]
+pub use locale::{set_locale, Locale, Messages};
+pub use observer::{
+    InstallEvent, InstallObserver, InstallObservers, JsonLogObserver, LogObserver,
+    ProgressObserver, SkipReason,
+};
+#[cfg(feature = "native")]
 pub use parser::parse_skill;
+pub use parser::{parse_skill_str, skill_dir_name, slugify_skill_name};
+#[cfg(feature = "prompts")]
+pub use prompter::{Prompter, ProviderPrompt};
+#[cfg(feature = "registry")]
+pub use providers::configured_publish_url;
+#[cfg(feature = "native")]
 pub use providers::{
-    detect_providers, is_agents_provider, normalize_providers, parse_providers_csv,
-    supported_providers, ProviderInfo,
+    detect_provider_version, detect_providers, detect_providers_with_versions, load_project_config,
+    load_providers_file, load_user_providers, resolve_provider_dir,
+};
+pub use providers::{
+    favorite_providers, is_agents_provider, normalize_providers, parse_provider_path,
+    parse_providers_csv, provider_groups, provider_supports_scope, rank_providers,
+    register_provider, register_provider_group, set_favorite_providers, set_project_path,
+    supported_providers, user_config_path, user_dir_env_var, user_providers_path,
+    ProviderCapabilities, ProviderGroup, ProviderInfo, ProviderLimits, PROJECT_CONFIG_FILE,
+};
+#[cfg(feature = "python")]
+pub use python::skillinstaller as python_module;
+#[cfg(feature = "native")]
+pub use receipt::{read_receipt, InstallReceipt, RECEIPT_FILE_NAME};
+#[cfg(feature = "registry")]
+pub use registry::{
+    pack_skill, parse_skill_spec, registry_cache_dir, PackedSkill, RegistryClient, RegistryEntry,
+    RegistryIndex, REGISTRY_SCHEMA_VERSION,
+};
+pub use render::{ColorChoice, OutputFormat, Verbosity};
+pub use schema::{
+    skill_frontmatter_schema, AvailableUpdate, DetectReport, DoctorCheck, DoctorReport,
+    DoctorStatus, InstallBatchReport, InstallFailure, InstalledListReport, InstalledReport,
+    PlanBatchReport, ProviderListReport, ProviderSummary, Versioned, SCHEMA_VERSION,
+};
+#[cfg(feature = "registry")]
+pub use schema::{PublishReport, RegistrySearchReport, UpdateReport};
+#[cfg(feature = "interactive")]
+pub use theme::{set_color_choice, set_theme, Color, Theme};
+pub use transform::{ProviderTransform, ProviderTransforms};
+#[cfg(feature = "interactive")]
+pub use tui::{
+    prompt_confirm, prompt_provider_selection, prompt_select, prompt_text, TerminalPrompter,
 };
 pub use types::{
-    DetectedProvider, EmbeddedSkill, InstallMethod, InstallRequest, InstallResult,
-    InstallSkillArgs, InstallTarget, ParsedSkill, ProviderId, Scope, SkillSource,
+    DetectedProvider, DetectionConfidence, DetectionEvidence, EmbeddedSkill, InstallMethod,
+    InstallOptions, InstallPlan, InstallRequest, InstallResult, InstallStats, InstallTarget,
+    InstalledSkill, ParsedSkill, PlannedAction, PlannedTarget, ProviderId, RegistryPin, Scope,
+    SkillFile, SkillFileKind, SkillHooks, SkillProviders, SkillSource, SkillVariable,
+    SymlinkPolicy, UninstallRequest, UninstallResult,
 };
+#[cfg(feature = "native")]
+pub use uninstall::uninstall;
+
+/// Re-exported so callers can compare `ParsedSkill::version` without a direct dependency.
+pub use semver;
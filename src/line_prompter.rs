@@ -0,0 +1,228 @@
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, StdinLock, Stdout, Write};
+
+use crate::error::{InstallerError, Result};
+use crate::locale::{fill, messages};
+use crate::prompter::{detection_reason, Prompter, ProviderPrompt};
+use crate::providers::provider_display_name;
+use crate::types::ProviderId;
+
+/// Plain-text `Prompter` that prints numbered lists and reads answers a line at a time.
+///
+/// Needs no raw mode, so it works over pipes, dumb terminals, and builds without the
+/// `interactive` feature. `b` goes back a step; end of input cancels.
+///
+/// ```
+/// use std::io::Cursor;
+/// use skillinstaller::{LinePrompter, Prompter};
+///
+/// let mut prompter = LinePrompter::new(Cursor::new("2\n"), Vec::new());
+/// assert_eq!(prompter.select("Scope?", &["Project", "User"], 0).unwrap(), 1);
+/// ```
+pub struct LinePrompter<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> LinePrompter<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+
+    /// Prints `prompt` and returns the trimmed answer.
+    fn ask(&mut self, prompt: &str) -> Result<String> {
+        write!(self.writer, "{prompt} ").map_err(prompt_err)?;
+        self.writer.flush().map_err(prompt_err)?;
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).map_err(prompt_err)? == 0 {
+            writeln!(self.writer).map_err(prompt_err)?;
+            return Err(InstallerError::PromptCancelled);
+        }
+        Ok(line.trim().to_string())
+    }
+
+    fn say(&mut self, line: &str) -> Result<()> {
+        writeln!(self.writer, "{line}").map_err(prompt_err)
+    }
+}
+
+impl LinePrompter<StdinLock<'static>, Stdout> {
+    pub fn stdio() -> Self {
+        Self::new(io::stdin().lock(), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> Prompter for LinePrompter<R, W> {
+    fn select(&mut self, message: &str, options: &[&str], default: usize) -> Result<usize> {
+        self.say(message)?;
+        for (i, option) in options.iter().enumerate() {
+            let marker = if i == default { "*" } else { " " };
+            self.say(&format!(" {marker}{:>2}) {option}", i + 1))?;
+        }
+        loop {
+            let answer = self.ask(&fill(
+                messages().choose,
+                &[("count", &options.len()), ("default", &(default + 1))],
+            ))?;
+            if answer.is_empty() {
+                return Ok(default);
+            }
+            if answer.eq_ignore_ascii_case("b") {
+                return Err(InstallerError::PromptBack);
+            }
+            match answer.parse::<usize>() {
+                Ok(n) if (1..=options.len()).contains(&n) => return Ok(n - 1),
+                _ => self.say(&fill(
+                    messages().choose_number,
+                    &[("count", &options.len())],
+                ))?,
+            }
+        }
+    }
+
+    fn multi_select(&mut self, prompt: &ProviderPrompt<'_>) -> Result<Vec<ProviderId>> {
+        self.say(prompt.message)?;
+        if !prompt.locked.is_empty() {
+            let names = prompt
+                .locked
+                .iter()
+                .map(|p| provider_display_name(*p))
+                .collect::<Vec<_>>();
+            self.say(&format!("  {}: {}", prompt.locked_label, names.join(", ")))?;
+        }
+        let (found, others): (Vec<_>, Vec<_>) = prompt
+            .choices
+            .iter()
+            .copied()
+            .partition(|p| prompt.detected.iter().any(|d| d.provider == *p));
+        let headings = !found.is_empty();
+        let order = [found.as_slice(), others.as_slice()].concat();
+        for (i, provider) in order.iter().enumerate() {
+            if headings && i == 0 {
+                self.say(messages().detected_heading)?;
+            } else if headings && i == found.len() {
+                self.say(messages().others_heading)?;
+            }
+            let marker = if prompt.disabled.contains(provider) {
+                "-"
+            } else if prompt.defaults.contains(provider) {
+                "*"
+            } else {
+                " "
+            };
+            let reason = detection_reason(prompt.detected, *provider)
+                .map(|reason| format!(" ({reason})"))
+                .unwrap_or_default();
+            self.say(&format!(
+                " {marker}{:>2}) {}{reason}",
+                i + 1,
+                provider_display_name(*provider)
+            ))?;
+        }
+        for group in prompt.groups {
+            let names = group
+                .providers
+                .iter()
+                .map(|p| provider_display_name(*p))
+                .collect::<Vec<_>>();
+            self.say(&format!("  {}: {}", group.name, names.join(", ")))?;
+        }
+
+        let enabled = order
+            .iter()
+            .copied()
+            .filter(|p| !prompt.disabled.contains(p))
+            .collect::<Vec<_>>();
+        loop {
+            let answer = self.ask(messages().choose_providers)?;
+            match parse_multi(&answer, prompt, &order, &enabled) {
+                Ok(Some(selected)) => return Ok(selected),
+                Ok(None) => return Err(InstallerError::PromptBack),
+                Err(message) => self.say(&message)?,
+            }
+        }
+    }
+
+    fn confirm(&mut self, message: &str, default: bool) -> Result<bool> {
+        let hint = if default { "[Y/n]" } else { "[y/N]" };
+        loop {
+            let answer = self.ask(&format!("{message} {hint}"))?;
+            match answer.to_ascii_lowercase().as_str() {
+                "" => return Ok(default),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                "b" => return Err(InstallerError::PromptBack),
+                _ => self.say(messages().answer_yes_no)?,
+            }
+        }
+    }
+
+    fn text(&mut self, message: &str, default: Option<&str>) -> Result<String> {
+        let prompt = match default {
+            Some(default) => format!("{message} [{default}]:"),
+            None => format!("{message}:"),
+        };
+        let answer = self.ask(&prompt)?;
+        Ok(match default {
+            Some(default) if answer.is_empty() => default.to_string(),
+            _ => answer,
+        })
+    }
+}
+
+/// Numbers index `order`, the choices as listed. `None` means go back; `Err` carries a
+/// message to show before asking again.
+fn parse_multi(
+    answer: &str,
+    prompt: &ProviderPrompt<'_>,
+    order: &[ProviderId],
+    enabled: &[ProviderId],
+) -> std::result::Result<Option<Vec<ProviderId>>, String> {
+    let keep = |p: &ProviderId| enabled.contains(p);
+    match answer.to_ascii_lowercase().as_str() {
+        "" => return Ok(Some(prompt.defaults.iter().copied().filter(keep).collect())),
+        "a" | "all" => return Ok(Some(enabled.to_vec())),
+        "n" | "none" => return Ok(Some(Vec::new())),
+        "b" => return Ok(None),
+        _ => {}
+    }
+
+    let mut picked = BTreeSet::new();
+    for token in answer
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+    {
+        if let Ok(n) = token.parse::<usize>() {
+            let provider = n
+                .checked_sub(1)
+                .and_then(|i| order.get(i))
+                .ok_or_else(|| fill(messages().no_such_choice, &[("number", &n)]))?;
+            if !keep(provider) {
+                let name = provider_display_name(*provider);
+                return Err(fill(messages().not_selectable, &[("name", &name)]));
+            }
+            picked.insert(n - 1);
+        } else if let Some(group) = prompt.groups.iter().find(|g| g.name == token) {
+            picked.extend(
+                order
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| keep(p) && group.providers.contains(p))
+                    .map(|(i, _)| i),
+            );
+        } else {
+            return Err(fill(messages().unknown_choice, &[("choice", &token)]));
+        }
+    }
+    Ok(Some(picked.into_iter().map(|i| order[i]).collect()))
+}
+
+fn prompt_err(err: io::Error) -> InstallerError {
+    InstallerError::PromptError {
+        message: err.to_string(),
+    }
+}
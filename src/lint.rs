@@ -0,0 +1,207 @@
+use crate::providers::{provider_capabilities, provider_display_name};
+use crate::types::{ParsedSkill, ProviderId};
+
+/// Limits from the Agent Skills specification.
+const MAX_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 1024;
+const MAX_BODY_LINES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Byte range into the linted `SKILL.md`, with the 1-based line it starts on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable kebab-case rule id, e.g. `description-too-long`.
+    pub rule: &'static str,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+/// Checks a parsed skill against authoring guidelines. `payload` is the raw `SKILL.md`
+/// the skill was parsed from and is only used to locate spans.
+pub fn lint_skill(skill: &ParsedSkill, payload: &[u8]) -> Vec<Diagnostic> {
+    let text = String::from_utf8_lossy(payload);
+    let mut diagnostics = Vec::new();
+
+    let (namespace, short_name) = match skill.name.split_once('/') {
+        Some((namespace, short_name)) => (Some(namespace), short_name),
+        None => (None, skill.name.as_str()),
+    };
+    if !namespace.is_none_or(is_kebab_case)
+        || !is_kebab_case(short_name)
+        || short_name.len() > MAX_NAME_LEN
+    {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            rule: "name-not-kebab-case",
+            message: format!(
+                "name '{}' should be lowercase letters, digits, and single hyphens, at most {MAX_NAME_LEN} characters, optionally after an `org/` namespace",
+                skill.name
+            ),
+            span: key_span(&text, "name"),
+        });
+    }
+
+    match skill.description.as_deref().map(str::trim) {
+        None | Some("") => diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            rule: "missing-description",
+            message: "description is required so agents know when to use the skill".to_string(),
+            span: key_span(&text, "description"),
+        }),
+        Some(description) if description.chars().count() > MAX_DESCRIPTION_LEN => {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                rule: "description-too-long",
+                message: format!(
+                    "description is {} characters; keep it under {MAX_DESCRIPTION_LEN}",
+                    description.chars().count()
+                ),
+                span: key_span(&text, "description"),
+            })
+        }
+        Some(_) => {}
+    }
+
+    let body_lines = skill.body.lines().count();
+    if body_lines > MAX_BODY_LINES {
+        let start = text.len().saturating_sub(skill.body.len());
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            rule: "body-too-long",
+            message: format!(
+                "body is {body_lines} lines; move detail into referenced files to stay under {MAX_BODY_LINES}"
+            ),
+            span: Some(Span {
+                start,
+                end: text.len(),
+                line: line_of(&text, start),
+            }),
+        });
+    }
+
+    diagnostics
+}
+
+/// `lint_skill` plus a warning for every limit of `providers` the skill exceeds.
+pub fn lint_skill_for_providers(
+    skill: &ParsedSkill,
+    payload: &[u8],
+    providers: &[ProviderId],
+) -> Vec<Diagnostic> {
+    let text = String::from_utf8_lossy(payload);
+    let mut diagnostics = lint_skill(skill, payload);
+    for &provider in providers {
+        for (key, message) in limit_violations(skill, provider) {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                rule: "exceeds-provider-limit",
+                message,
+                span: key_span(&text, key),
+            });
+        }
+    }
+    diagnostics
+}
+
+#[cfg(feature = "native")]
+pub(crate) fn provider_limit_warnings(
+    skill: &ParsedSkill,
+    providers: &[ProviderId],
+) -> Vec<String> {
+    providers
+        .iter()
+        .flat_map(|&provider| limit_violations(skill, provider))
+        .map(|(_, message)| message)
+        .collect()
+}
+
+/// The frontmatter key at fault and a message for each limit `skill` exceeds.
+fn limit_violations(skill: &ParsedSkill, provider: ProviderId) -> Vec<(&'static str, String)> {
+    let limits = provider_capabilities(provider).limits;
+    let display = provider_display_name(provider);
+    let mut violations = Vec::new();
+
+    let name_len = skill.name.chars().count();
+    if let Some(max) = limits.max_name_len.filter(|max| name_len > *max) {
+        violations.push((
+            "name",
+            format!("name is {name_len} characters; {display} accepts at most {max}"),
+        ));
+    }
+
+    let description_len = skill
+        .description
+        .as_deref()
+        .map_or(0, |d| d.trim().chars().count());
+    if let Some(max) = limits
+        .max_description_len
+        .filter(|max| description_len > *max)
+    {
+        violations.push((
+            "description",
+            format!("description is {description_len} characters; {display} truncates it to {max}"),
+        ));
+    }
+
+    if let (Some(allowed), Some(metadata)) = (limits.metadata_keys, &skill.metadata) {
+        for key in metadata
+            .keys()
+            .filter(|key| !allowed.contains(&key.as_str()))
+        {
+            violations.push((
+                "metadata",
+                format!("metadata key '{key}' is ignored by {display}"),
+            ));
+        }
+    }
+
+    violations
+}
+
+fn is_kebab_case(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// The first top-level `key:` line, spanning the whole line.
+fn key_span(text: &str, key: &str) -> Option<Span> {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        {
+            return Some(Span {
+                start: offset,
+                end: offset + line.trim_end().len(),
+                line: line_of(text, offset),
+            });
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset].matches('\n').count() + 1
+}
@@ -0,0 +1,278 @@
+use std::fmt::Display;
+use std::sync::RwLock;
+
+/// Language of the interactive prompts; errors and CLI output stay in English.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Es,
+    /// A catalog supplied by the host, e.g. a community translation.
+    Custom(&'static Messages),
+}
+
+impl Locale {
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Locale::En => &Messages::EN,
+            Locale::De => &Messages::DE,
+            Locale::Es => &Messages::ES,
+            Locale::Custom(messages) => messages,
+        }
+    }
+}
+
+/// Every string the interactive installer shows. Placeholders in braces, such as
+/// `{path}`, are filled in; prompt titles keep their leading `◆`.
+///
+/// Start a translation from an existing catalog: `Messages { yes: "Oui", ..Messages::EN }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Messages {
+    pub select_providers: &'static str,
+    pub universal_label: &'static str,
+    pub providers_prompt: &'static str,
+    pub scope_prompt: &'static str,
+    pub project_scope: &'static str,
+    pub user_scope: &'static str,
+    pub method_prompt: &'static str,
+    pub symlink: &'static str,
+    pub copy: &'static str,
+    /// `{name}`
+    pub variable_prompt: &'static str,
+    /// `{description}`, `{name}`
+    pub variable_described: &'static str,
+    /// `{path}`
+    pub overwrite_prompt: &'static str,
+    /// `{path}`
+    pub conflict_prompt: &'static str,
+    pub overwrite: &'static str,
+    pub skip: &'static str,
+    pub back_up: &'static str,
+    /// `{skill}`, with its version when it has one.
+    pub install_prompt: &'static str,
+    pub overwrites_existing: &'static str,
+    pub yes: &'static str,
+    pub no: &'static str,
+    pub detected_heading: &'static str,
+    pub others_heading: &'static str,
+    pub additional_agents: &'static str,
+    pub always_included: &'static str,
+    pub none: &'static str,
+    pub search: &'static str,
+    pub selected: &'static str,
+    pub picker_hint: &'static str,
+    pub select_hint: &'static str,
+    pub select_back_hint: &'static str,
+    pub no_matches: &'static str,
+    /// `{count}`
+    pub more_above: &'static str,
+    /// `{count}`
+    pub more_below: &'static str,
+    /// `{count}`
+    pub and_more: &'static str,
+    pub nothing_selected: &'static str,
+    pub unsupported: &'static str,
+    /// `{path}`
+    pub installs_to: &'static str,
+    /// `{confidence}`
+    pub detected: &'static str,
+    /// `{confidence}`, `{version}`
+    pub detected_version: &'static str,
+    pub not_detected: &'static str,
+    pub up_to_date: &'static str,
+    pub skipped: &'static str,
+    /// `{count}`, `{default}`
+    pub choose: &'static str,
+    /// `{count}`
+    pub choose_number: &'static str,
+    pub choose_providers: &'static str,
+    pub answer_yes_no: &'static str,
+    /// `{number}`
+    pub no_such_choice: &'static str,
+    /// `{name}`
+    pub not_selectable: &'static str,
+    /// `{choice}`
+    pub unknown_choice: &'static str,
+}
+
+impl Messages {
+    pub const EN: Messages = Messages {
+        select_providers: "Select providers to install to",
+        universal_label: "Universal (.agents/skills)",
+        providers_prompt: "◆  Select providers to install to",
+        scope_prompt: "◆  Installation scope",
+        project_scope: "Project (Install in current directory (committed with your project))",
+        user_scope: "Global",
+        method_prompt: "◆  Installation method",
+        symlink: "Symlink (Recommended) (Single source of truth, easy updates)",
+        copy: "Copy to all agents",
+        variable_prompt: "◆  Value for {name}",
+        variable_described: "◆  {description} [{name}]",
+        overwrite_prompt: "◆  Skill already exists at {path}. Overwrite?",
+        conflict_prompt: "◆  Skill already exists at {path}",
+        overwrite: "Overwrite",
+        skip: "Skip",
+        back_up: "Back up, then overwrite",
+        install_prompt: "◆  Install {skill}?",
+        overwrites_existing: "overwrites existing",
+        yes: "Yes",
+        no: "No",
+        detected_heading: "Detected on this machine",
+        others_heading: "Other agents",
+        additional_agents: "Additional agents",
+        always_included: "always included",
+        none: "none",
+        search: "Search: ",
+        selected: "Selected: ",
+        picker_hint:
+            "↑↓ move, space select, type to search, tab then jk move, a all, n none, i invert, enter confirm",
+        select_hint: "↑↓/jk move, enter confirm",
+        select_back_hint: "↑↓/jk move, enter confirm, ← back",
+        no_matches: "No matches found",
+        more_above: "↑ {count} more",
+        more_below: "↓ {count} more",
+        and_more: "+{count} more",
+        nothing_selected: "(none)",
+        unsupported: "unsupported by this skill",
+        installs_to: "installs to {path}",
+        detected: "detected ({confidence} confidence)",
+        detected_version: "detected ({confidence} confidence, version {version})",
+        not_detected: "not detected",
+        up_to_date: "up to date",
+        skipped: "skipped",
+        choose: "Choose 1-{count} [{default}]:",
+        choose_number: "Enter a number from 1 to {count}.",
+        choose_providers: "Numbers or group names, `a` for all, `n` for none [*]:",
+        answer_yes_no: "Answer y or n.",
+        no_such_choice: "No choice numbered {number}.",
+        not_selectable: "{name} can't be selected.",
+        unknown_choice: "Unknown choice '{choice}'.",
+    };
+
+    pub const DE: Messages = Messages {
+        select_providers: "Anbieter für die Installation auswählen",
+        universal_label: "Universell (.agents/skills)",
+        providers_prompt: "◆  Anbieter für die Installation auswählen",
+        scope_prompt: "◆  Installationsbereich",
+        project_scope: "Projekt (Im aktuellen Verzeichnis installieren (wird mit dem Projekt eingecheckt))",
+        user_scope: "Global",
+        method_prompt: "◆  Installationsmethode",
+        symlink: "Symlink (Empfohlen) (Eine Quelle, einfache Updates)",
+        copy: "In jeden Agenten kopieren",
+        variable_prompt: "◆  Wert für {name}",
+        variable_described: "◆  {description} [{name}]",
+        overwrite_prompt: "◆  Skill existiert bereits unter {path}. Überschreiben?",
+        conflict_prompt: "◆  Skill existiert bereits unter {path}",
+        overwrite: "Überschreiben",
+        skip: "Überspringen",
+        back_up: "Sichern, dann überschreiben",
+        install_prompt: "◆  {skill} installieren?",
+        overwrites_existing: "überschreibt vorhandene Installation",
+        yes: "Ja",
+        no: "Nein",
+        detected_heading: "Auf diesem Rechner erkannt",
+        others_heading: "Weitere Agenten",
+        additional_agents: "Zusätzliche Agenten",
+        always_included: "immer enthalten",
+        none: "keine",
+        search: "Suche: ",
+        selected: "Ausgewählt: ",
+        picker_hint: "↑↓ bewegen, Leertaste wählen, tippen sucht, Tab dann jk bewegen, a alle, n keine, i umkehren, Enter bestätigen",
+        select_hint: "↑↓/jk bewegen, Enter bestätigen",
+        select_back_hint: "↑↓/jk bewegen, Enter bestätigen, ← zurück",
+        no_matches: "Keine Treffer",
+        more_above: "↑ {count} weitere",
+        more_below: "↓ {count} weitere",
+        and_more: "+{count} weitere",
+        nothing_selected: "(keine)",
+        unsupported: "von diesem Skill nicht unterstützt",
+        installs_to: "installiert nach {path}",
+        detected: "erkannt (Sicherheit: {confidence})",
+        detected_version: "erkannt (Sicherheit: {confidence}, Version {version})",
+        not_detected: "nicht erkannt",
+        up_to_date: "aktuell",
+        skipped: "übersprungen",
+        choose: "Auswahl 1-{count} [{default}]:",
+        choose_number: "Bitte eine Zahl von 1 bis {count} eingeben.",
+        choose_providers: "Nummern oder Gruppennamen, `a` für alle, `n` für keine [*]:",
+        answer_yes_no: "Bitte y oder n eingeben.",
+        no_such_choice: "Keine Auswahl mit der Nummer {number}.",
+        not_selectable: "{name} kann nicht ausgewählt werden.",
+        unknown_choice: "Unbekannte Auswahl '{choice}'.",
+    };
+
+    pub const ES: Messages = Messages {
+        select_providers: "Selecciona los proveedores donde instalar",
+        universal_label: "Universal (.agents/skills)",
+        providers_prompt: "◆  Selecciona los proveedores donde instalar",
+        scope_prompt: "◆  Ámbito de instalación",
+        project_scope: "Proyecto (Instalar en el directorio actual (se versiona con tu proyecto))",
+        user_scope: "Global",
+        method_prompt: "◆  Método de instalación",
+        symlink: "Enlace simbólico (Recomendado) (Una sola fuente, actualizaciones sencillas)",
+        copy: "Copiar a todos los agentes",
+        variable_prompt: "◆  Valor para {name}",
+        variable_described: "◆  {description} [{name}]",
+        overwrite_prompt: "◆  El skill ya existe en {path}. ¿Sobrescribir?",
+        conflict_prompt: "◆  El skill ya existe en {path}",
+        overwrite: "Sobrescribir",
+        skip: "Omitir",
+        back_up: "Hacer copia de seguridad y sobrescribir",
+        install_prompt: "◆  ¿Instalar {skill}?",
+        overwrites_existing: "sobrescribe la instalación existente",
+        yes: "Sí",
+        no: "No",
+        detected_heading: "Detectados en este equipo",
+        others_heading: "Otros agentes",
+        additional_agents: "Agentes adicionales",
+        always_included: "siempre incluidos",
+        none: "ninguno",
+        search: "Buscar: ",
+        selected: "Seleccionados: ",
+        picker_hint: "↑↓ mover, espacio seleccionar, escribir busca, tab y luego jk mover, a todos, n ninguno, i invertir, enter confirmar",
+        select_hint: "↑↓/jk mover, enter confirmar",
+        select_back_hint: "↑↓/jk mover, enter confirmar, ← atrás",
+        no_matches: "Sin resultados",
+        more_above: "↑ {count} más",
+        more_below: "↓ {count} más",
+        and_more: "+{count} más",
+        nothing_selected: "(ninguno)",
+        unsupported: "no compatible con este skill",
+        installs_to: "se instala en {path}",
+        detected: "detectado (confianza: {confidence})",
+        detected_version: "detectado (confianza: {confidence}, versión {version})",
+        not_detected: "no detectado",
+        up_to_date: "al día",
+        skipped: "omitido",
+        choose: "Elige 1-{count} [{default}]:",
+        choose_number: "Introduce un número del 1 al {count}.",
+        choose_providers: "Números o nombres de grupo, `a` para todos, `n` para ninguno [*]:",
+        answer_yes_no: "Responde y o n.",
+        no_such_choice: "No hay ninguna opción con el número {number}.",
+        not_selectable: "{name} no se puede seleccionar.",
+        unknown_choice: "Opción desconocida '{choice}'.",
+    };
+}
+
+static LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+/// Switches the prompts to `locale`; option structs built afterwards take their default
+/// wording from it too.
+pub fn set_locale(locale: Locale) {
+    *LOCALE.write().unwrap_or_else(|e| e.into_inner()) = locale;
+}
+
+pub(crate) fn messages() -> &'static Messages {
+    LOCALE.read().unwrap_or_else(|e| e.into_inner()).messages()
+}
+
+/// Replaces each `{key}` in `template` with its value.
+pub(crate) fn fill(template: &str, values: &[(&str, &dyn Display)]) -> String {
+    let mut out = template.to_string();
+    for (key, value) in values {
+        out = out.replace(&format!("{{{key}}}"), &value.to_string());
+    }
+    out
+}
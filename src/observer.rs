@@ -0,0 +1,410 @@
+use std::fmt;
+use std::io::Write;
+#[cfg(feature = "native")]
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "native")]
+use crate::error::{InstallerError, Result};
+use crate::render::Verbosity;
+use crate::types::ProviderId;
+
+/// Lifecycle events emitted while `install()` runs, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallEvent {
+    /// The skill was parsed and is about to be installed.
+    SourceResolved {
+        skill: String,
+        version: Option<semver::Version>,
+        files: usize,
+    },
+    /// A provider's destination was resolved; its outcome follows.
+    ProviderResolved {
+        provider: ProviderId,
+        destination: PathBuf,
+    },
+    /// A payload file is being copied for `provider`; `file` is relative to the skill root
+    /// and `bytes` is its size (0 for links).
+    Copying {
+        provider: ProviderId,
+        file: PathBuf,
+        bytes: u64,
+    },
+    /// `link` now points at the shared copy at `target`.
+    Linked {
+        provider: ProviderId,
+        link: PathBuf,
+        target: PathBuf,
+    },
+    Skipped {
+        provider: ProviderId,
+        destination: PathBuf,
+        reason: SkipReason,
+    },
+    Completed {
+        skill: String,
+        targets: usize,
+        elapsed: Duration,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Another provider in the same request resolves to this destination.
+    Duplicate,
+    /// An earlier request in the same `install_many` batch wrote it.
+    ClaimedByBatch,
+    /// The destination already has identical content.
+    UpToDate,
+    /// `upgrade` found the installed version at or above the source version.
+    NotNewer,
+}
+
+impl SkipReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkipReason::Duplicate => "duplicate",
+            SkipReason::ClaimedByBatch => "claimed-by-batch",
+            SkipReason::UpToDate => "up-to-date",
+            SkipReason::NotNewer => "not-newer",
+        }
+    }
+}
+
+pub trait InstallObserver: Send + Sync {
+    fn on_event(&self, event: &InstallEvent);
+}
+
+impl<F> InstallObserver for F
+where
+    F: Fn(&InstallEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &InstallEvent) {
+        self(event)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct InstallObservers(Vec<Arc<dyn InstallObserver>>);
+
+impl InstallObservers {
+    pub fn push(&mut self, observer: Arc<dyn InstallObserver>) {
+        self.0.push(observer);
+    }
+
+    #[cfg(feature = "native")]
+    pub(crate) fn emit(&self, event: InstallEvent) {
+        for observer in &self.0 {
+            observer.on_event(&event);
+        }
+    }
+}
+
+impl fmt::Debug for InstallObservers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstallObservers")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// Writes install events to stderr: one line per destination at `Verbose`, plus one per
+/// copied file at `Debug`. Quieter levels write nothing.
+#[derive(Debug, Clone, Copy)]
+pub struct LogObserver {
+    pub verbosity: Verbosity,
+}
+
+impl LogObserver {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Self { verbosity }
+    }
+
+    /// The line for `event`, or `None` when it is below this observer's verbosity.
+    pub fn line(&self, event: &InstallEvent) -> Option<String> {
+        let debug = self.verbosity >= Verbosity::Debug;
+        if self.verbosity < Verbosity::Verbose {
+            return None;
+        }
+        Some(match event {
+            InstallEvent::SourceResolved {
+                skill,
+                version,
+                files,
+            } if debug => match version {
+                Some(version) => format!("source: {skill} {version}, {files} files"),
+                None => format!("source: {skill}, {files} files"),
+            },
+            InstallEvent::ProviderResolved {
+                provider,
+                destination,
+            } => format!("{}: {}", provider.as_str(), destination.display()),
+            InstallEvent::Copying { provider, file, .. } if debug => {
+                format!("{}: copy {}", provider.as_str(), file.display())
+            }
+            InstallEvent::Linked {
+                provider,
+                link,
+                target,
+            } => format!(
+                "{}: link {} -> {}",
+                provider.as_str(),
+                link.display(),
+                target.display()
+            ),
+            InstallEvent::Skipped {
+                provider,
+                destination,
+                reason,
+            } => format!(
+                "{}: skipped {} ({})",
+                provider.as_str(),
+                destination.display(),
+                reason.as_str()
+            ),
+            InstallEvent::Completed {
+                skill,
+                targets,
+                elapsed,
+            } => format!("installed {skill} to {targets} targets in {elapsed:.2?}"),
+            _ => return None,
+        })
+    }
+}
+
+impl InstallObserver for LogObserver {
+    fn on_event(&self, event: &InstallEvent) {
+        if let Some(line) = self.line(event) {
+            eprintln!("{line}");
+        }
+    }
+}
+
+/// Redraws one stderr line per destination with the files and bytes copied so far, so a
+/// long copy shows it is still moving. Meant for a terminal; the CLI adds it at the default
+/// verbosity when stderr is one.
+pub struct ProgressObserver {
+    state: Mutex<ProgressState>,
+}
+
+struct ProgressState {
+    out: Box<dyn Write + Send>,
+    total_files: usize,
+    current: Option<(ProviderId, usize, u64)>,
+    drawn: Option<Instant>,
+}
+
+impl ProgressObserver {
+    const REDRAW: Duration = Duration::from_millis(100);
+
+    pub fn new() -> Self {
+        Self::with_writer(Box::new(std::io::stderr()))
+    }
+
+    pub fn with_writer(out: Box<dyn Write + Send>) -> Self {
+        Self {
+            state: Mutex::new(ProgressState {
+                out,
+                total_files: 0,
+                current: None,
+                drawn: None,
+            }),
+        }
+    }
+}
+
+impl Default for ProgressObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ProgressObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressObserver").finish_non_exhaustive()
+    }
+}
+
+impl InstallObserver for ProgressObserver {
+    fn on_event(&self, event: &InstallEvent) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match event {
+            InstallEvent::SourceResolved { files, .. } => state.total_files = *files,
+            InstallEvent::Copying {
+                provider, bytes, ..
+            } => {
+                if state.current.is_some_and(|(p, _, _)| p != *provider) {
+                    state.finish();
+                }
+                let (_, files, total) = state.current.get_or_insert((*provider, 0, 0));
+                *files += 1;
+                *total += bytes;
+                if state.drawn.is_none_or(|at| at.elapsed() >= Self::REDRAW) {
+                    state.draw(false);
+                }
+            }
+            InstallEvent::ProviderResolved { .. } | InstallEvent::Completed { .. } => {
+                state.finish()
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ProgressState {
+    fn draw(&mut self, done: bool) {
+        let Some((provider, files, bytes)) = self.current else {
+            return;
+        };
+        let count = if files <= self.total_files {
+            format!("{files}/{}", self.total_files)
+        } else {
+            files.to_string()
+        };
+        let end = if done { "\n" } else { "" };
+        let _ = write!(
+            self.out,
+            "\r\x1b[2K{}: {count} files, {}{end}",
+            provider.as_str(),
+            format_bytes(bytes)
+        );
+        let _ = self.out.flush();
+        self.drawn = Some(Instant::now());
+    }
+
+    /// Leaves the current line at its final count.
+    fn finish(&mut self) {
+        self.draw(true);
+        self.current = None;
+        self.drawn = None;
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Appends one JSON object per line for every install event, e.g.
+/// `{"event":"copying","elapsed-ms":3,"provider":"claude-code","file":"SKILL.md","bytes":95}`,
+/// for `--log-file`. `record` adds lines of the host's own.
+pub struct JsonLogObserver {
+    out: Mutex<Box<dyn Write + Send>>,
+    started: Instant,
+}
+
+impl JsonLogObserver {
+    pub fn new(out: Box<dyn Write + Send>) -> Self {
+        Self {
+            out: Mutex::new(out),
+            started: Instant::now(),
+        }
+    }
+
+    /// Creates or truncates `path`.
+    #[cfg(feature = "native")]
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = std::fs::File::create(path).map_err(|err| InstallerError::IoError {
+            path: path.to_path_buf(),
+            message: format!("failed to create log file: {err}"),
+        })?;
+        Ok(Self::new(Box::new(std::io::LineWriter::new(file))))
+    }
+
+    /// Writes `fields`, which should be a JSON object, tagged with `event` and the time
+    /// since this log was opened.
+    pub fn record(&self, event: &str, fields: serde_json::Value) {
+        let mut line = serde_json::Map::new();
+        line.insert("event".into(), event.into());
+        line.insert(
+            "elapsed-ms".into(),
+            (self.started.elapsed().as_millis() as u64).into(),
+        );
+        if let serde_json::Value::Object(fields) = fields {
+            line.extend(fields);
+        }
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(out, "{}", serde_json::Value::Object(line));
+    }
+}
+
+impl fmt::Debug for JsonLogObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLogObserver").finish_non_exhaustive()
+    }
+}
+
+impl InstallObserver for JsonLogObserver {
+    fn on_event(&self, event: &InstallEvent) {
+        use serde_json::json;
+
+        match event {
+            InstallEvent::SourceResolved {
+                skill,
+                version,
+                files,
+            } => self.record(
+                "source-resolved",
+                json!({ "skill": skill, "version": version, "files": files }),
+            ),
+            InstallEvent::ProviderResolved {
+                provider,
+                destination,
+            } => self.record(
+                "provider-resolved",
+                json!({ "provider": provider.as_str(), "destination": destination }),
+            ),
+            InstallEvent::Copying {
+                provider,
+                file,
+                bytes,
+            } => self.record(
+                "copying",
+                json!({ "provider": provider.as_str(), "file": file, "bytes": bytes }),
+            ),
+            InstallEvent::Linked {
+                provider,
+                link,
+                target,
+            } => self.record(
+                "linked",
+                json!({ "provider": provider.as_str(), "link": link, "target": target }),
+            ),
+            InstallEvent::Skipped {
+                provider,
+                destination,
+                reason,
+            } => self.record(
+                "skipped",
+                json!({
+                    "provider": provider.as_str(),
+                    "destination": destination,
+                    "reason": reason.as_str(),
+                }),
+            ),
+            InstallEvent::Completed {
+                skill,
+                targets,
+                elapsed,
+            } => self.record(
+                "completed",
+                json!({
+                    "skill": skill,
+                    "targets": targets,
+                    "duration-ms": elapsed.as_millis() as u64,
+                }),
+            ),
+        }
+    }
+}
@@ -1,79 +1,427 @@
 use std::collections::BTreeMap;
+#[cfg(feature = "native")]
 use std::fs;
-use std::path::{Path, PathBuf};
+#[cfg(feature = "native")]
+use std::path::PathBuf;
+use std::path::{Component, Path};
 
-use serde_yaml::Value;
+use serde_yaml::{Mapping, Value};
 
 use crate::error::{InstallerError, Result};
-use crate::types::{ParsedSkill, SkillSource};
+#[cfg(feature = "native")]
+use crate::inventory::skill_files;
+use crate::providers::edit_distance;
+#[cfg(feature = "native")]
+use crate::references::missing_reference_warnings;
+#[cfg(feature = "native")]
+use crate::types::SkillSource;
+use crate::types::{ParsedSkill, ProviderId, SkillHooks, SkillProviders, SkillVariable};
 
+#[cfg(feature = "native")]
 pub fn parse_skill(source: &SkillSource) -> Result<ParsedSkill> {
-    let skill_md = match source {
+    let mut parsed = parse_source(source, false)?;
+    parsed.files = skill_files(source, false)?;
+    Ok(parsed)
+}
+
+/// Like `parse_skill`, optionally replacing the name with `slugify_skill_name`. Leaves
+/// `files` empty: installs hash the payload anyway and need no separate inventory.
+#[cfg(feature = "native")]
+pub(crate) fn parse_source(source: &SkillSource, slugify: bool) -> Result<ParsedSkill> {
+    trace_span!(DEBUG, "parse_skill");
+    let (skill_md, root) = match source {
         SkillSource::LocalPath(path) => {
             let root = resolve_local_skill_root(path)?;
-            fs::read_to_string(root.join("SKILL.md")).map_err(|err| InstallerError::IoError {
-                path: root.join("SKILL.md"),
-                message: err.to_string(),
-            })?
+            let skill_md = fs::read_to_string(root.join("SKILL.md")).map_err(|err| {
+                InstallerError::IoError {
+                    path: root.join("SKILL.md"),
+                    message: err.to_string(),
+                }
+            })?;
+            (skill_md, Some(root))
         }
-        SkillSource::Embedded(embedded) => embedded.skill_md.clone(),
+        SkillSource::Embedded(embedded) => (embedded.skill_md.clone(), None),
     };
 
-    let (frontmatter, body) = split_frontmatter(&skill_md)?;
+    let mut parsed = parse_skill_md(&skill_md, slugify)?;
+    let references = missing_reference_warnings(&parsed.body, source, root.as_deref());
+    parsed.warnings.extend(references);
+    trace_event!(
+        debug,
+        skill = %parsed.name,
+        warnings = parsed.warnings.len(),
+        "parsed skill"
+    );
+    Ok(parsed)
+}
+
+/// Parses `SKILL.md` content directly; references to payload files are not checked.
+pub fn parse_skill_str(skill_md: &str) -> Result<ParsedSkill> {
+    parse_skill_md(skill_md, false)
+}
+
+fn parse_skill_md(skill_md: &str, slugify: bool) -> Result<ParsedSkill> {
+    let (frontmatter, body) = split_frontmatter(skill_md)?;
     let yaml: Value =
         serde_yaml::from_str(frontmatter).map_err(|err| InstallerError::InvalidFrontmatter {
             message: err.to_string(),
         })?;
-
-    let map = yaml
-        .as_mapping()
-        .ok_or_else(|| InstallerError::InvalidFrontmatter {
+    let Value::Mapping(mut yaml) = yaml else {
+        return Err(InstallerError::InvalidFrontmatter {
             message: "frontmatter must be a YAML mapping".to_string(),
-        })?;
+        });
+    };
+    stringify_text_fields(&mut yaml)?;
+    let fields: Frontmatter = serde_yaml::from_value(Value::Mapping(yaml)).map_err(|err| {
+        InstallerError::InvalidFrontmatter {
+            message: err.to_string(),
+        }
+    })?;
 
-    let name = map
-        .get(Value::from("name"))
-        .and_then(Value::as_str)
+    let name = fields
+        .name
+        .as_deref()
         .map(str::trim)
         .filter(|s| !s.is_empty())
         .ok_or(InstallerError::MissingName)?
         .to_string();
 
+    let (name, display_name) = if slugify {
+        let slug = slugify_skill_name(&name);
+        if slug.is_empty() {
+            return Err(InstallerError::InvalidName { name });
+        }
+        if slug == name {
+            (slug, None)
+        } else {
+            (slug, Some(name))
+        }
+    } else {
+        (name, None)
+    };
     validate_skill_name(&name)?;
 
-    let description = map
-        .get(Value::from("description"))
-        .and_then(Value::as_str)
-        .map(ToString::to_string);
+    // Non-string metadata values have never been part of the spec; they are dropped.
+    let metadata = fields
+        .metadata
+        .map(|meta| {
+            meta.into_iter()
+                .filter_map(|(key, value)| value.as_str().map(|v| (key, v.to_string())))
+                .collect::<BTreeMap<_, _>>()
+        })
+        .filter(|m| !m.is_empty());
+
+    let mut warnings = unknown_field_warnings("", &fields.unknown, FRONTMATTER_FIELDS);
 
-    let allowed_tools = map
-        .get(Value::from("allowed-tools"))
-        .and_then(Value::as_str)
-        .map(ToString::to_string);
+    let version =
+        match fields.version.as_deref().map(str::trim) {
+            Some(raw) => Some(semver::Version::parse(raw).map_err(|err| {
+                InstallerError::InvalidFrontmatter {
+                    message: format!("version '{raw}' is not semver: {err}"),
+                }
+            })?),
+            None => metadata
+                .as_ref()
+                .and_then(|m| m.get("version"))
+                .and_then(|raw| match semver::Version::parse(raw.trim()) {
+                    Ok(version) => Some(version),
+                    Err(_) => {
+                        warnings.push(format!(
+                            "metadata.version '{raw}' is not semver; upgrades cannot compare it"
+                        ));
+                        None
+                    }
+                }),
+        };
 
-    let metadata = map
-        .get(Value::from("metadata"))
-        .and_then(Value::as_mapping)
-        .map(|meta| {
-            let mut out = BTreeMap::new();
-            for (k, v) in meta {
-                if let (Some(key), Some(value)) = (k.as_str(), v.as_str()) {
-                    out.insert(key.to_string(), value.to_string());
+    let hooks = match fields.hooks {
+        Some(hooks) => {
+            warnings.extend(unknown_field_warnings(
+                "hooks.",
+                &hooks.unknown,
+                HOOK_FIELDS,
+            ));
+            for (stage, path) in [
+                ("pre-install", &hooks.pre_install),
+                ("post-install", &hooks.post_install),
+            ] {
+                if let Some(path) = path {
+                    validate_hook_path(stage, path)?;
+                }
+            }
+            SkillHooks {
+                pre_install: hooks.pre_install,
+                post_install: hooks.post_install,
+            }
+        }
+        None => SkillHooks::default(),
+    };
+
+    let providers = match fields.providers {
+        Some(FrontmatterProviders::Allow(allow)) => SkillProviders {
+            allow: Some(allowed_provider_ids(allow, &mut warnings)?),
+            deny: Vec::new(),
+        },
+        Some(FrontmatterProviders::Rules { allow, deny }) => SkillProviders {
+            allow: allow
+                .map(|allow| allowed_provider_ids(allow, &mut warnings))
+                .transpose()?,
+            deny: provider_ids(deny, &mut warnings),
+        },
+        None => SkillProviders::default(),
+    };
+
+    let platforms = fields
+        .platforms
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|raw| {
+            let platform = match raw.trim().to_ascii_lowercase().as_str() {
+                "linux" => "linux",
+                "macos" | "darwin" | "osx" => "macos",
+                "windows" | "win32" => "windows",
+                "unix" => "unix",
+                _ => {
+                    warnings.push(format!("unknown platform '{raw}' in frontmatter platforms"));
+                    return None;
                 }
+            };
+            Some(platform.to_string())
+        })
+        .collect();
+
+    let variables = fields
+        .variables
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, variable)| {
+            let valid = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if !valid {
+                return Err(InstallerError::InvalidFrontmatter {
+                    message: format!("invalid variable name '{name}'"),
+                });
             }
-            out
+            let (description, default) = match variable {
+                FrontmatterVariable::Spec(spec) => (spec.description, spec.default),
+                FrontmatterVariable::Default(default) => (None, Some(default)),
+            };
+            let default = default
+                .map(|value| match value {
+                    Value::String(s) => Ok(s),
+                    Value::Number(n) => Ok(n.to_string()),
+                    Value::Bool(b) => Ok(b.to_string()),
+                    _ => Err(InstallerError::InvalidFrontmatter {
+                        message: format!("default for variable '{name}' must be a scalar"),
+                    }),
+                })
+                .transpose()?;
+            Ok(SkillVariable {
+                name,
+                description,
+                default,
+            })
         })
-        .filter(|m| !m.is_empty());
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut authors = fields
+        .author
+        .into_iter()
+        .chain(fields.authors.unwrap_or_default())
+        .map(|author| author.trim().to_string())
+        .filter(|author| !author.is_empty())
+        .collect::<Vec<_>>();
+    if authors.is_empty() {
+        authors.extend(metadata.as_ref().and_then(|m| m.get("author")).cloned());
+    }
 
     Ok(ParsedSkill {
         name,
-        description,
+        display_name,
+        description: fields.description,
+        version,
+        license: fields.license.map(|license| license.trim().to_string()),
+        authors,
+        compatibility: fields.compatibility,
         metadata,
-        allowed_tools,
+        allowed_tools: fields.allowed_tools,
+        providers,
+        platforms,
+        variables,
+        hooks,
         body: body.to_string(),
+        warnings,
+        files: Vec::new(),
     })
 }
 
+/// SKILL.md frontmatter as defined by the Agent Skills spec, plus installer `hooks`.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Frontmatter {
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    license: Option<String>,
+    author: Option<String>,
+    authors: Option<Vec<String>>,
+    compatibility: Option<String>,
+    allowed_tools: Option<String>,
+    providers: Option<FrontmatterProviders>,
+    platforms: Option<Vec<String>>,
+    variables: Option<BTreeMap<String, FrontmatterVariable>>,
+    metadata: Option<BTreeMap<String, Value>>,
+    hooks: Option<FrontmatterHooks>,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, Value>,
+}
+
+/// `providers: [a, b]` is shorthand for `providers: { allow: [a, b] }`.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum FrontmatterProviders {
+    Allow(Vec<String>),
+    Rules {
+        allow: Option<Vec<String>>,
+        #[serde(default)]
+        deny: Vec<String>,
+    },
+}
+
+/// `name: value` is shorthand for `name: { default: value }`.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum FrontmatterVariable {
+    Spec(VariableSpec),
+    Default(Value),
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VariableSpec {
+    description: Option<String>,
+    default: Option<Value>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct FrontmatterHooks {
+    pre_install: Option<String>,
+    post_install: Option<String>,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, Value>,
+}
+
+const FRONTMATTER_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "version",
+    "license",
+    "author",
+    "authors",
+    "compatibility",
+    "allowed-tools",
+    "providers",
+    "platforms",
+    "variables",
+    "metadata",
+    "hooks",
+];
+const HOOK_FIELDS: &[&str] = &["pre-install", "post-install"];
+const TEXT_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "license",
+    "author",
+    "compatibility",
+    "allowed-tools",
+];
+
+// Free-text fields take any YAML scalar, so `description: 42` still reads as "42".
+// `version` does not: YAML reads `1.10` as the number 1.1, so it has to be quoted.
+fn stringify_text_fields(yaml: &mut Mapping) -> Result<()> {
+    match yaml.get("version") {
+        None | Some(Value::String(_) | Value::Null) => {}
+        Some(value) => {
+            let shown = serde_yaml::to_string(value).unwrap_or_default();
+            return Err(InstallerError::InvalidFrontmatter {
+                message: format!(
+                    "'version' must be a quoted semver string, e.g. version: \"1.0.0\" (got {})",
+                    shown.trim()
+                ),
+            });
+        }
+    }
+    for field in TEXT_FIELDS {
+        let Some(value) = yaml.get_mut(*field) else {
+            continue;
+        };
+        let text = match value {
+            Value::String(_) | Value::Null => continue,
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            _ => {
+                return Err(InstallerError::InvalidFrontmatter {
+                    message: format!("'{field}' must be a string"),
+                })
+            }
+        };
+        *value = Value::String(text);
+    }
+    Ok(())
+}
+
+// Skills may name agents this installer does not know yet; those entries are skipped.
+fn provider_ids(ids: Vec<String>, warnings: &mut Vec<String>) -> Vec<ProviderId> {
+    ids.into_iter()
+        .filter_map(|id| {
+            let provider = ProviderId::from_str(id.trim());
+            if provider.is_none() {
+                warnings.push(format!("unknown provider '{id}' in frontmatter providers"));
+            }
+            provider
+        })
+        .collect()
+}
+
+// An allowlist left empty once unknown ids are dropped would block every provider.
+fn allowed_provider_ids(ids: Vec<String>, warnings: &mut Vec<String>) -> Result<Vec<ProviderId>> {
+    let listed = ids.join(", ");
+    let allow = provider_ids(ids, warnings);
+    if allow.is_empty() {
+        return Err(InstallerError::InvalidFrontmatter {
+            message: format!("providers allowlist [{listed}] names no known provider"),
+        });
+    }
+    Ok(allow)
+}
+
+fn unknown_field_warnings(
+    prefix: &str,
+    unknown: &BTreeMap<String, Value>,
+    known: &[&str],
+) -> Vec<String> {
+    unknown
+        .keys()
+        .map(|key| {
+            let suggestion = known
+                .iter()
+                .map(|field| (edit_distance(key, field), field))
+                .filter(|(distance, _)| *distance <= 2)
+                .min_by_key(|(distance, _)| *distance);
+            match suggestion {
+                Some((_, field)) => format!(
+                    "unknown frontmatter field '{prefix}{key}' (did you mean '{prefix}{field}'?)"
+                ),
+                None => format!("unknown frontmatter field '{prefix}{key}'"),
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "native")]
 pub(crate) fn resolve_local_skill_root(path: &Path) -> Result<PathBuf> {
     let direct = path.join("SKILL.md");
     if path.ends_with(".skill") && direct.exists() {
@@ -90,28 +438,72 @@ pub(crate) fn resolve_local_skill_root(path: &Path) -> Result<PathBuf> {
     })
 }
 
-fn split_frontmatter(content: &str) -> Result<(&str, &str)> {
-    if !content.starts_with("---\n") {
+/// Splits at the first line that is exactly `---` (ignoring trailing whitespace and `\r`),
+/// so horizontal rules later in the body are left alone.
+pub(crate) fn split_frontmatter(content: &str) -> Result<(&str, &str)> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut lines = content.split_inclusive('\n');
+    let opening = lines.next().unwrap_or_default();
+    if opening.trim_end() != "---" {
         return Err(InstallerError::InvalidFrontmatter {
             message: "missing opening frontmatter delimiter".to_string(),
         });
     }
 
-    let after = &content[4..];
-    let end = after
-        .find("\n---\n")
-        .ok_or_else(|| InstallerError::InvalidFrontmatter {
-            message: "missing closing frontmatter delimiter".to_string(),
-        })?;
+    let start = opening.len();
+    let mut offset = start;
+    for line in lines {
+        if line.trim_end() == "---" {
+            return Ok((&content[start..offset], &content[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
 
-    let frontmatter = &after[..end];
-    let body = &after[(end + 5)..];
-    Ok((frontmatter, body))
+    Err(InstallerError::InvalidFrontmatter {
+        message: "missing closing frontmatter delimiter".to_string(),
+    })
+}
+
+/// Lowercases `name` and joins its alphanumeric runs with `-`: "My Cool Skill!" → `my-cool-skill`.
+pub fn slugify_skill_name(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// The directory (or flat file stem) a skill installs under: its name, with a namespaced
+/// `org/name` encoded as `org--name` so every provider sees a single directory.
+pub fn skill_dir_name(name: &str) -> String {
+    name.replacen('/', "--", 1)
 }
 
-fn validate_skill_name(name: &str) -> Result<()> {
+fn validate_hook_path(stage: &str, path: &str) -> Result<()> {
+    let valid = !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+    if valid {
+        Ok(())
+    } else {
+        Err(InstallerError::InvalidFrontmatter {
+            message: format!("hook '{stage}' must be a relative path inside the skill: {path}"),
+        })
+    }
+}
+
+/// A name is one path segment, optionally prefixed by an `org/` namespace. The namespace
+/// may not contain `--`, so the directory name `skill_dir_name` builds stays unambiguous.
+pub(crate) fn validate_skill_name(name: &str) -> Result<()> {
     let invalid = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
-    if name.chars().any(|c| invalid.contains(&c)) || name == "." || name == ".." {
+    let bad_segment =
+        |s: &str| s.is_empty() || s.chars().any(|c| invalid.contains(&c)) || s == "." || s == "..";
+    let bad = match name.split_once('/') {
+        Some((org, rest)) => bad_segment(org) || org.contains("--") || bad_segment(rest),
+        None => bad_segment(name),
+    };
+    if bad {
         return Err(InstallerError::InvalidName {
             name: name.to_string(),
         });
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::error::Result;
+use crate::observer::InstallObserver;
+use crate::providers::ProviderGroup;
+use crate::types::{DetectedProvider, InstallResult, ParsedSkill, PlannedTarget, ProviderId};
+
+/// Why `provider` was detected, e.g. `found ~/.claude`, shown next to it in the picker.
+pub(crate) fn detection_reason(
+    detected: &[DetectedProvider],
+    provider: ProviderId,
+) -> Option<String> {
+    let reason = &detected.iter().find(|d| d.provider == provider)?.reason;
+    let home = std::env::var("HOME").unwrap_or_default();
+    Some(match reason.split_once(&format!(" {home}/")) {
+        Some((verb, rest)) if !home.is_empty() => format!("{verb} ~/{rest}"),
+        _ => reason.clone(),
+    })
+}
+
+/// Provider picker contents, already filtered, ranked, and defaulted.
+#[derive(Debug, Clone)]
+pub struct ProviderPrompt<'a> {
+    pub message: &'a str,
+    /// `.agents` providers installed through `universal` regardless of the selection.
+    pub locked: &'a [ProviderId],
+    /// Heading for the `locked` section.
+    pub locked_label: &'a str,
+    /// Providers the user can pick from, detected ones first.
+    pub choices: &'a [ProviderId],
+    /// Presets that toggle several choices at once.
+    pub groups: &'a [ProviderGroup],
+    pub defaults: &'a [ProviderId],
+    /// Shown but not selectable, e.g. providers the skill declares unsupported.
+    pub disabled: &'a [ProviderId],
+    /// The skill being installed, previewed next to the choices.
+    pub preview: Option<&'a ParsedSkill>,
+    pub detected: &'a [DetectedProvider],
+    /// The host asked for a full-screen picker rather than an inline one.
+    pub fullscreen: bool,
+}
+
+/// Answers the questions `install_interactive_with` asks; the terminal UI is one implementation.
+///
+/// Return `InstallerError::PromptCancelled` when the user backs out.
+pub trait Prompter {
+    /// Index of the chosen option.
+    fn select(&mut self, message: &str, options: &[&str], default: usize) -> Result<usize>;
+
+    /// Chosen providers out of `prompt.choices`, without `universal`.
+    fn multi_select(&mut self, prompt: &ProviderPrompt<'_>) -> Result<Vec<ProviderId>>;
+
+    fn confirm(&mut self, message: &str, default: bool) -> Result<bool>;
+
+    /// Free-form answer; an empty answer should take `default` when there is one.
+    fn text(&mut self, message: &str, default: Option<&str>) -> Result<String>;
+
+    /// Runs `install` for `targets`, handing it an observer that shows progress.
+    /// The default shows nothing.
+    fn progress(
+        &mut self,
+        targets: &[PlannedTarget],
+        install: &mut dyn FnMut(Option<Arc<dyn InstallObserver>>) -> Result<InstallResult>,
+    ) -> Result<InstallResult> {
+        let _ = targets;
+        install(None)
+    }
+}
@@ -1,8 +1,25 @@
+#[cfg(feature = "native")]
+use std::collections::BTreeMap;
 use std::collections::HashSet;
+#[cfg(feature = "native")]
+use std::io::Read;
 use std::path::{Path, PathBuf};
+#[cfg(feature = "native")]
+use std::process::{Command, Stdio};
+#[cfg(feature = "native")]
+use std::sync::OnceLock;
+use std::sync::RwLock;
+#[cfg(feature = "native")]
+use std::time::{Duration, Instant};
 
 use crate::error::{InstallerError, Result};
-use crate::types::{DetectedProvider, ProviderId, Scope};
+#[cfg(feature = "native")]
+use crate::render::ColorChoice;
+#[cfg(feature = "native")]
+use crate::types::InstallMethod;
+#[cfg(feature = "native")]
+use crate::types::{DetectedProvider, DetectionConfidence, DetectionEvidence};
+use crate::types::{ProviderId, Scope};
 
 #[derive(Debug, Clone)]
 pub struct ProviderInfo {
@@ -10,6 +27,67 @@ pub struct ProviderInfo {
     pub display_name: &'static str,
     pub uses_agents_dir: bool,
     pub project_path: &'static str,
+    /// Home-relative user-scope path for custom providers; defaults to `project_path`.
+    pub user_path: Option<&'static str>,
+    /// Home-relative paths whose presence means a custom provider is installed.
+    pub detect_markers: &'static [&'static str],
+    /// Replacement provider for renamed or merged agents; deprecated providers are
+    /// left out of `'*'` and the picker, and warn when requested explicitly.
+    pub deprecated: Option<&'static str>,
+    pub capabilities: ProviderCapabilities,
+}
+
+/// What a provider can consume; `install()` and the picker adapt to these
+/// instead of failing at filesystem time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// Follows symlinked skill directories; otherwise symlink installs copy instead.
+    pub supports_symlink: bool,
+    pub supports_project_scope: bool,
+    pub supports_user_scope: bool,
+    /// Reads a single instructions file rather than a skills directory.
+    pub flat_layout: bool,
+    pub limits: ProviderLimits,
+}
+
+impl ProviderCapabilities {
+    pub const STANDARD: Self = Self {
+        supports_symlink: true,
+        supports_project_scope: true,
+        supports_user_scope: true,
+        flat_layout: false,
+        limits: ProviderLimits::NONE,
+    };
+
+    pub fn supports_scope(&self, scope: Scope) -> bool {
+        match scope {
+            Scope::Project => self.supports_project_scope,
+            Scope::User => self.supports_user_scope,
+        }
+    }
+}
+
+/// Frontmatter a provider truncates or rejects beyond; `None` means no known limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderLimits {
+    pub max_name_len: Option<usize>,
+    pub max_description_len: Option<usize>,
+    /// The only `metadata` keys the provider reads.
+    pub metadata_keys: Option<&'static [&'static str]>,
+}
+
+impl ProviderLimits {
+    pub const NONE: Self = Self {
+        max_name_len: None,
+        max_description_len: None,
+        metadata_keys: None,
+    };
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self::STANDARD
+    }
 }
 
 const PROVIDERS: &[ProviderInfo] = &[
@@ -18,251 +96,1024 @@ const PROVIDERS: &[ProviderInfo] = &[
         display_name: "Amp",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Antigravity,
         display_name: "Antigravity",
         uses_agents_dir: false,
         project_path: ".agent/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Augment,
         display_name: "Augment",
         uses_agents_dir: false,
         project_path: ".augment/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::ClaudeCode,
         display_name: "Claude Code",
         uses_agents_dir: false,
         project_path: ".claude/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities {
+            limits: ProviderLimits {
+                max_name_len: Some(64),
+                max_description_len: Some(1024),
+                metadata_keys: None,
+            },
+            ..ProviderCapabilities::STANDARD
+        },
     },
     ProviderInfo {
         id: ProviderId::Openclaw,
         display_name: "OpenClaw",
         uses_agents_dir: false,
         project_path: "skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Cline,
         display_name: "Cline",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Codebuddy,
         display_name: "CodeBuddy",
         uses_agents_dir: false,
         project_path: ".codebuddy/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Codex,
         display_name: "Codex",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities {
+            limits: ProviderLimits {
+                max_name_len: Some(100),
+                max_description_len: Some(500),
+                metadata_keys: None,
+            },
+            ..ProviderCapabilities::STANDARD
+        },
     },
     ProviderInfo {
         id: ProviderId::CommandCode,
         display_name: "Command Code",
         uses_agents_dir: false,
         project_path: ".commandcode/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Continue,
         display_name: "Continue",
         uses_agents_dir: false,
         project_path: ".continue/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Cortex,
         display_name: "Cortex Code",
         uses_agents_dir: false,
         project_path: ".cortex/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Crush,
         display_name: "Crush",
         uses_agents_dir: false,
         project_path: ".crush/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Cursor,
         display_name: "Cursor",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Droid,
         display_name: "Droid",
         uses_agents_dir: false,
         project_path: ".factory/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::GeminiCli,
         display_name: "Gemini CLI",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::GithubCopilot,
         display_name: "GitHub Copilot",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Goose,
         display_name: "Goose",
         uses_agents_dir: false,
         project_path: ".goose/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Junie,
         display_name: "Junie",
         uses_agents_dir: false,
         project_path: ".junie/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::IflowCli,
         display_name: "iFlow CLI",
         uses_agents_dir: false,
         project_path: ".iflow/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Kilo,
         display_name: "Kilo Code",
         uses_agents_dir: false,
         project_path: ".kilocode/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::KimiCli,
         display_name: "Kimi Code CLI",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::KiroCli,
         display_name: "Kiro CLI",
         uses_agents_dir: false,
         project_path: ".kiro/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Kode,
         display_name: "Kode",
         uses_agents_dir: false,
         project_path: ".kode/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Mcpjam,
         display_name: "MCPJam",
         uses_agents_dir: false,
         project_path: ".mcpjam/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::MistralVibe,
         display_name: "Mistral Vibe",
         uses_agents_dir: false,
         project_path: ".vibe/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Mux,
         display_name: "Mux",
         uses_agents_dir: false,
         project_path: ".mux/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Opencode,
         display_name: "OpenCode",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Openhands,
         display_name: "OpenHands",
         uses_agents_dir: false,
         project_path: ".openhands/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Pi,
         display_name: "Pi",
         uses_agents_dir: false,
         project_path: ".pi/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Qoder,
         display_name: "Qoder",
         uses_agents_dir: false,
         project_path: ".qoder/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::QwenCode,
         display_name: "Qwen Code",
         uses_agents_dir: false,
         project_path: ".qwen/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Replit,
         display_name: "Replit",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        // Replit agents only see the workspace; there is no home directory to install into.
+        capabilities: ProviderCapabilities {
+            supports_user_scope: false,
+            ..ProviderCapabilities::STANDARD
+        },
     },
     ProviderInfo {
         id: ProviderId::Roo,
         display_name: "Roo Code",
         uses_agents_dir: false,
         project_path: ".roo/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Trae,
         display_name: "Trae",
         uses_agents_dir: false,
         project_path: ".trae/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::TraeCn,
         display_name: "Trae CN",
         uses_agents_dir: false,
         project_path: ".trae/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: Some("trae"),
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Windsurf,
         display_name: "Windsurf",
         uses_agents_dir: false,
         project_path: ".windsurf/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Zencoder,
         display_name: "Zencoder",
         uses_agents_dir: false,
         project_path: ".zencoder/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Neovate,
         display_name: "Neovate",
         uses_agents_dir: false,
         project_path: ".neovate/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Pochi,
         display_name: "Pochi",
         uses_agents_dir: false,
         project_path: ".pochi/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Adal,
         display_name: "AdaL",
         uses_agents_dir: false,
         project_path: ".adal/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
+    },
+    ProviderInfo {
+        id: ProviderId::Zed,
+        display_name: "Zed",
+        uses_agents_dir: false,
+        project_path: ".zed/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
+    },
+    ProviderInfo {
+        id: ProviderId::Aider,
+        display_name: "Aider",
+        uses_agents_dir: false,
+        project_path: ".aider/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
+    },
+    ProviderInfo {
+        id: ProviderId::Vscode,
+        display_name: "VS Code",
+        uses_agents_dir: false,
+        project_path: ".vscode/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
     ProviderInfo {
         id: ProviderId::Universal,
         display_name: "Universal",
         uses_agents_dir: true,
         project_path: ".agents/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
     },
 ];
 
-pub fn supported_providers() -> &'static [ProviderInfo] {
-    PROVIDERS
+static REGISTERED: RwLock<Vec<ProviderInfo>> = RwLock::new(Vec::new());
+static GROUPS: RwLock<Vec<ProviderGroup>> = RwLock::new(Vec::new());
+static PROJECT_PATHS: RwLock<Vec<(ProviderId, String)>> = RwLock::new(Vec::new());
+static INTERNED_PATHS: RwLock<Vec<&'static str>> = RwLock::new(Vec::new());
+static FAVORITES: RwLock<Vec<ProviderId>> = RwLock::new(Vec::new());
+#[cfg(feature = "native")]
+static USER_PROVIDERS_LOADED: OnceLock<UserProvidersLoad> = OnceLock::new();
+#[cfg(feature = "native")]
+static DEFAULTS: RwLock<InstallDefaults> = RwLock::new(InstallDefaults {
+    providers: None,
+    scope: None,
+    method: None,
+    color: None,
+    backups: None,
+});
+
+/// A named preset of providers, referenced as `@name` in provider lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderGroup {
+    pub name: String,
+    pub providers: Vec<ProviderId>,
+}
+
+#[cfg(feature = "native")]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ProvidersFile {
+    #[serde(default)]
+    provider: Vec<ProviderEntry>,
+    #[serde(default)]
+    groups: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    project_paths: BTreeMap<String, String>,
+    #[serde(default)]
+    favorites: Vec<String>,
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+    #[serde(default)]
+    defaults: InstallDefaults,
+    /// Index URL for `RegistryClient::configured`.
+    registry: Option<String>,
+    /// Upload endpoint for `install-skill publish`.
+    publish_url: Option<String>,
+}
+
+/// `[defaults]` from the config files: filled in when the interactive installer cannot
+/// prompt, and preselected when it can.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[cfg_attr(not(feature = "cli"), allow(dead_code))]
+pub(crate) struct InstallDefaults {
+    /// Comma-separated, as for `--providers`.
+    #[serde(default, deserialize_with = "provider_list")]
+    pub providers: Option<String>,
+    pub scope: Option<Scope>,
+    pub method: Option<InstallMethod>,
+    /// Used while `--color` is `auto` and neither `NO_COLOR` nor `CLICOLOR_FORCE` is set.
+    pub color: Option<ColorChoice>,
+    /// Back up existing installs to `<path>.bak` instead of overwriting them in place.
+    pub backups: Option<bool>,
+}
+
+#[cfg(feature = "native")]
+impl InstallDefaults {
+    /// Takes every value `other` sets, keeping the rest.
+    fn merge(&mut self, other: InstallDefaults) {
+        self.providers = other.providers.or(self.providers.take());
+        self.scope = other.scope.or(self.scope);
+        self.method = other.method.or(self.method);
+        self.color = other.color.or(self.color);
+        self.backups = other.backups.or(self.backups);
+    }
+}
+
+#[cfg(feature = "native")]
+fn provider_list<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<String>, D::Error> {
+    let providers = <Vec<String> as serde::Deserialize>::deserialize(deserializer)?;
+    Ok(Some(providers.join(",")))
+}
+
+#[cfg(feature = "native")]
+static REGISTRY: RwLock<Option<String>> = RwLock::new(None);
+#[cfg(feature = "native")]
+static PUBLISH_URL: RwLock<Option<String>> = RwLock::new(None);
+
+#[cfg(feature = "registry")]
+pub(crate) fn configured_registry() -> Option<String> {
+    load_user_providers_once();
+    REGISTRY.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// `publish-url` from the config files, where `install-skill publish` uploads to.
+#[cfg(feature = "registry")]
+pub fn configured_publish_url() -> Option<String> {
+    load_user_providers_once();
+    PUBLISH_URL
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+#[cfg(feature = "cli")]
+pub(crate) fn configured_defaults() -> InstallDefaults {
+    load_user_providers_once();
+    DEFAULTS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(feature = "native")]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ProviderEntry {
+    id: String,
+    display_name: Option<String>,
+    project_path: String,
+    user_path: Option<String>,
+    #[serde(default)]
+    uses_agents_dir: bool,
+    #[serde(default)]
+    detect_markers: Vec<String>,
+    deprecated: Option<String>,
+    #[serde(default = "yes")]
+    supports_symlink: bool,
+    #[serde(default = "yes")]
+    supports_project_scope: bool,
+    #[serde(default = "yes")]
+    supports_user_scope: bool,
+    #[serde(default)]
+    flat_layout: bool,
+    max_name_length: Option<usize>,
+    max_description_length: Option<usize>,
+    metadata_keys: Option<Vec<String>>,
+}
+
+#[cfg(feature = "native")]
+fn yes() -> bool {
+    true
+}
+
+pub fn supported_providers() -> Vec<ProviderInfo> {
+    let mut providers = PROVIDERS.to_vec();
+    providers.extend(registered_providers());
+
+    let overrides = PROJECT_PATHS.read().unwrap_or_else(|e| e.into_inner());
+    for provider in &mut providers {
+        if let Some((_, path)) = overrides.iter().find(|(id, _)| *id == provider.id) {
+            provider.project_path = intern(path);
+        }
+    }
+    providers
+}
+
+// `ProviderInfo` borrows its paths for the whole process, so each distinct override is
+// leaked once and reused, however often it is set or read.
+fn intern(path: &str) -> &'static str {
+    if let Some(&known) = INTERNED_PATHS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|known| **known == path)
+    {
+        return known;
+    }
+    let mut interned = INTERNED_PATHS.write().unwrap_or_else(|e| e.into_inner());
+    match interned.iter().find(|known| **known == path) {
+        Some(&known) => known,
+        None => {
+            let leaked = leak(path.to_string());
+            interned.push(leaked);
+            leaked
+        }
+    }
+}
+
+// Project paths are joined onto the project root, so they must stay inside it: no root,
+// prefix, `.`, or `..` components.
+fn is_nested_relative(path: &str) -> bool {
+    let mut components = Path::new(path).components().peekable();
+    components.peek().is_some()
+        && components.all(|component| matches!(component, std::path::Component::Normal(_)))
+}
+
+/// Overrides a provider's project-relative skills path for the rest of the process,
+/// e.g. `.claude/agents/skills` for teams that nest skills differently.
+pub fn set_project_path(provider: ProviderId, path: &str) -> Result<()> {
+    if provider == ProviderId::TargetDir || !is_nested_relative(path) {
+        return Err(InstallerError::InvalidProvider {
+            provider: provider.as_str().to_string(),
+            message: format!(
+                "project path '{path}' must be a relative path inside the project, without '.' or '..'"
+            ),
+        });
+    }
+
+    let mut overrides = PROJECT_PATHS.write().unwrap_or_else(|e| e.into_inner());
+    overrides.retain(|(id, _)| *id != provider);
+    overrides.push((provider, path.to_string()));
+    Ok(())
+}
+
+/// Parses a `provider=path` pair as accepted by `--provider-path`.
+pub fn parse_provider_path(raw: &str) -> Result<(ProviderId, String)> {
+    let (id, path) = raw
+        .split_once('=')
+        .ok_or_else(|| InstallerError::InvalidProvider {
+            provider: raw.to_string(),
+            message: "expected <provider>=<path>".to_string(),
+        })?;
+    let provider =
+        ProviderId::from_str(id.trim()).ok_or_else(|| InstallerError::UnsupportedProvider {
+            provider: id.trim().to_string(),
+            suggestion: suggest_provider(id.trim()),
+        })?;
+    Ok((provider, path.trim().to_string()))
+}
+
+/// Adds a provider at runtime; registering the same custom id again replaces it.
+pub fn register_provider(info: ProviderInfo) -> Result<()> {
+    let ProviderId::Custom(id) = info.id else {
+        return Err(InstallerError::InvalidProvider {
+            provider: info.id.as_str().to_string(),
+            message: "built-in providers cannot be re-registered; use ProviderId::Custom"
+                .to_string(),
+        });
+    };
+    let valid_id = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_id || id == ProviderId::TargetDir.as_str() {
+        return Err(InstallerError::InvalidProvider {
+            provider: id.to_string(),
+            message: "id must be a non-empty lowercase slug".to_string(),
+        });
+    }
+    if ProviderId::builtin(id).is_some() {
+        return Err(InstallerError::InvalidProvider {
+            provider: id.to_string(),
+            message: "id is already used by a built-in provider or alias".to_string(),
+        });
+    }
+    if !is_nested_relative(info.project_path) {
+        return Err(InstallerError::InvalidProvider {
+            provider: id.to_string(),
+            message: "project_path must be a relative path without '.' or '..'".to_string(),
+        });
+    }
+
+    let mut registered = REGISTERED.write().unwrap_or_else(|e| e.into_inner());
+    registered.retain(|p| p.id != info.id);
+    registered.push(info);
+    Ok(())
+}
+
+/// Defines (or replaces) a provider group usable as `@name`.
+pub fn register_provider_group(name: &str, providers: Vec<ProviderId>) -> Result<()> {
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_name || providers.is_empty() {
+        return Err(InstallerError::InvalidProvider {
+            provider: format!("@{name}"),
+            message: "groups need a slug name and at least one provider".to_string(),
+        });
+    }
+
+    let mut groups = GROUPS.write().unwrap_or_else(|e| e.into_inner());
+    groups.retain(|g| g.name != name);
+    groups.push(ProviderGroup {
+        name: name.to_string(),
+        providers,
+    });
+    Ok(())
+}
+
+pub fn provider_groups() -> Vec<ProviderGroup> {
+    load_user_providers_once();
+    GROUPS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Providers pinned to the top of `'*'` expansion and the picker, in this order.
+pub fn set_favorite_providers(favorites: Vec<ProviderId>) {
+    *FAVORITES.write().unwrap_or_else(|e| e.into_inner()) = favorites;
+}
+
+pub fn favorite_providers() -> Vec<ProviderId> {
+    load_user_providers_once();
+    FAVORITES.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Orders providers as favorites first, then detected ones, then alphabetically by id.
+pub fn rank_providers(providers: &mut [ProviderId], detected: &[ProviderId]) {
+    let favorites = favorite_providers();
+    providers.sort_by_key(|p| {
+        (
+            favorites.iter().position(|f| f == p).unwrap_or(usize::MAX),
+            !detected.contains(p),
+            p.as_str(),
+        )
+    });
+}
+
+// Backs `ProviderId::from_str`, so it must not trigger the config loader.
+pub(crate) fn registered_provider(id: &str) -> Option<ProviderId> {
+    REGISTERED
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|p| p.id.as_str() == id)
+        .map(|p| p.id)
+}
+
+/// `$XDG_CONFIG_HOME/skill-installer/providers.toml`, falling back to `~/.config`.
+pub fn user_providers_path() -> Option<PathBuf> {
+    Some(user_config_dir()?.join("providers.toml"))
+}
+
+/// `$XDG_CONFIG_HOME/skill-installer/config.toml`, falling back to `~/.config`.
+pub fn user_config_path() -> Option<PathBuf> {
+    Some(user_config_dir()?.join("config.toml"))
+}
+
+/// File name of the per-project config read by [`load_project_config`].
+pub const PROJECT_CONFIG_FILE: &str = ".skill-installer.toml";
+
+fn user_config_dir() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("skill-installer"))
+}
+
+/// Loads the user's `providers.toml` and `config.toml`, skipping any that are missing.
+/// `config.toml` wins for `[defaults]`, favorites, and variables.
+///
+/// This also happens implicitly the first time providers are listed; call it
+/// directly to surface configuration errors. The files are read once per process, and
+/// later calls return the first outcome again.
+#[cfg(feature = "native")]
+pub fn load_user_providers() -> Result<Vec<ProviderId>> {
+    USER_PROVIDERS_LOADED
+        .get_or_init(load_user_providers_uncached)
+        .clone()
+        .map_err(|(path, message)| InstallerError::ConfigError { path, message })
+}
+
+// The ids a load registered, or the config error it stopped at, kept to replay.
+#[cfg(feature = "native")]
+type UserProvidersLoad = std::result::Result<Vec<ProviderId>, (PathBuf, String)>;
+
+#[cfg(feature = "native")]
+fn load_user_providers_uncached() -> UserProvidersLoad {
+    read_user_providers().map_err(|err| match err {
+        InstallerError::ConfigError { path, message } => (path, message),
+        other => (PathBuf::new(), other.to_string()),
+    })
+}
+
+#[cfg(feature = "native")]
+fn read_user_providers() -> Result<Vec<ProviderId>> {
+    let mut ids = Vec::new();
+    for path in [user_providers_path(), user_config_path()]
+        .into_iter()
+        .flatten()
+    {
+        if path.exists() {
+            ids.extend(load_providers_file(&path)?);
+        }
+    }
+    Ok(ids)
+}
+
+/// The parts of `.skill-installer.toml` a project may set. It arrives with cloned
+/// repositories, so it cannot register providers or move install paths.
+#[cfg(feature = "native")]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ProjectConfigFile {
+    #[serde(default)]
+    defaults: InstallDefaults,
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+}
+
+/// Applies `[defaults]` and `[variables]` from `<dir>/.skill-installer.toml`, if it exists,
+/// over the user config. The library never reads it on its own; the CLI asks for the
+/// current directory's.
+#[cfg(feature = "native")]
+pub fn load_project_config(dir: &Path) -> Result<()> {
+    let path = dir.join(PROJECT_CONFIG_FILE);
+    if !path.exists() {
+        return Ok(());
+    }
+    let config_error = |message: String| InstallerError::ConfigError {
+        path: path.clone(),
+        message,
+    };
+    let raw = std::fs::read_to_string(&path).map_err(|err| config_error(err.to_string()))?;
+    let file: ProjectConfigFile =
+        toml::from_str(&raw).map_err(|err| config_error(err.to_string()))?;
+
+    // User files first, so the project settings merged below win over them.
+    load_user_providers_once();
+    if let Some(providers) = &file.defaults.providers {
+        check_provider_list(providers)
+            .map_err(|err| config_error(format!("defaults.providers: {err}")))?;
+    }
+    DEFAULTS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .merge(file.defaults);
+    crate::variables::set_configured_variables(file.variables);
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+pub fn load_providers_file(path: &Path) -> Result<Vec<ProviderId>> {
+    let config_error = |message: String| InstallerError::ConfigError {
+        path: path.to_path_buf(),
+        message,
+    };
+    let raw = std::fs::read_to_string(path).map_err(|err| config_error(err.to_string()))?;
+    let file: ProvidersFile = toml::from_str(&raw).map_err(|err| config_error(err.to_string()))?;
+
+    let mut ids = Vec::new();
+    for entry in file.provider {
+        let id = ProviderId::Custom(leak(entry.id));
+        let display_name = entry.display_name.map(leak).unwrap_or(id.as_str());
+        let markers = entry
+            .detect_markers
+            .into_iter()
+            .map(leak)
+            .collect::<Vec<_>>();
+        let metadata_keys = entry.metadata_keys.map(|keys| {
+            let keys = keys.into_iter().map(leak).collect::<Vec<_>>();
+            &*Box::leak(keys.into_boxed_slice())
+        });
+        register_provider(ProviderInfo {
+            id,
+            display_name,
+            uses_agents_dir: entry.uses_agents_dir,
+            project_path: leak(entry.project_path),
+            user_path: entry.user_path.map(leak),
+            detect_markers: Box::leak(markers.into_boxed_slice()),
+            deprecated: entry.deprecated.map(leak),
+            capabilities: ProviderCapabilities {
+                supports_symlink: entry.supports_symlink,
+                supports_project_scope: entry.supports_project_scope,
+                supports_user_scope: entry.supports_user_scope,
+                flat_layout: entry.flat_layout,
+                limits: ProviderLimits {
+                    max_name_len: entry.max_name_length,
+                    max_description_len: entry.max_description_length,
+                    metadata_keys,
+                },
+            },
+        })
+        .map_err(|err| config_error(err.to_string()))?;
+        ids.push(id);
+    }
+
+    for (name, members) in file.groups {
+        let providers = members
+            .iter()
+            .map(|member| {
+                ProviderId::from_str(member).ok_or_else(|| {
+                    config_error(format!("group '{name}' has unknown provider '{member}'"))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        register_provider_group(&name, providers).map_err(|err| config_error(err.to_string()))?;
+    }
+
+    for (id, path) in file.project_paths {
+        let provider = ProviderId::from_str(&id)
+            .ok_or_else(|| config_error(format!("unknown provider '{id}' in project-paths")))?;
+        set_project_path(provider, &path).map_err(|err| config_error(err.to_string()))?;
+    }
+
+    if !file.favorites.is_empty() {
+        let favorites = file
+            .favorites
+            .iter()
+            .map(|id| {
+                ProviderId::from_str(id)
+                    .ok_or_else(|| config_error(format!("unknown provider '{id}' in favorites")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        set_favorite_providers(favorites);
+    }
+    if let Some(providers) = &file.defaults.providers {
+        check_provider_list(providers)
+            .map_err(|err| config_error(format!("defaults.providers: {err}")))?;
+    }
+    DEFAULTS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .merge(file.defaults);
+    crate::variables::set_configured_variables(file.variables);
+    if let Some(registry) = file.registry {
+        *REGISTRY.write().unwrap_or_else(|e| e.into_inner()) = Some(registry);
+    }
+    if let Some(url) = file.publish_url {
+        *PUBLISH_URL.write().unwrap_or_else(|e| e.into_inner()) = Some(url);
+    }
+    Ok(ids)
+}
+
+// Registered providers live for the rest of the process, like the built-in table.
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+// Like `parse_providers_csv`, but only against what is registered so far, since it runs
+// while the config files are loading.
+#[cfg(feature = "native")]
+fn check_provider_list(raw: &str) -> Result<()> {
+    let groups = GROUPS.read().unwrap_or_else(|e| e.into_inner());
+    for token in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let known = match token.strip_prefix('@') {
+            Some(name) => groups.iter().any(|g| g.name == name),
+            None => token == "*" || ProviderId::from_str(token).is_some(),
+        };
+        if !known {
+            return Err(InstallerError::UnsupportedProvider {
+                provider: token.to_string(),
+                suggestion: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Everything the loader calls reads the statics directly, so this never re-enters itself;
+// other threads block here until the files are fully applied.
+pub(crate) fn load_user_providers_once() {
+    #[cfg(feature = "native")]
+    USER_PROVIDERS_LOADED.get_or_init(load_user_providers_uncached);
+}
+
+fn registered_providers() -> Vec<ProviderInfo> {
+    load_user_providers_once();
+    REGISTERED.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(feature = "native")]
+pub(crate) fn deprecation_warnings(providers: &[ProviderId]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    providers
+        .iter()
+        .filter(|p| seen.insert(**p))
+        .filter_map(|p| {
+            let replacement = provider_info(*p)?.deprecated?;
+            Some(format!(
+                "provider '{}' is deprecated; use '{}' instead",
+                p.as_str(),
+                replacement
+            ))
+        })
+        .collect()
 }
 
 pub fn is_agents_provider(provider: ProviderId) -> bool {
@@ -297,14 +1148,36 @@ pub fn normalize_providers(
 
 pub fn parse_providers_csv(raw: &str) -> Result<Vec<ProviderId>> {
     if raw.trim() == "*" {
-        return Ok(supported_providers().iter().map(|p| p.id).collect());
+        let mut all = supported_providers()
+            .iter()
+            .filter(|p| p.deprecated.is_none())
+            .map(|p| p.id)
+            .collect::<Vec<_>>();
+        #[cfg(feature = "native")]
+        let detected = detected_provider_ids(None);
+        #[cfg(not(feature = "native"))]
+        let detected = Vec::new();
+        rank_providers(&mut all, &detected);
+        return Ok(all);
     }
 
     let mut out = Vec::new();
     for token in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if let Some(name) = token.strip_prefix('@') {
+            let group = provider_groups()
+                .into_iter()
+                .find(|g| g.name == name)
+                .ok_or_else(|| InstallerError::UnsupportedProvider {
+                    provider: token.to_string(),
+                    suggestion: None,
+                })?;
+            out.extend(group.providers);
+            continue;
+        }
         let provider =
             ProviderId::from_str(token).ok_or_else(|| InstallerError::UnsupportedProvider {
                 provider: token.to_string(),
+                suggestion: suggest_provider(token),
             })?;
         out.push(provider);
     }
@@ -312,114 +1185,309 @@ pub fn parse_providers_csv(raw: &str) -> Result<Vec<ProviderId>> {
     if out.is_empty() {
         return Err(InstallerError::UnsupportedProvider {
             provider: "(empty)".to_string(),
+            suggestion: None,
         });
     }
 
     Ok(out)
 }
 
+/// Closest known provider id to a mistyped one, if any is within two edits.
+pub(crate) fn suggest_provider(input: &str) -> Option<String> {
+    let input = input.to_ascii_lowercase();
+    supported_providers()
+        .iter()
+        .map(|p| (edit_distance(&input, p.id.as_str()), p.id.as_str()))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, id)| id.to_string())
+}
+
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Providers with a detection marker on disk. Only the filesystem is checked; nothing is run.
+#[cfg(feature = "native")]
 pub fn detect_providers(project_root: Option<&Path>) -> Vec<DetectedProvider> {
-    let home = std::env::var("HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| PathBuf::from("~"));
-    let config_home = std::env::var("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| home.join(".config"));
+    detect(project_root, false)
+}
+
+/// Like [`detect_providers`], but also runs each detected provider's `<cli> --version` from
+/// `PATH`, which fills in `version` and raises the confidence of providers that answer.
+///
+/// This executes whatever binary of that name comes first on `PATH`, so only call it where
+/// `PATH` is trusted.
+#[cfg(feature = "native")]
+pub fn detect_providers_with_versions(project_root: Option<&Path>) -> Vec<DetectedProvider> {
+    detect(project_root, true)
+}
 
+#[cfg(feature = "native")]
+fn detect(project_root: Option<&Path>, probe_versions: bool) -> Vec<DetectedProvider> {
+    trace_span!(DEBUG, "detect_providers");
+    let (home, config_home) = detection_homes();
     let mut detected = Vec::new();
     for provider in supported_providers() {
         if provider.id == ProviderId::Universal {
             continue;
         }
 
-        if let Some(reason) = detect_provider(provider.id, &home, &config_home, project_root) {
+        let evidence = detection_evidence(provider.id, &home, &config_home, project_root);
+        if let Some(first) = evidence.first() {
             detected.push(DetectedProvider {
                 provider: provider.id,
-                reason,
+                reason: format!("found {}", first.path.display()),
+                version: None,
+                confidence: detection_confidence(&evidence, None),
+                evidence,
             });
         }
     }
 
+    if probe_versions {
+        // Version probes spawn agent CLIs, so run them side by side.
+        std::thread::scope(|scope| {
+            for entry in &mut detected {
+                scope.spawn(|| {
+                    entry.version = detect_provider_version(entry.provider);
+                    entry.confidence =
+                        detection_confidence(&entry.evidence, entry.version.as_deref());
+                });
+            }
+        });
+    }
+    #[cfg(feature = "tracing")]
+    for entry in &detected {
+        trace_event!(
+            debug,
+            provider = entry.provider.as_str(),
+            confidence = entry.confidence.as_str(),
+            version = ?entry.version,
+            "detected provider"
+        );
+    }
+
     detected
 }
 
-fn detect_provider(
+/// Providers with any detection marker, skipping the slower CLI version probes.
+#[cfg(feature = "native")]
+fn detected_provider_ids(project_root: Option<&Path>) -> Vec<ProviderId> {
+    let (home, config_home) = detection_homes();
+    supported_providers()
+        .into_iter()
+        .map(|p| p.id)
+        .filter(|id| !detection_evidence(*id, &home, &config_home, project_root).is_empty())
+        .collect()
+}
+
+#[cfg(feature = "native")]
+fn detection_homes() -> (PathBuf, PathBuf) {
+    let home = std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("~"));
+    let config_home = std::env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| home.join(".config"));
+    (home, config_home)
+}
+
+#[cfg(feature = "native")]
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Runs the provider's CLI from `PATH` with `--version` and extracts the first version-like
+/// token; the probe is killed after two seconds.
+#[cfg(feature = "native")]
+pub fn detect_provider_version(provider: ProviderId) -> Option<String> {
+    let program = find_on_path(version_command(provider)?)?;
+    let mut child = Command::new(program)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let deadline = Instant::now() + VERSION_PROBE_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(20)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    parse_version(&output)
+}
+
+#[cfg(feature = "native")]
+fn version_command(provider: ProviderId) -> Option<&'static str> {
+    Some(match provider {
+        ProviderId::Amp => "amp",
+        ProviderId::ClaudeCode => "claude",
+        ProviderId::Codex => "codex",
+        ProviderId::Crush => "crush",
+        ProviderId::Cursor => "cursor-agent",
+        ProviderId::GeminiCli => "gemini",
+        ProviderId::GithubCopilot => "copilot",
+        ProviderId::Goose => "goose",
+        ProviderId::KimiCli => "kimi",
+        ProviderId::KiroCli => "kiro-cli",
+        ProviderId::MistralVibe => "vibe",
+        ProviderId::Opencode => "opencode",
+        ProviderId::QwenCode => "qwen",
+        ProviderId::Zed => "zed",
+        ProviderId::Aider => "aider",
+        ProviderId::Vscode => "code",
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "native")]
+fn find_on_path(program: &str) -> Option<PathBuf> {
+    let file_name = if cfg!(windows) {
+        format!("{program}.exe")
+    } else {
+        program.to_string()
+    };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(feature = "native")]
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .map(|token| token.trim_start_matches('v'))
+        .find(|token| {
+            token.split('.').count() >= 2
+                && token.split('.').next().is_some_and(|major| {
+                    !major.is_empty() && major.chars().all(|c| c.is_ascii_digit())
+                })
+        })
+        .map(str::to_string)
+}
+
+#[cfg(feature = "native")]
+fn detection_evidence(
     provider: ProviderId,
     home: &Path,
     config_home: &Path,
     project_root: Option<&Path>,
-) -> Option<String> {
-    let marker = match provider {
-        ProviderId::Openclaw => {
-            let candidates = [
-                home.join(".openclaw"),
-                home.join(".clawdbot"),
-                home.join(".moltbot"),
-            ];
-            return candidates
-                .into_iter()
-                .find(|p| p.exists())
-                .map(|p| format!("found {}", p.display()));
-        }
-        ProviderId::Codex => {
-            let codex_home = std::env::var("CODEX_HOME")
+) -> Vec<DetectionEvidence> {
+    let global = match provider {
+        ProviderId::Openclaw => vec![
+            home.join(".openclaw"),
+            home.join(".clawdbot"),
+            home.join(".moltbot"),
+        ],
+        ProviderId::Codex => vec![
+            std::env::var("CODEX_HOME")
                 .map(PathBuf::from)
-                .unwrap_or_else(|_| home.join(".codex"));
-            if codex_home.exists() {
-                return Some(format!("found {}", codex_home.display()));
-            }
-            if Path::new("/etc/codex").exists() {
-                return Some("found /etc/codex".to_string());
-            }
-            codex_home
-        }
-        ProviderId::ClaudeCode => std::env::var("CLAUDE_CONFIG_DIR")
+                .unwrap_or_else(|_| home.join(".codex")),
+            PathBuf::from("/etc/codex"),
+        ],
+        ProviderId::ClaudeCode => vec![std::env::var("CLAUDE_CONFIG_DIR")
             .map(PathBuf::from)
-            .unwrap_or_else(|_| home.join(".claude")),
-        ProviderId::Amp => config_home.join("amp"),
-        ProviderId::Goose => config_home.join("goose"),
-        ProviderId::Opencode => config_home.join("opencode"),
-        ProviderId::KimiCli => home.join(".kimi"),
-        ProviderId::Replit => {
-            if let Some(root) = project_root {
-                let p = root.join(".replit");
-                if p.exists() {
-                    return Some(format!("found {}", p.display()));
-                }
-            }
-            return None;
-        }
-        ProviderId::Pi => home.join(".pi/agent"),
-        ProviderId::Cortex => home.join(".snowflake/cortex"),
-        ProviderId::Windsurf => home.join(".codeium/windsurf"),
+            .unwrap_or_else(|_| home.join(".claude"))],
+        ProviderId::Amp => vec![config_home.join("amp")],
+        ProviderId::Goose => vec![config_home.join("goose")],
+        ProviderId::Opencode => vec![config_home.join("opencode")],
+        ProviderId::KimiCli => vec![home.join(".kimi")],
+        ProviderId::Replit => Vec::new(),
+        ProviderId::Pi => vec![home.join(".pi/agent")],
+        ProviderId::Cortex => vec![home.join(".snowflake/cortex")],
+        ProviderId::Windsurf => vec![home.join(".codeium/windsurf")],
+        ProviderId::Zed => vec![config_home.join("zed")],
+        // `~/.vscode` exists for anyone who ever opened the editor, so only a user skills
+        // folder, which a skills-aware extension reads, counts.
+        ProviderId::Vscode => vec![home.join(".vscode/skills")],
+        ProviderId::Custom(_) if !markers_for(provider).is_empty() => markers_for(provider)
+            .iter()
+            .map(|marker| home.join(marker))
+            .collect(),
         _ => {
             let base = project_path_for(provider)
                 .trim_start_matches('.')
                 .trim_start_matches('/');
             let first = base.split('/').next().unwrap_or(base);
-            home.join(format!(".{}", first))
+            vec![home.join(format!(".{}", first))]
         }
     };
+    let project = project_root.map_or_else(Vec::new, |root| match provider {
+        // Replit shares `.agents/skills`, so only its own project file counts.
+        ProviderId::Replit => vec![root.join(".replit")],
+        // Aider's own project directory appears before any skill is installed.
+        ProviderId::Aider => vec![root.join(".aider")],
+        // Extensions following the Copilot convention read `.github/instructions` instead.
+        ProviderId::Vscode => vec![
+            root.join(project_path_for(provider)),
+            root.join(".github/instructions"),
+        ],
+        _ => vec![root.join(project_path_for(provider))],
+    });
 
-    if marker.exists() {
-        return Some(format!("found {}", marker.display()));
-    }
+    let global = global.into_iter().map(|path| (Scope::User, path));
+    let project = project.into_iter().map(|path| (Scope::Project, path));
+    global
+        .chain(project)
+        .filter(|(_, path)| path.exists())
+        .map(|(scope, path)| DetectionEvidence { scope, path })
+        .collect()
+}
 
-    if let Some(root) = project_root {
-        let p = root.join(project_path_for(provider));
-        if p.exists() {
-            return Some(format!("found {}", p.display()));
-        }
+#[cfg(feature = "native")]
+fn detection_confidence(
+    evidence: &[DetectionEvidence],
+    version: Option<&str>,
+) -> DetectionConfidence {
+    let global = evidence.iter().any(|e| e.scope == Scope::User);
+    let project = evidence.iter().any(|e| e.scope == Scope::Project);
+    match (global, project, version.is_some()) {
+        (true, true, _) | (true, _, true) => DetectionConfidence::High,
+        (true, _, _) | (_, _, true) => DetectionConfidence::Medium,
+        _ => DetectionConfidence::Low,
     }
+}
 
-    None
+#[cfg(feature = "native")]
+fn markers_for(provider: ProviderId) -> &'static [&'static str] {
+    provider_info(provider)
+        .map(|p| p.detect_markers)
+        .unwrap_or(&[])
 }
 
+#[cfg(feature = "native")]
 pub fn resolve_provider_dir(
     provider: ProviderId,
     scope: Scope,
     project_root: Option<&Path>,
 ) -> Result<PathBuf> {
+    if provider == ProviderId::TargetDir {
+        return Err(InstallerError::UnsupportedProvider {
+            provider: provider.as_str().to_string(),
+            suggestion: None,
+        });
+    }
+
     let home = std::env::var("HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| PathBuf::from("~"));
@@ -432,22 +1500,59 @@ pub fn resolve_provider_dir(
             let root = project_root.ok_or(InstallerError::ProjectRootRequired)?;
             Ok(root.join(project_path_for(provider)))
         }
-        Scope::User => Ok(user_path_for(provider, &home, &config_home)),
+        Scope::User => Ok(user_dir_override(provider)
+            .unwrap_or_else(|| user_path_for(provider, &home, &config_home))),
     }
 }
 
+/// `SKILL_INSTALLER_<PROVIDER>_DIR`, e.g. `SKILL_INSTALLER_CLAUDE_CODE_DIR`.
+pub fn user_dir_env_var(provider: ProviderId) -> String {
+    format!(
+        "SKILL_INSTALLER_{}_DIR",
+        provider.as_str().to_ascii_uppercase().replace('-', "_")
+    )
+}
+
+#[cfg(feature = "native")]
+fn user_dir_override(provider: ProviderId) -> Option<PathBuf> {
+    std::env::var_os(user_dir_env_var(provider))
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+#[cfg(feature = "native")]
 pub fn project_path_for(provider: ProviderId) -> &'static str {
     provider_info(provider)
         .map(|p| p.project_path)
         .unwrap_or(".agents/skills")
 }
 
-fn provider_info(provider: ProviderId) -> Option<&'static ProviderInfo> {
-    supported_providers().iter().find(|p| p.id == provider)
+pub fn provider_supports_scope(provider: ProviderId, scope: Scope) -> bool {
+    provider_capabilities(provider).supports_scope(scope)
+}
+
+pub(crate) fn provider_capabilities(provider: ProviderId) -> ProviderCapabilities {
+    provider_info(provider)
+        .map(|p| p.capabilities)
+        .unwrap_or_default()
+}
+
+pub(crate) fn provider_display_name(provider: ProviderId) -> &'static str {
+    provider_info(provider)
+        .map(|p| p.display_name)
+        .unwrap_or(provider.as_str())
 }
 
+fn provider_info(provider: ProviderId) -> Option<ProviderInfo> {
+    supported_providers().into_iter().find(|p| p.id == provider)
+}
+
+#[cfg(feature = "native")]
 fn user_path_for(provider: ProviderId, home: &Path, config_home: &Path) -> PathBuf {
     match provider {
+        ProviderId::TargetDir => {
+            unreachable!("resolve_provider_dir rejects target-dir before asking for a user path")
+        }
         ProviderId::Universal | ProviderId::Amp | ProviderId::KimiCli | ProviderId::Replit => {
             config_home.join("agents/skills")
         }
@@ -508,5 +1613,13 @@ fn user_path_for(provider: ProviderId, home: &Path, config_home: &Path) -> PathB
         ProviderId::Neovate => home.join(".neovate/skills"),
         ProviderId::Pochi => home.join(".pochi/skills"),
         ProviderId::Adal => home.join(".adal/skills"),
+        ProviderId::Zed => config_home.join("zed/skills"),
+        ProviderId::Aider => home.join(".aider/skills"),
+        ProviderId::Vscode => home.join(".vscode/skills"),
+        ProviderId::Custom(_) => home.join(
+            provider_info(provider)
+                .and_then(|p| p.user_path)
+                .unwrap_or_else(|| project_path_for(provider)),
+        ),
     }
 }
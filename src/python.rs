@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3::IntoPyObjectExt;
+use serde_json::Value;
+
+use crate::error::InstallerError;
+use crate::install::install as install_skill;
+use crate::parser::{parse_skill as parse_skill_source, parse_skill_str};
+use crate::providers::{detect_providers as detect, parse_providers_csv};
+use crate::schema::{DetectReport, Versioned};
+use crate::types::{InstallMethod, InstallOptions, InstallRequest, Scope, SkillSource};
+
+create_exception!(skillinstaller, SkillInstallerError, PyException);
+
+/// Raised as `SkillInstallerError(code, message)`, e.g. `("E_ALREADY_EXISTS", "...")`.
+fn to_py_err(err: InstallerError) -> PyErr {
+    SkillInstallerError::new_err((err.code(), err.to_string()))
+}
+
+fn to_py<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        Value::Null => Ok(py.None().into_bound(py)),
+        Value::Bool(b) => b.into_bound_py_any(py),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_bound_py_any(py),
+            None => n.as_f64().unwrap_or_default().into_bound_py_any(py),
+        },
+        Value::String(s) => s.into_bound_py_any(py),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(to_py(py, item)?)?;
+            }
+            Ok(list.into_any())
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, item) in map {
+                dict.set_item(key, to_py(py, item)?)?;
+            }
+            Ok(dict.into_any())
+        }
+    }
+}
+
+fn to_py_value<'py, T: serde::Serialize>(
+    py: Python<'py>,
+    value: &T,
+) -> PyResult<Bound<'py, PyAny>> {
+    let value =
+        serde_json::to_value(value).map_err(|err| PyValueError::new_err(err.to_string()))?;
+    to_py(py, &value)
+}
+
+/// Parses `scope`/`method` strings with the same kebab-case names the serde types use.
+fn from_name<T: serde::de::DeserializeOwned>(kind: &str, name: &str) -> PyResult<T> {
+    serde_json::from_value(Value::String(name.to_string()))
+        .map_err(|_| PyValueError::new_err(format!("invalid {kind} '{name}'")))
+}
+
+#[pyfunction]
+fn parse_skill<'py>(py: Python<'py>, path: PathBuf) -> PyResult<Bound<'py, PyAny>> {
+    let skill = parse_skill_source(&SkillSource::LocalPath(path)).map_err(to_py_err)?;
+    to_py_value(py, &skill)
+}
+
+#[pyfunction]
+fn parse_skill_md<'py>(py: Python<'py>, content: &str) -> PyResult<Bound<'py, PyAny>> {
+    let skill = parse_skill_str(content).map_err(to_py_err)?;
+    to_py_value(py, &skill)
+}
+
+#[pyfunction]
+#[pyo3(signature = (project_root=None))]
+fn detect_providers<'py>(
+    py: Python<'py>,
+    project_root: Option<PathBuf>,
+) -> PyResult<Bound<'py, PyAny>> {
+    to_py_value(
+        py,
+        &Versioned::new(DetectReport {
+            detected: detect(project_root.as_deref()),
+        }),
+    )
+}
+
+#[pyfunction]
+#[pyo3(signature = (
+    source,
+    providers,
+    *,
+    scope = "project",
+    project_root = None,
+    method = "symlink",
+    force = false,
+    upgrade = false,
+    include_ignored = false,
+    allow_hooks = false,
+    ignore_platform = false,
+    slugify_name = false,
+    target_dirs = Vec::new(),
+    variables = BTreeMap::new(),
+))]
+#[allow(clippy::too_many_arguments)]
+fn install<'py>(
+    py: Python<'py>,
+    source: PathBuf,
+    providers: Vec<String>,
+    scope: &str,
+    project_root: Option<PathBuf>,
+    method: &str,
+    force: bool,
+    upgrade: bool,
+    include_ignored: bool,
+    allow_hooks: bool,
+    ignore_platform: bool,
+    slugify_name: bool,
+    target_dirs: Vec<PathBuf>,
+    variables: BTreeMap<String, String>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let request = InstallRequest {
+        source: SkillSource::LocalPath(source),
+        providers: parse_providers_csv(&providers.join(",")).map_err(to_py_err)?,
+        scope: from_name::<Scope>("scope", scope)?,
+        project_root,
+        method: from_name::<InstallMethod>("method", method)?,
+        force,
+        options: InstallOptions {
+            upgrade,
+            include_ignored,
+            allow_hooks,
+            ignore_platform,
+            slugify_name,
+            target_dirs,
+            variables,
+            ..InstallOptions::default()
+        },
+    };
+    let result = py
+        .allow_threads(|| install_skill(request))
+        .map_err(to_py_err)?;
+    to_py_value(py, &Versioned::new(result))
+}
+
+/// The `skillinstaller` module, also usable from embedded interpreters via
+/// `pyo3::append_to_inittab!`.
+#[pymodule]
+pub fn skillinstaller(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add(
+        "SkillInstallerError",
+        m.py().get_type::<SkillInstallerError>(),
+    )?;
+    m.add_function(wrap_pyfunction!(parse_skill, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_skill_md, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_providers, m)?)?;
+    m.add_function(wrap_pyfunction!(install, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{InstallerError, Result};
+use crate::hash::TreeDigest;
+use crate::types::{ParsedSkill, RegistryPin, SkillSource};
+
+pub const RECEIPT_FILE_NAME: &str = ".skill-receipt.json";
+/// Written next to `.skill/` by registry downloads; installs copy it into the receipt.
+pub const REGISTRY_PIN_FILE_NAME: &str = "registry-pin.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct InstallReceipt {
+    pub name: String,
+    /// Original frontmatter name when the install slugified it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub version: Option<String>,
+    pub source: String,
+    /// Content hash of the installed payload (`sha256:<hex>`), excluding the receipt.
+    pub hash: String,
+    pub installer_version: String,
+    /// Seconds since the Unix epoch.
+    pub installed_at: u64,
+    /// Payload files relative to the skill directory, `/`-separated.
+    pub files: Vec<String>,
+    /// Set when the skill was downloaded from a registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<RegistryPin>,
+}
+
+impl InstallReceipt {
+    pub(crate) fn new(parsed: &ParsedSkill, source: &SkillSource, digest: &TreeDigest) -> Self {
+        let (source, registry) = match source {
+            SkillSource::LocalPath(path) => (
+                path.display().to_string(),
+                fs::read(path.join(REGISTRY_PIN_FILE_NAME))
+                    .ok()
+                    .and_then(|raw| serde_json::from_slice(&raw).ok()),
+            ),
+            SkillSource::Embedded(_) => ("embedded".to_string(), None),
+        };
+        let installed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            name: parsed.name.clone(),
+            display_name: parsed.display_name.clone(),
+            version: parsed
+                .version
+                .as_ref()
+                .map(ToString::to_string)
+                .or_else(|| {
+                    parsed
+                        .metadata
+                        .as_ref()
+                        .and_then(|m| m.get("version"))
+                        .cloned()
+                }),
+            source,
+            hash: digest.digest.clone(),
+            installer_version: env!("CARGO_PKG_VERSION").to_string(),
+            installed_at,
+            files: digest.paths.clone(),
+            registry,
+        }
+    }
+}
+
+pub fn read_receipt(skill_dir: &Path) -> Result<Option<InstallReceipt>> {
+    let path = skill_dir.join(RECEIPT_FILE_NAME);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(InstallerError::IoError {
+                path,
+                message: err.to_string(),
+            })
+        }
+    };
+
+    serde_json::from_str(&raw)
+        .map(Some)
+        .map_err(|err| InstallerError::IoError {
+            path,
+            message: format!("invalid receipt: {err}"),
+        })
+}
+
+pub(crate) fn write_receipt(skill_dir: &Path, receipt: &InstallReceipt) -> Result<()> {
+    let path = skill_dir.join(RECEIPT_FILE_NAME);
+    let json = serde_json::to_string_pretty(receipt).map_err(|err| InstallerError::IoError {
+        path: path.clone(),
+        message: err.to_string(),
+    })?;
+    fs::write(&path, json).map_err(|err| InstallerError::IoError {
+        path,
+        message: err.to_string(),
+    })
+}
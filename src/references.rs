@@ -0,0 +1,102 @@
+use std::path::{Component, Path};
+
+use crate::types::SkillSource;
+
+/// Prefixes that mark a code span or word as a payload path. Other slash-separated text
+/// (`owner/repo`, `src/main.rs` in prose about the user's project) is left alone.
+const PAYLOAD_PREFIXES: &[&str] = &["./", "scripts/", "references/", "assets/"];
+
+fn is_payload_path(word: &str) -> bool {
+    PAYLOAD_PREFIXES
+        .iter()
+        .any(|prefix| word.starts_with(prefix))
+}
+
+/// Relative paths the body points at, from markdown links and from code spans or words
+/// under a payload prefix, in order of first appearance.
+fn body_references(body: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+
+    let mut rest = body;
+    while let Some(start) = rest.find("](") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        // Drop an optional link title: [x](path "title").
+        let target = after[..end].split_whitespace().next().unwrap_or_default();
+        candidates.push(target.trim_matches(['<', '>']));
+        rest = &after[end..];
+    }
+
+    for (index, span) in body.split('`').enumerate() {
+        // Odd segments sit between a pair of backticks.
+        if index % 2 == 1 && is_payload_path(span) && !span.contains(char::is_whitespace) {
+            candidates.push(span);
+        }
+    }
+
+    candidates.extend(
+        body.split_whitespace()
+            .map(|word| word.trim_start_matches(['(', '`', '"', '\'']))
+            .filter(|word| is_payload_path(word))
+            .map(|word| word.trim_end_matches(['.', ',', ';', ':', ')', '`', '"', '\''])),
+    );
+
+    let mut out: Vec<String> = Vec::new();
+    for candidate in candidates {
+        if let Some(path) = local_path(candidate) {
+            if !out.contains(&path) {
+                out.push(path);
+            }
+        }
+    }
+    out
+}
+
+/// Normalizes a reference to a payload-relative path, or `None` for URLs, anchors,
+/// absolute paths, and anything leaving the skill directory.
+fn local_path(raw: &str) -> Option<String> {
+    let path = raw.split(['#', '?']).next().unwrap_or_default();
+    if path.is_empty()
+        || path.contains("://")
+        || path.contains("{{")
+        || path.starts_with(['/', '~', '$'])
+        || path.starts_with("mailto:")
+    {
+        return None;
+    }
+
+    let mut parts = Vec::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Warnings for references in the body that are not part of the payload.
+pub(crate) fn missing_reference_warnings(
+    body: &str,
+    source: &SkillSource,
+    root: Option<&Path>,
+) -> Vec<String> {
+    body_references(body)
+        .into_iter()
+        .filter(|reference| match (source, root) {
+            (SkillSource::LocalPath(_), Some(root)) => !root.join(reference).exists(),
+            (SkillSource::Embedded(embedded), _) => {
+                reference != "SKILL.md"
+                    && !embedded.files.iter().any(|(path, _)| {
+                        let key = path.to_string_lossy().replace('\\', "/");
+                        key == *reference || key.starts_with(&format!("{reference}/"))
+                    })
+            }
+            _ => false,
+        })
+        .map(|reference| format!("SKILL.md references '{reference}', which is not in the skill"))
+        .collect()
+}
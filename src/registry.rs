@@ -0,0 +1,559 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{InstallerError, Result};
+use crate::lint::{lint_skill, Severity};
+use crate::parser::{parse_skill, resolve_local_skill_root, skill_dir_name, validate_skill_name};
+use crate::providers::configured_registry;
+use crate::receipt::REGISTRY_PIN_FILE_NAME;
+use crate::types::{InstalledSkill, RegistryPin, SkillSource};
+
+/// Layout of the registry index this client reads.
+pub const REGISTRY_SCHEMA_VERSION: u32 = 1;
+
+/// A static registry: one JSON file, served over HTTPS or read from disk, listing every
+/// published version of every skill.
+///
+/// ```json
+/// {
+///   "schema-version": 1,
+///   "skills": [{
+///     "name": "pdf-tools",
+///     "version": "1.2.0",
+///     "description": "Fill and merge PDFs",
+///     "tags": ["pdf"],
+///     "url": "pdf-tools-1.2.0.tar.gz",
+///     "hash": "sha256:9f86d0…"
+///   }]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegistryIndex {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub skills: Vec<RegistryEntry>,
+}
+
+/// One published version of a skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegistryEntry {
+    pub name: String,
+    pub version: Version,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// `.tar.gz` of the skill with `SKILL.md` at its root; relative URLs resolve against
+    /// the index.
+    pub url: String,
+    /// `sha256:<hex>` of the archive.
+    pub hash: String,
+}
+
+impl RegistryIndex {
+    /// Latest version of each skill whose name, description, or tags contain `query`,
+    /// ignoring case; an empty query lists every skill.
+    pub fn search(&self, query: &str) -> Vec<&RegistryEntry> {
+        let query = query.to_lowercase();
+        let mut found: Vec<&RegistryEntry> = Vec::new();
+        for entry in &self.skills {
+            let matches = entry.name.to_lowercase().contains(&query)
+                || entry.description.to_lowercase().contains(&query)
+                || entry.tags.iter().any(|t| t.to_lowercase().contains(&query));
+            if !matches {
+                continue;
+            }
+            match found.iter_mut().find(|e| e.name == entry.name) {
+                Some(latest) if latest.version < entry.version => *latest = entry,
+                Some(_) => {}
+                None => found.push(entry),
+            }
+        }
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        found
+    }
+
+    /// Highest version of `name` that satisfies `version`, or the highest overall.
+    pub fn resolve(&self, name: &str, version: Option<&VersionReq>) -> Option<&RegistryEntry> {
+        self.skills
+            .iter()
+            .filter(|e| e.name == name)
+            .filter(|e| version.is_none_or(|req| req.matches(&e.version)))
+            .max_by(|a, b| a.version.cmp(&b.version))
+    }
+
+    /// The release `installed` can move to: the highest version its recorded range allows
+    /// (any, when it has none), if that is newer than the installed one.
+    pub fn update_for(&self, name: &str, installed: &InstalledSkill) -> Option<&RegistryEntry> {
+        let requirement = installed
+            .registry
+            .as_ref()
+            .and_then(|pin| pin.requirement.as_deref())
+            .and_then(|raw| VersionReq::parse(raw).ok());
+        self.resolve(name, requirement.as_ref()).filter(|entry| {
+            installed
+                .version
+                .as_ref()
+                .is_none_or(|v| *v < entry.version)
+        })
+    }
+}
+
+/// Splits `name@version` into the name and a version requirement. A full version pins it
+/// exactly (`pdf-tools@1.2.0`); anything else is a semver range (`pdf-tools@^1`).
+pub fn parse_skill_spec(spec: &str) -> Result<(String, Option<VersionReq>)> {
+    let Some((name, version)) = spec.split_once('@') else {
+        return Ok((spec.to_string(), None));
+    };
+    let req = match Version::parse(version) {
+        Ok(exact) => VersionReq::parse(&format!("={exact}")),
+        Err(_) => VersionReq::parse(version),
+    }
+    .map_err(|err| InstallerError::InvalidName {
+        name: format!("{spec} ({err})"),
+    })?;
+    Ok((name.to_string(), Some(req)))
+}
+
+/// `$XDG_CACHE_HOME/skill-installer/registry`, falling back to `~/.cache`; downloaded
+/// skills are unpacked here.
+pub fn registry_cache_dir() -> Option<PathBuf> {
+    let cache_home = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(cache_home.join("skill-installer/registry"))
+}
+
+/// Reads a registry index and downloads the skills it lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryClient {
+    index_url: String,
+    allow_http: bool,
+}
+
+impl RegistryClient {
+    /// `index_url` is an `https://` URL, a `file://` URL, or a path; plain `http://` needs
+    /// `allow_http`.
+    pub fn new(index_url: impl Into<String>) -> Self {
+        Self {
+            index_url: index_url.into(),
+            allow_http: false,
+        }
+    }
+
+    /// Also fetches the index and archives over unencrypted `http://`. Archives are still
+    /// hash-checked, but against hashes from an index anyone on the path could rewrite.
+    pub fn allow_http(mut self, allow: bool) -> Self {
+        self.allow_http = allow;
+        self
+    }
+
+    /// The `registry` URL from the config files.
+    pub fn configured() -> Result<Self> {
+        configured_registry()
+            .map(Self::new)
+            .ok_or_else(|| InstallerError::RegistryError {
+                url: String::new(),
+                message: "no registry configured; set `registry` in config.toml or pass --registry"
+                    .to_string(),
+            })
+    }
+
+    pub fn index_url(&self) -> &str {
+        &self.index_url
+    }
+
+    pub fn fetch_index(&self) -> Result<RegistryIndex> {
+        let raw = fetch(&self.index_url, self.allow_http)?;
+        let index: RegistryIndex =
+            serde_json::from_slice(&raw).map_err(|err| self.error(err.to_string()))?;
+        if index.schema_version > REGISTRY_SCHEMA_VERSION {
+            return Err(self.error(format!(
+                "index schema version {} is newer than this installer supports ({REGISTRY_SCHEMA_VERSION})",
+                index.schema_version
+            )));
+        }
+        // Entry names become cache paths, so reject anything that could escape the cache.
+        for entry in &index.skills {
+            if validate_skill_name(&entry.name).is_err() {
+                return Err(self.error(format!("invalid skill name '{}' in index", entry.name)));
+            }
+        }
+        Ok(index)
+    }
+
+    /// Downloads `entry`, checks its hash, and unpacks it under `registry_cache_dir()`.
+    /// The returned directory holds the skill in `.skill/`, ready for
+    /// `SkillSource::LocalPath`, next to a `RegistryPin` that installs copy into their
+    /// receipt; `requirement` is the range `entry` was resolved from.
+    pub fn download(
+        &self,
+        entry: &RegistryEntry,
+        requirement: Option<&VersionReq>,
+    ) -> Result<PathBuf> {
+        let cache = registry_cache_dir().ok_or_else(|| {
+            self.error("no cache directory; set XDG_CACHE_HOME or HOME".to_string())
+        })?;
+        self.download_to(
+            entry,
+            requirement,
+            &cache.join(&entry.name).join(entry.version.to_string()),
+        )
+    }
+
+    /// `download`, unpacking into `dir` instead of the cache; `dir` is replaced.
+    pub fn download_to(
+        &self,
+        entry: &RegistryEntry,
+        requirement: Option<&VersionReq>,
+        dir: &Path,
+    ) -> Result<PathBuf> {
+        let url = self.resolve_url(&entry.url)?;
+        let archive = fetch(&url, self.allow_http)?;
+        let actual = sha256_hex(&archive);
+        if entry.hash.strip_prefix("sha256:") != Some(actual.as_str()) {
+            return Err(InstallerError::RegistryError {
+                url,
+                message: format!(
+                    "hash mismatch: expected {}, got sha256:{actual}",
+                    entry.hash
+                ),
+            });
+        }
+
+        let io_err = |path: &Path| {
+            let path = path.to_path_buf();
+            move |err: std::io::Error| InstallerError::IoError {
+                path,
+                message: err.to_string(),
+            }
+        };
+        // Not `with_extension`: it would turn `1.2.3` into `1.2.partial` for every patch.
+        let mut partial_name = dir.file_name().unwrap_or_default().to_os_string();
+        partial_name.push(".partial");
+        let partial = dir.with_file_name(partial_name);
+        if partial.exists() {
+            fs::remove_dir_all(&partial).map_err(io_err(&partial))?;
+        }
+        let skill_dir = partial.join(".skill");
+        fs::create_dir_all(&skill_dir).map_err(io_err(&skill_dir))?;
+        tar::Archive::new(flate2::read::GzDecoder::new(archive.as_slice()))
+            .unpack(&skill_dir)
+            .map_err(|err| InstallerError::RegistryError {
+                url: url.clone(),
+                message: format!("failed to unpack: {err}"),
+            })?;
+
+        let parsed = parse_skill(&SkillSource::LocalPath(partial.clone()))?;
+        if parsed.name != entry.name {
+            let _ = fs::remove_dir_all(&partial);
+            return Err(InstallerError::RegistryError {
+                url,
+                message: format!(
+                    "archive contains skill '{}', not '{}'",
+                    parsed.name, entry.name
+                ),
+            });
+        }
+
+        let pin = RegistryPin {
+            index: self.index_url.clone(),
+            name: entry.name.clone(),
+            version: entry.version.to_string(),
+            requirement: requirement.map(ToString::to_string),
+            hash: entry.hash.clone(),
+        };
+        let pin_path = partial.join(REGISTRY_PIN_FILE_NAME);
+        let pin = serde_json::to_vec_pretty(&pin).expect("pins serialize to JSON");
+        fs::write(&pin_path, pin).map_err(io_err(&pin_path))?;
+
+        if dir.exists() {
+            fs::remove_dir_all(dir).map_err(io_err(dir))?;
+        }
+        fs::rename(&partial, dir).map_err(io_err(dir))?;
+        Ok(dir.to_path_buf())
+    }
+
+    /// `url` relative to the directory holding the index. An absolute URL must use the
+    /// index's scheme, and local paths and `file://` URLs are only followed from a local
+    /// index, so a remote index cannot point downloads at the user's disk.
+    pub fn resolve_url(&self, url: &str) -> Result<String> {
+        let index_scheme = url_scheme(&self.index_url);
+        let local_index = matches!(index_scheme.as_deref(), None | Some("file"));
+        let allowed = match url_scheme(url) {
+            Some(scheme) => {
+                Some(&scheme) == index_scheme.as_ref() || (local_index && scheme == "file")
+            }
+            None if Path::new(url).is_absolute() || url.starts_with(['/', '\\']) => local_index,
+            None => {
+                return Ok(match self.index_url.rsplit_once('/') {
+                    Some((base, _)) => format!("{base}/{url}"),
+                    None => url.to_string(),
+                })
+            }
+        };
+        if !allowed {
+            return Err(self.error(format!(
+                "archive url '{url}' must be relative or use the index's scheme"
+            )));
+        }
+        Ok(url.to_string())
+    }
+
+    fn error(&self, message: String) -> InstallerError {
+        InstallerError::RegistryError {
+            url: self.index_url.clone(),
+            message,
+        }
+    }
+}
+
+/// A validated skill packed as a registry archive, with the index entry describing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedSkill {
+    /// `url` is the archive's file name, `<skill_dir_name>-<version>.tar.gz`.
+    pub entry: RegistryEntry,
+    pub archive: Vec<u8>,
+}
+
+/// Parses and lints the skill at `path` (a directory containing `.skill/`, or the `.skill`
+/// directory itself) and packs its payload files into a `.tar.gz`.
+///
+/// Publishing needs a frontmatter `version`; lint errors and a missing version are
+/// `InstallerError::LintFailed`. Tags come from a comma-separated `metadata.tags`. The
+/// archive is byte-for-byte reproducible, so republishing unchanged files keeps the hash.
+pub fn pack_skill(path: &Path) -> Result<PackedSkill> {
+    let root = resolve_local_skill_root(path)?;
+    let skill_md = root.join("SKILL.md");
+    let parsed = parse_skill(&SkillSource::LocalPath(path.to_path_buf()))?;
+    let payload = fs::read(&skill_md).map_err(|err| InstallerError::IoError {
+        path: skill_md.clone(),
+        message: err.to_string(),
+    })?;
+    let mut errors = lint_skill(&parsed, &payload)
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+        .map(|d| format!("{}: {}", d.rule, d.message))
+        .collect::<Vec<_>>();
+    if parsed.version.is_none() {
+        errors.push("a `version` is required to publish".to_string());
+    }
+    let Some(version) = parsed.version.clone().filter(|_| errors.is_empty()) else {
+        return Err(InstallerError::LintFailed {
+            path: skill_md,
+            message: errors.join("; "),
+        });
+    };
+
+    let pack_err = |err: std::io::Error| InstallerError::IoError {
+        path: root.clone(),
+        message: format!("failed to pack: {err}"),
+    };
+    let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    tar.mode(tar::HeaderMode::Deterministic);
+    for file in &parsed.files {
+        tar.append_path_with_name(root.join(&file.path), &file.path)
+            .map_err(pack_err)?;
+    }
+    let archive = tar
+        .into_inner()
+        .and_then(|gz| gz.finish())
+        .map_err(pack_err)?;
+
+    let tags = parsed
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("tags"))
+        .map(|tags| {
+            tags.split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(PackedSkill {
+        entry: RegistryEntry {
+            url: format!("{}-{version}.tar.gz", skill_dir_name(&parsed.name)),
+            hash: format!("sha256:{}", sha256_hex(&archive)),
+            name: parsed.name,
+            version,
+            description: parsed.description.unwrap_or_default(),
+            tags,
+        },
+        archive,
+    })
+}
+
+impl PackedSkill {
+    /// Writes the archive into `dir` and adds its entry to `dir/index.json`, creating the
+    /// index if needed. Publishing a name and version that is already listed is an error.
+    pub fn add_to_index(&self, dir: &Path) -> Result<PathBuf> {
+        let index_path = dir.join("index.json");
+        let io_err = |path: &Path| {
+            let path = path.to_path_buf();
+            move |err: std::io::Error| InstallerError::IoError {
+                path,
+                message: err.to_string(),
+            }
+        };
+        let mut index = if index_path.exists() {
+            RegistryClient::new(index_path.to_string_lossy()).fetch_index()?
+        } else {
+            RegistryIndex {
+                schema_version: REGISTRY_SCHEMA_VERSION,
+                skills: Vec::new(),
+            }
+        };
+        if index
+            .skills
+            .iter()
+            .any(|e| e.name == self.entry.name && e.version == self.entry.version)
+        {
+            return Err(InstallerError::RegistryError {
+                url: index_path.display().to_string(),
+                message: format!(
+                    "{} {} is already published",
+                    self.entry.name, self.entry.version
+                ),
+            });
+        }
+
+        let archive_path = dir.join(&self.entry.url);
+        fs::write(&archive_path, &self.archive).map_err(io_err(&archive_path))?;
+        index.skills.push(self.entry.clone());
+        index
+            .skills
+            .sort_by(|a, b| a.name.cmp(&b.name).then(a.version.cmp(&b.version)));
+        let json =
+            serde_json::to_string_pretty(&index).map_err(|err| InstallerError::RegistryError {
+                url: index_path.display().to_string(),
+                message: err.to_string(),
+            })?;
+        fs::write(&index_path, json + "\n").map_err(io_err(&index_path))?;
+        Ok(index_path)
+    }
+
+    /// PUTs the archive to `<endpoint>/<name>/<version>` with its hash in `X-Skill-Hash`
+    /// and `token`, if any, as a bearer token; updating the index is up to the server.
+    /// The name is one percent-encoded segment, so `org/name` is sent as `org%2Fname`.
+    pub fn upload(&self, endpoint: &str, token: Option<&str>) -> Result<String> {
+        let url = format!(
+            "{}/{}/{}",
+            endpoint.trim_end_matches('/'),
+            percent_encode_segment(&self.entry.name),
+            self.entry.version
+        );
+        trace_span!(DEBUG, "upload", url = %url);
+        let mut request = ureq::put(&url)
+            .header("Content-Type", "application/gzip")
+            .header("X-Skill-Hash", &self.entry.hash);
+        if let Some(token) = token {
+            request = request.header("Authorization", &format!("Bearer {token}"));
+        }
+        request
+            .send(self.archive.as_slice())
+            .map_err(|err| InstallerError::RegistryError {
+                url: url.clone(),
+                message: err.to_string(),
+            })?;
+        trace_event!(debug, bytes = self.archive.len(), "uploaded");
+        Ok(url)
+    }
+}
+
+/// Percent-encodes everything outside RFC 3986's unreserved set.
+fn percent_encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// The lowercased scheme of `url`, if it has one.
+fn url_scheme(url: &str) -> Option<String> {
+    let (scheme, _) = url.split_once("://")?;
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then(|| scheme.to_ascii_lowercase())
+}
+
+/// Largest index or archive `fetch` reads into memory.
+const MAX_FETCH_BYTES: u64 = 64 * 1024 * 1024;
+
+/// GETs an `https://` URL (or `http://` with `allow_http`), or reads a `file://` URL or
+/// plain path, refusing bodies over `MAX_FETCH_BYTES`.
+pub(crate) fn fetch(url: &str, allow_http: bool) -> Result<Vec<u8>> {
+    trace_span!(DEBUG, "fetch", url);
+    let registry_err = |message: String| InstallerError::RegistryError {
+        url: url.to_string(),
+        message,
+    };
+    let reader: Box<dyn Read> = match url_scheme(url).as_deref() {
+        Some("https") => get(url)?,
+        Some("http") if allow_http => get(url)?,
+        Some("http") => {
+            return Err(registry_err(
+                "plain http is not allowed; use https or pass --allow-http".to_string(),
+            ))
+        }
+        Some("file") => open(&url["file://".len()..])?,
+        None => open(url)?,
+        Some(scheme) => return Err(registry_err(format!("unsupported scheme '{scheme}'"))),
+    };
+    let mut body = Vec::new();
+    reader
+        .take(MAX_FETCH_BYTES + 1)
+        .read_to_end(&mut body)
+        .map_err(|err| registry_err(err.to_string()))?;
+    if body.len() as u64 > MAX_FETCH_BYTES {
+        return Err(registry_err(format!(
+            "larger than {} MiB",
+            MAX_FETCH_BYTES / 1024 / 1024
+        )));
+    }
+    trace_event!(debug, bytes = body.len(), "fetched");
+    Ok(body)
+}
+
+fn get(url: &str) -> Result<Box<dyn Read>> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|err| InstallerError::RegistryError {
+            url: url.to_string(),
+            message: err.to_string(),
+        })?;
+    Ok(Box::new(response.into_body().into_reader()))
+}
+
+fn open(path: &str) -> Result<Box<dyn Read>> {
+    let file = fs::File::open(path).map_err(|err| InstallerError::RegistryError {
+        url: path.to_string(),
+        message: err.to_string(),
+    })?;
+    Ok(Box::new(file))
+}
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
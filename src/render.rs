@@ -0,0 +1,252 @@
+use std::fmt::Write;
+use std::io::IsTerminal;
+
+use crate::schema::Versioned;
+use crate::types::{InstallPlan, InstallResult, PlannedAction};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// How much the CLI prints: `-q` for errors only, `-v` per destination, `-vv` per file.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+    Debug,
+}
+
+impl Verbosity {
+    /// `quiet` wins over any number of `-v`.
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Debug,
+        }
+    }
+}
+
+impl ColorChoice {
+    /// `self`, unless it is `Auto` and neither `NO_COLOR` nor `CLICOLOR_FORCE` is set, in
+    /// which case the config file's choice.
+    pub fn or_configured(self, configured: Option<ColorChoice>) -> ColorChoice {
+        let set = |name| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+        match configured {
+            Some(choice)
+                if self == ColorChoice::Auto && !set("NO_COLOR") && !set("CLICOLOR_FORCE") =>
+            {
+                choice
+            }
+            _ => self,
+        }
+    }
+
+    /// `Auto` honors `NO_COLOR`, then `CLICOLOR_FORCE`, then whether stdout is a terminal.
+    pub fn enabled(self) -> bool {
+        let set = |name| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto if set("NO_COLOR") => false,
+            ColorChoice::Auto if set("CLICOLOR_FORCE") => {
+                std::env::var_os("CLICOLOR_FORCE").is_some_and(|value| value != "0")
+            }
+            ColorChoice::Auto => std::io::stdout().is_terminal(),
+        }
+    }
+
+    /// `self` with the `[defaults]` color from the config files applied, then `enabled`.
+    #[cfg(feature = "cli")]
+    pub fn resolve(self) -> bool {
+        self.or_configured(crate::providers::configured_defaults().color)
+            .enabled()
+    }
+}
+
+pub(crate) const BOLD: &str = "1";
+pub(crate) const DIM: &str = "2";
+pub(crate) const RED: &str = "31";
+pub(crate) const GREEN: &str = "32";
+pub(crate) const YELLOW: &str = "33";
+
+/// `text` wrapped in the ANSI `style` when `color` is on.
+pub(crate) fn paint(text: impl std::fmt::Display, style: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{style}m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+impl InstallResult {
+    /// Human summary, or JSON/YAML stamped with `schema-version`.
+    pub fn render(&self, format: OutputFormat) -> String {
+        self.render_styled(format, false)
+    }
+
+    /// `render`, with the text summary colored when `color` is on.
+    pub fn render_styled(&self, format: OutputFormat, color: bool) -> String {
+        match format {
+            OutputFormat::Json => return Versioned::new(self).to_json(),
+            OutputFormat::Yaml => return Versioned::new(self).to_yaml(),
+            OutputFormat::Text => {}
+        }
+
+        let mut out = String::new();
+        match &self.skill_version {
+            Some(version) => writeln!(
+                out,
+                "installed skill: {} {version}",
+                paint(&self.skill_name, BOLD, color)
+            ),
+            None => writeln!(
+                out,
+                "installed skill: {}",
+                paint(&self.skill_name, BOLD, color)
+            ),
+        }
+        .unwrap();
+
+        for target in &self.installed_targets {
+            writeln!(
+                out,
+                "  {} -> {} ({}){}",
+                target.requested_provider.as_str(),
+                paint(target.target_provider.as_str(), GREEN, color),
+                target.target_dir.display(),
+                if target.stats.up_to_date {
+                    paint(" already up-to-date", DIM, color)
+                } else {
+                    String::new()
+                }
+            )
+            .unwrap();
+        }
+
+        let totals = format!(
+            "  {} files, {} bytes in {:.2?}",
+            self.total_files(),
+            self.total_bytes(),
+            self.elapsed
+        );
+        writeln!(out, "{}", paint(totals, DIM, color)).unwrap();
+
+        if !self.warnings.is_empty() {
+            writeln!(out, "{}", paint("warnings:", YELLOW, color)).unwrap();
+            for w in &self.warnings {
+                writeln!(out, "  - {w}").unwrap();
+            }
+        }
+        out
+    }
+}
+
+impl InstallPlan {
+    /// What `execute_plan` would do, for `--dry-run`; JSON/YAML are stamped with `schema-version`.
+    pub fn render(&self, format: OutputFormat) -> String {
+        self.render_styled(format, false)
+    }
+
+    /// `render`, with the text plan colored when `color` is on.
+    pub fn render_styled(&self, format: OutputFormat, color: bool) -> String {
+        match format {
+            OutputFormat::Json => return Versioned::new(self).to_json(),
+            OutputFormat::Yaml => return Versioned::new(self).to_yaml(),
+            OutputFormat::Text => {}
+        }
+
+        let mut out = String::new();
+        match &self.skill_version {
+            Some(version) => writeln!(
+                out,
+                "would install skill: {} {version}",
+                paint(&self.skill_name, BOLD, color)
+            ),
+            None => writeln!(
+                out,
+                "would install skill: {}",
+                paint(&self.skill_name, BOLD, color)
+            ),
+        }
+        .unwrap();
+
+        for target in &self.targets {
+            let note = match target.action {
+                PlannedAction::Create | PlannedAction::Link => String::new(),
+                action => paint(format!(" ({})", action.as_str()), YELLOW, color),
+            };
+            writeln!(
+                out,
+                "  {} -> {} ({}){note}",
+                target.requested_provider.as_str(),
+                paint(target.target_provider.as_str(), GREEN, color),
+                target.destination.display()
+            )
+            .unwrap();
+        }
+        for (requested, target) in &self.normalized_providers {
+            writeln!(
+                out,
+                "  {} normalized to {}",
+                requested.as_str(),
+                target.as_str()
+            )
+            .unwrap();
+        }
+        for dir in &self.create_dirs {
+            writeln!(
+                out,
+                "{}",
+                paint(format!("  creates {}", dir.display()), DIM, color)
+            )
+            .unwrap();
+        }
+        if !self.conflicts.is_empty() {
+            let heading = "conflicts (pass --force or --upgrade):";
+            writeln!(out, "{}", paint(heading, RED, color)).unwrap();
+            for path in &self.conflicts {
+                writeln!(out, "  - {}", path.display()).unwrap();
+            }
+        }
+
+        if !self.warnings.is_empty() {
+            writeln!(out, "{}", paint("warnings:", YELLOW, color)).unwrap();
+            for w in &self.warnings {
+                writeln!(out, "  - {w}").unwrap();
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,293 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::providers::ProviderInfo;
+#[cfg(feature = "registry")]
+use crate::registry::RegistryEntry;
+use crate::types::{DetectedProvider, InstallPlan, InstallResult, InstalledSkill};
+
+/// Version stamped as `schema-version` on every JSON/YAML report.
+///
+/// Within a version, fields and enum values are only ever added, so consumers should ignore
+/// unknown keys. Renaming or removing a field, or changing its type, bumps the version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A report with `schema-version` alongside its own (flattened) fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Versioned<T> {
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub report: T,
+}
+
+impl<T> Versioned<T> {
+    pub fn new(report: T) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            report,
+        }
+    }
+}
+
+impl<T: Serialize> Versioned<T> {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports serialize to JSON") + "\n"
+    }
+
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(self).expect("reports serialize to YAML")
+    }
+}
+
+/// `detect` output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DetectReport {
+    pub detected: Vec<DetectedProvider>,
+}
+
+/// `detect_installed` output for one skill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InstalledReport {
+    pub skill_name: String,
+    pub installed: Vec<InstalledSkill>,
+}
+
+/// `list` output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InstalledListReport {
+    pub skills: Vec<InstalledReport>,
+    /// Newer registry releases, with `--outdated`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub updates: Vec<AvailableUpdate>,
+}
+
+/// A skill with a newer release in the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AvailableUpdate {
+    pub skill_name: String,
+    /// Highest installed version, if any install records one.
+    pub current: Option<semver::Version>,
+    /// Highest version the recorded range allows; what `update` installs.
+    pub wanted: Option<semver::Version>,
+    pub latest: semver::Version,
+}
+
+/// `install` output for several `--source`s: the skills that installed, then the sources
+/// that failed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InstallBatchReport {
+    pub installed: Vec<InstallResult>,
+    pub failed: Vec<InstallFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InstallFailure {
+    pub source: PathBuf,
+    /// `InstallerError::code()`.
+    pub code: String,
+    pub message: String,
+}
+
+/// `install --dry-run` output for several `--source`s, in order.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PlanBatchReport {
+    pub plans: Vec<InstallPlan>,
+}
+
+/// `search` output: the latest version of each matching skill.
+#[cfg(feature = "registry")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegistrySearchReport {
+    pub registry: String,
+    pub skills: Vec<RegistryEntry>,
+}
+
+/// `update` output: installs replaced by a newer registry version, then skills already at
+/// the latest one.
+#[cfg(feature = "registry")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateReport {
+    pub updated: Vec<InstallResult>,
+    pub up_to_date: Vec<String>,
+}
+
+/// `publish` output.
+#[cfg(feature = "registry")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PublishReport {
+    pub skill: RegistryEntry,
+    /// Upload URL, or the index file for `--git`; `None` for `--dry-run`.
+    pub published_to: Option<String>,
+    /// Branch pushed for `--git`.
+    pub branch: Option<String>,
+}
+
+/// `doctor` output: every check that ran, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DoctorCheck {
+    /// What was checked, e.g. `config` or `links`.
+    pub check: String,
+    pub status: DoctorStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DoctorStatus {
+    Ok,
+    Warning,
+    /// `doctor` exits with `1` when any check has this status.
+    Error,
+}
+
+impl DoctorStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DoctorStatus::Ok => "ok",
+            DoctorStatus::Warning => "warning",
+            DoctorStatus::Error => "error",
+        }
+    }
+}
+
+/// `providers` output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProviderListReport {
+    pub providers: Vec<ProviderSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProviderSummary {
+    pub id: String,
+    pub display_name: String,
+    pub project_path: String,
+    pub uses_agents_dir: bool,
+    pub supports_project_scope: bool,
+    pub supports_user_scope: bool,
+    pub deprecated: Option<String>,
+}
+
+impl From<&ProviderInfo> for ProviderSummary {
+    fn from(info: &ProviderInfo) -> Self {
+        Self {
+            id: info.id.as_str().to_string(),
+            display_name: info.display_name.to_string(),
+            project_path: info.project_path.to_string(),
+            uses_agents_dir: info.uses_agents_dir,
+            supports_project_scope: info.capabilities.supports_project_scope,
+            supports_user_scope: info.capabilities.supports_user_scope,
+            deprecated: info.deprecated.map(str::to_string),
+        }
+    }
+}
+
+/// JSON Schema for `SKILL.md` frontmatter, usable with yaml-language-server.
+pub fn skill_frontmatter_schema() -> Value {
+    let string_list = json!({ "type": "array", "items": { "type": "string" } });
+    // Free-text fields take any scalar; the parser reads `description: 42` as "42".
+    let scalar = json!({ "type": ["string", "number", "boolean"] });
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "$id": "https://github.com/j0nl1/skill-installer/skill-frontmatter.schema.json",
+        "title": "SKILL.md frontmatter",
+        "type": "object",
+        "required": ["name"],
+        "additionalProperties": false,
+        "properties": {
+            "name": {
+                "type": ["string", "number", "boolean"],
+                "description": "Skill name, optionally namespaced as `org/name`; also the installed directory name",
+                "minLength": 1,
+                "maxLength": 64,
+                "pattern": "^([^/\\\\:*?\"<>|]+/)?[^/\\\\:*?\"<>|]+$"
+            },
+            "description": {
+                "type": ["string", "number", "boolean"],
+                "description": "What the skill does and when an agent should use it",
+                "maxLength": 1024
+            },
+            "version": {
+                "type": "string",
+                "description": "Semantic version, quoted so YAML keeps it a string; compared by --upgrade",
+                "pattern": "^\\d+\\.\\d+\\.\\d+(-[0-9A-Za-z.-]+)?(\\+[0-9A-Za-z.-]+)?$"
+            },
+            "license": scalar,
+            "author": scalar,
+            "authors": string_list,
+            "compatibility": scalar,
+            "allowed-tools": scalar,
+            "providers": {
+                "description": "Providers the skill supports; a list is shorthand for `allow`",
+                "oneOf": [
+                    string_list,
+                    {
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": {
+                            "allow": string_list,
+                            "deny": string_list
+                        }
+                    }
+                ]
+            },
+            "platforms": {
+                "type": "array",
+                "items": {
+                    "enum": ["linux", "macos", "darwin", "osx", "windows", "win32", "unix"]
+                }
+            },
+            "variables": {
+                "type": "object",
+                "description": "Template variables substituted into `{{name}}` placeholders in the body",
+                "additionalProperties": {
+                    "oneOf": [
+                        scalar,
+                        {
+                            "type": "object",
+                            "additionalProperties": false,
+                            "properties": {
+                                "description": { "type": "string" },
+                                "default": scalar
+                            }
+                        }
+                    ]
+                }
+            },
+            "metadata": {
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "hooks": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "pre-install": { "type": "string" },
+                    "post-install": { "type": "string" }
+                }
+            }
+        }
+    })
+}
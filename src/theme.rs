@@ -0,0 +1,81 @@
+use std::sync::RwLock;
+
+pub use ratatui::style::Color;
+use ratatui::style::Style;
+
+use crate::render::ColorChoice;
+
+/// Colors of the interactive prompts; install one with `set_theme`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Headings such as the "Selected:" footer.
+    pub accent: Color,
+    /// Hints, paths, dividers, and unselected markers.
+    pub dim: Color,
+    /// Markers of chosen items and the select cursor.
+    pub selected: Color,
+    /// Failures, such as a destination that could not be installed.
+    pub error: Color,
+}
+
+impl Theme {
+    pub const fn dark() -> Self {
+        Self {
+            accent: Color::Green,
+            dim: Color::DarkGray,
+            selected: Color::Green,
+            error: Color::Red,
+        }
+    }
+
+    /// Darker shades that stay readable on white backgrounds.
+    pub const fn light() -> Self {
+        Self {
+            accent: Color::Blue,
+            dim: Color::Indexed(242),
+            selected: Color::Indexed(28),
+            error: Color::Indexed(124),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+static THEME: RwLock<Theme> = RwLock::new(Theme::dark());
+static COLOR: RwLock<ColorChoice> = RwLock::new(ColorChoice::Auto);
+
+pub fn set_theme(theme: Theme) {
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+/// Whether prompts are colored; `InstallSkillArgs::color` sets this for interactive installs.
+pub fn set_color_choice(choice: ColorChoice) {
+    *COLOR.write().unwrap_or_else(|e| e.into_inner()) = choice;
+}
+
+fn styled(pick: fn(&Theme) -> Color) -> Style {
+    if !COLOR.read().unwrap_or_else(|e| e.into_inner()).enabled() {
+        return Style::default();
+    }
+    Style::default().fg(pick(&THEME.read().unwrap_or_else(|e| e.into_inner())))
+}
+
+pub(crate) fn accent() -> Style {
+    styled(|theme| theme.accent)
+}
+
+pub(crate) fn dim() -> Style {
+    styled(|theme| theme.dim)
+}
+
+pub(crate) fn selected() -> Style {
+    styled(|theme| theme.selected)
+}
+
+pub(crate) fn error() -> Style {
+    styled(|theme| theme.error)
+}
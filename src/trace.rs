@@ -0,0 +1,16 @@
+//! Thin wrappers over `tracing` that compile to nothing without the `tracing` feature.
+
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
+/// Enters a span until the end of the enclosing block.
+macro_rules! trace_span {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $($arg)+).entered();
+    };
+}
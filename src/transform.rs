@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::error::Result;
+use crate::types::{ParsedSkill, ProviderId};
+
+/// Adapts the payload installed for one provider.
+///
+/// Transforms run against the staged copy before it is moved into place, so a
+/// failing transform leaves the destination untouched. The shared universal
+/// copy is never transformed; in symlink mode, providers with a transform get
+/// their own copy instead of a link.
+pub trait ProviderTransform: Send + Sync {
+    fn transform(&self, provider: ProviderId, skill_dir: &Path, skill: &ParsedSkill) -> Result<()>;
+}
+
+#[derive(Clone, Default)]
+pub struct ProviderTransforms(HashMap<ProviderId, Arc<dyn ProviderTransform>>);
+
+impl ProviderTransforms {
+    pub fn insert(&mut self, provider: ProviderId, transform: Arc<dyn ProviderTransform>) {
+        self.0.insert(provider, transform);
+    }
+
+    pub fn get(&self, provider: ProviderId) -> Option<&dyn ProviderTransform> {
+        if provider == ProviderId::Universal {
+            return None;
+        }
+        self.0.get(&provider).map(Arc::as_ref)
+    }
+}
+
+impl fmt::Debug for ProviderTransforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
@@ -0,0 +1,1746 @@
+use std::collections::HashSet;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use ratatui::{Terminal, TerminalOptions, Viewport};
+
+use crate::error::{InstallerError, Result};
+use crate::interactive::{
+    prompt_provider_selection_with, InteractiveProviderSelection,
+    InteractiveProviderSelectionOptions,
+};
+use crate::locale::{fill, messages};
+use crate::observer::{InstallEvent, InstallObserver, SkipReason};
+use crate::prompter::{detection_reason, Prompter, ProviderPrompt};
+use crate::providers::{project_path_for, provider_display_name, ProviderGroup};
+use crate::theme;
+use crate::types::{DetectedProvider, InstallResult, PlannedTarget, ProviderId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PickerRow {
+    Preset(usize),
+    /// Section title; the cursor skips it.
+    Heading(&'static str),
+    Provider(ProviderId),
+}
+
+#[derive(Debug)]
+struct UiState {
+    query: String,
+    cursor: usize,
+    selected: HashSet<ProviderId>,
+    disabled: HashSet<ProviderId>,
+    scroll_offset: usize,
+    /// Where the list was last drawn, in screen coordinates, for mouse clicks.
+    list_area: Rect,
+    /// Toggled by Tab: letters run commands and move vim-style instead of searching.
+    navigating: bool,
+}
+
+impl UiState {
+    fn toggle(&mut self, presets: &[ProviderGroup], row: PickerRow) {
+        match row {
+            PickerRow::Provider(provider) if self.disabled.contains(&provider) => {}
+            PickerRow::Provider(provider) => {
+                if !self.selected.remove(&provider) {
+                    self.selected.insert(provider);
+                }
+            }
+            PickerRow::Preset(index) => {
+                let members = self.preset_members(&presets[index]);
+                if members.iter().all(|p| self.selected.contains(p)) {
+                    for provider in members {
+                        self.selected.remove(provider);
+                    }
+                } else {
+                    self.selected.extend(members);
+                }
+            }
+            PickerRow::Heading(_) => {}
+        }
+    }
+
+    /// Moves the cursor `delta` rows, clamped to the list, then onto the nearest
+    /// non-heading row in the direction of travel.
+    fn move_cursor(&mut self, filtered: &[PickerRow], delta: isize) {
+        if filtered.is_empty() {
+            return;
+        }
+        let target = self
+            .cursor
+            .saturating_add_signed(delta)
+            .min(filtered.len() - 1);
+        let is_row = |i: &usize| !matches!(filtered[*i], PickerRow::Heading(_));
+        let ahead = (target..filtered.len()).find(is_row);
+        let behind = (0..=target).rev().find(is_row);
+        let nearest = if delta >= 0 {
+            ahead.or(behind)
+        } else {
+            behind.or(ahead)
+        };
+        if let Some(cursor) = nearest {
+            self.cursor = cursor;
+        }
+    }
+
+    /// Index into the filtered rows under screen row `row`, skipping the "more" markers.
+    fn row_at(&self, total: usize, row: u16) -> Option<usize> {
+        let area = self.list_area;
+        if row < area.y || row >= area.bottom() {
+            return None;
+        }
+        let (has_top, visible) = visible_rows(total, self.scroll_offset, area.height as usize);
+        let line = (row - area.y) as usize;
+        let index = visible.start + line.checked_sub(usize::from(has_top))?;
+        visible.contains(&index).then_some(index)
+    }
+
+    fn preset_members<'p>(&self, preset: &'p ProviderGroup) -> Vec<&'p ProviderId> {
+        preset
+            .providers
+            .iter()
+            .filter(|p| !self.disabled.contains(p))
+            .collect()
+    }
+}
+
+pub fn prompt_provider_selection(
+    options: InteractiveProviderSelectionOptions<'_>,
+) -> Result<InteractiveProviderSelection> {
+    prompt_provider_selection_with(&mut TerminalPrompter, options)
+}
+
+/// The default `Prompter`: inline ratatui widgets on the current terminal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TerminalPrompter;
+
+impl Prompter for TerminalPrompter {
+    fn select(&mut self, message: &str, options: &[&str], default: usize) -> Result<usize> {
+        print_prompt_spacing();
+        select_step(message, options, default, true)
+    }
+
+    fn multi_select(&mut self, prompt: &ProviderPrompt<'_>) -> Result<Vec<ProviderId>> {
+        if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+            return Err(InstallerError::PromptError {
+                message: "interactive provider selection requires a TTY".to_string(),
+            });
+        }
+
+        if !prompt.fullscreen {
+            println!("{}", prompt.message);
+        }
+
+        let disabled = prompt.disabled.iter().copied().collect::<HashSet<_>>();
+        let mut state = UiState {
+            query: String::new(),
+            cursor: 0,
+            selected: prompt.defaults.iter().copied().collect(),
+            disabled,
+            scroll_offset: 0,
+            list_area: Rect::default(),
+            navigating: false,
+        };
+
+        let terminal = if prompt.fullscreen {
+            setup_fullscreen_terminal()
+        } else {
+            setup_terminal(VIEWPORT_HEIGHT)
+        };
+        let mut terminal = terminal.map_err(|err| InstallerError::PromptError {
+            message: err.to_string(),
+        })?;
+        crossterm::execute!(io::stdout(), EnableMouseCapture).ok();
+
+        let mut viewport_bottom = VIEWPORT_HEIGHT;
+        let result = run_ui_loop(&mut terminal, prompt, &mut state, &mut viewport_bottom);
+
+        crossterm::execute!(io::stdout(), DisableMouseCapture).ok();
+        if prompt.fullscreen {
+            crossterm::execute!(io::stdout(), LeaveAlternateScreen).ok();
+        }
+        restore_terminal(&mut terminal).map_err(|err| InstallerError::PromptError {
+            message: err.to_string(),
+        })?;
+        if prompt.fullscreen {
+            println!("{}", prompt.message);
+        } else {
+            move_cursor_below_viewport(viewport_bottom);
+        }
+        result
+    }
+
+    fn confirm(&mut self, message: &str, default: bool) -> Result<bool> {
+        print_prompt_spacing();
+        prompt_confirm(message, default)
+    }
+
+    fn text(&mut self, message: &str, default: Option<&str>) -> Result<String> {
+        print_prompt_spacing();
+        prompt_text(message, default, None)
+    }
+
+    fn progress(
+        &mut self,
+        targets: &[PlannedTarget],
+        install: &mut dyn FnMut(Option<Arc<dyn InstallObserver>>) -> Result<InstallResult>,
+    ) -> Result<InstallResult> {
+        if targets.is_empty() || !std::io::stdout().is_terminal() {
+            return install(None);
+        }
+        println!();
+        let height = targets.len() as u16;
+        let Ok(terminal) = Terminal::with_options(
+            CrosstermBackend::new(io::stdout()),
+            TerminalOptions {
+                viewport: Viewport::Inline(height),
+            },
+        ) else {
+            return install(None);
+        };
+
+        let view = Arc::new(ProgressView(Mutex::new(ProgressState {
+            terminal,
+            rows: targets
+                .iter()
+                .map(|target| ProgressRow {
+                    provider: target.requested_provider,
+                    destination: target.destination.clone(),
+                    status: RowStatus::Pending,
+                })
+                .collect(),
+            current: None,
+            started: Instant::now(),
+            bottom: height,
+        })));
+        view.lock().draw();
+        let result = install(Some(view.clone()));
+
+        let mut state = view.lock();
+        state.finish(result.is_ok());
+        state.draw();
+        move_cursor_below_viewport(state.bottom);
+        result
+    }
+}
+
+// ── Install progress ─────────────────────────────────────────────────────────
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowStatus {
+    Pending,
+    Running,
+    Done,
+    Skipped(SkipReason),
+    Failed,
+}
+
+struct ProgressRow {
+    provider: ProviderId,
+    destination: PathBuf,
+    status: RowStatus,
+}
+
+struct ProgressState {
+    terminal: Terminal<CrosstermBackend<std::io::Stdout>>,
+    rows: Vec<ProgressRow>,
+    current: Option<usize>,
+    started: Instant,
+    bottom: u16,
+}
+
+/// One line per planned destination, redrawn as `install()` reports events.
+struct ProgressView(Mutex<ProgressState>);
+
+impl ProgressView {
+    fn lock(&self) -> std::sync::MutexGuard<'_, ProgressState> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl InstallObserver for ProgressView {
+    fn on_event(&self, event: &InstallEvent) {
+        let mut state = self.lock();
+        match event {
+            InstallEvent::ProviderResolved { destination, .. } => {
+                let index = state
+                    .rows
+                    .iter()
+                    .position(|r| &r.destination == destination);
+                state.advance(index);
+            }
+            InstallEvent::Copying { provider, .. } if state.current.is_none() => {
+                let index = state.rows.iter().position(|r| r.provider == *provider);
+                state.advance(index);
+            }
+            InstallEvent::Skipped {
+                destination,
+                reason,
+                ..
+            } => {
+                if let Some(row) = state
+                    .rows
+                    .iter_mut()
+                    .find(|r| &r.destination == destination)
+                {
+                    row.status = RowStatus::Skipped(*reason);
+                }
+            }
+            InstallEvent::Completed { .. } => state.finish(true),
+            _ => {}
+        }
+        state.draw();
+    }
+}
+
+impl ProgressState {
+    /// Marks the running row done and starts `next`.
+    fn advance(&mut self, next: Option<usize>) {
+        if let Some(current) = self.current {
+            if self.rows[current].status == RowStatus::Running {
+                self.rows[current].status = RowStatus::Done;
+            }
+        }
+        if let Some(next) = next {
+            if self.rows[next].status == RowStatus::Pending {
+                self.rows[next].status = RowStatus::Running;
+            }
+        }
+        self.current = next.or(self.current);
+    }
+
+    fn finish(&mut self, ok: bool) {
+        for row in &mut self.rows {
+            match row.status {
+                RowStatus::Running if !ok => row.status = RowStatus::Failed,
+                RowStatus::Running | RowStatus::Pending if ok => row.status = RowStatus::Done,
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&mut self) {
+        let frame_index = (self.started.elapsed().as_millis() / 80) as usize % SPINNER.len();
+        let lines = self
+            .rows
+            .iter()
+            .map(|row| {
+                let (marker, style, note) = match row.status {
+                    RowStatus::Pending => ("·", theme::dim(), ""),
+                    RowStatus::Running => (SPINNER[frame_index], theme::accent(), ""),
+                    RowStatus::Done => ("✓", theme::selected(), ""),
+                    RowStatus::Skipped(SkipReason::UpToDate) => {
+                        ("✓", theme::selected(), messages().up_to_date)
+                    }
+                    RowStatus::Skipped(_) => ("-", theme::dim(), messages().skipped),
+                    RowStatus::Failed => ("✗", theme::error(), ""),
+                };
+                let note = if note.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({note})")
+                };
+                Line::from(vec![
+                    Span::styled(format!("{marker} "), style),
+                    Span::raw(provider_display_name(row.provider)),
+                    Span::styled(
+                        format!(" {}{note}", row.destination.display()),
+                        theme::dim(),
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+        if let Ok(completed) = self
+            .terminal
+            .draw(|frame| frame.render_widget(Paragraph::new(lines), frame.area()))
+        {
+            self.bottom = completed.area.bottom();
+        }
+    }
+}
+
+fn run_ui_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    prompt: &ProviderPrompt<'_>,
+    state: &mut UiState,
+    viewport_bottom: &mut u16,
+) -> Result<Vec<ProviderId>> {
+    let universal_locked = prompt.locked;
+    let presets = prompt.groups;
+    let selectable = prompt.choices;
+    loop {
+        let filtered = filtered_items(presets, selectable, prompt.detected, &state.query);
+        if state.cursor >= filtered.len() && !filtered.is_empty() {
+            state.cursor = filtered.len() - 1;
+        }
+        if let Some(PickerRow::Heading(_)) = filtered.get(state.cursor) {
+            state.move_cursor(&filtered, 0);
+        }
+
+        let term_width = terminal.size().map(|s| s.width).unwrap_or(80);
+        let viewport_height = terminal
+            .size()
+            .map(|s| {
+                if prompt.fullscreen {
+                    s.height
+                } else {
+                    s.height.min(VIEWPORT_HEIGHT)
+                }
+            })
+            .unwrap_or(VIEWPORT_HEIGHT);
+        let viewport_area = Rect::new(0, 0, term_width, viewport_height);
+        let picker_area = picker_areas(viewport_area, prompt).picker;
+        let list_height = compute_layout(picker_area, universal_locked.len())[6].height as usize;
+        adjust_scroll(state, filtered.len(), list_height);
+
+        let completed = terminal
+            .draw(|frame| draw_ui(frame, prompt, &filtered, state))
+            .map_err(|err| InstallerError::PromptError {
+                message: err.to_string(),
+            })?;
+        *viewport_bottom = completed.area.bottom();
+        let picker_area = picker_areas(completed.area, prompt).picker;
+        state.list_area = compute_layout(picker_area, universal_locked.len())[6];
+
+        let event = event::read().map_err(|err| InstallerError::PromptError {
+            message: err.to_string(),
+        })?;
+
+        if let Event::Mouse(mouse) = event {
+            handle_picker_mouse(state, mouse, presets, &filtered);
+            continue;
+        }
+
+        let Event::Key(key) = event else {
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        let page = list_height.saturating_sub(1).max(1) as isize;
+        if let Some(result) = handle_picker_key(state, key, prompt, &filtered, page) {
+            return result;
+        }
+    }
+}
+
+/// The wheel moves the cursor; a left click moves it to the row and toggles it.
+fn handle_picker_mouse(
+    state: &mut UiState,
+    mouse: MouseEvent,
+    presets: &[ProviderGroup],
+    filtered: &[PickerRow],
+) {
+    match mouse.kind {
+        MouseEventKind::ScrollUp => state.move_cursor(filtered, -1),
+        MouseEventKind::ScrollDown => state.move_cursor(filtered, 1),
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(index) = state.row_at(filtered.len(), mouse.row) {
+                if !matches!(filtered[index], PickerRow::Heading(_)) {
+                    state.cursor = index;
+                    state.toggle(presets, filtered[index]);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Applies one key press to the picker; `Some` once the prompt is answered or cancelled.
+fn handle_picker_key(
+    state: &mut UiState,
+    key: KeyEvent,
+    prompt: &ProviderPrompt<'_>,
+    filtered: &[PickerRow],
+    page: isize,
+) -> Option<Result<Vec<ProviderId>>> {
+    let presets = prompt.groups;
+    let selectable = prompt.choices;
+    match key.code {
+        KeyCode::Up => state.move_cursor(filtered, -1),
+        KeyCode::Down => state.move_cursor(filtered, 1),
+        KeyCode::PageUp => state.move_cursor(filtered, -page),
+        KeyCode::PageDown => state.move_cursor(filtered, page),
+        KeyCode::Home => state.move_cursor(filtered, -(filtered.len() as isize)),
+        KeyCode::End => state.move_cursor(filtered, filtered.len() as isize),
+        KeyCode::Char(' ') => {
+            if let Some(&row) = filtered.get(state.cursor) {
+                state.toggle(presets, row);
+            }
+        }
+        KeyCode::Tab => state.navigating = !state.navigating,
+        KeyCode::Backspace => {
+            state.query.pop();
+            state.cursor = 0;
+            state.scroll_offset = 0;
+        }
+        KeyCode::Enter => {
+            if state.selected.is_empty() && prompt.locked.is_empty() {
+                return None;
+            }
+            let mut selected = state.selected.iter().copied().collect::<Vec<_>>();
+            selected.sort_by_key(|p| p.as_str());
+            return Some(Ok(selected));
+        }
+        KeyCode::Esc => return Some(Err(InstallerError::PromptCancelled)),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return Some(Err(InstallerError::PromptCancelled))
+        }
+        KeyCode::Char(c) if state.navigating => {
+            let enabled = selectable.iter().filter(|p| !state.disabled.contains(p));
+            match c {
+                'j' => state.move_cursor(filtered, 1),
+                'k' => state.move_cursor(filtered, -1),
+                'g' => state.move_cursor(filtered, -(filtered.len() as isize)),
+                'G' => state.move_cursor(filtered, filtered.len() as isize),
+                'a' => state.selected.extend(enabled),
+                'n' => state.selected.clear(),
+                'i' => {
+                    for &provider in enabled {
+                        if !state.selected.remove(&provider) {
+                            state.selected.insert(provider);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.query.push(c);
+            state.cursor = 0;
+            state.scroll_offset = 0;
+        }
+        _ => {}
+    }
+    None
+}
+
+fn make_divider(label: &str, suffix: &str, width: u16) -> Line<'static> {
+    let prefix = "── ";
+    let tail = if suffix.is_empty() {
+        " ".to_string()
+    } else {
+        format!(" {} ", suffix)
+    };
+    let used = prefix.chars().count() + label.chars().count() + tail.chars().count();
+    let remaining = (width as usize).saturating_sub(used);
+    let fill = "─".repeat(remaining);
+
+    Line::from(vec![
+        Span::styled(prefix.to_string(), theme::dim()),
+        Span::styled(
+            label.to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("{}{}", tail, fill), theme::dim()),
+    ])
+}
+
+fn adjust_scroll(state: &mut UiState, total_items: usize, visible_height: usize) {
+    if total_items == 0 || visible_height == 0 {
+        state.scroll_offset = 0;
+        return;
+    }
+
+    let max_scroll = total_items.saturating_sub(1);
+    state.scroll_offset = state.scroll_offset.min(max_scroll);
+
+    if state.cursor < state.scroll_offset {
+        state.scroll_offset = state.cursor;
+    }
+
+    loop {
+        let top_lines = if state.scroll_offset > 0 { 1usize } else { 0 };
+        let items_space = visible_height.saturating_sub(top_lines);
+        let remaining = total_items - state.scroll_offset;
+        let bottom_lines = if remaining > items_space { 1usize } else { 0 };
+        let visible_count = items_space.saturating_sub(bottom_lines).max(1);
+
+        if state.cursor < state.scroll_offset + visible_count {
+            break;
+        }
+
+        state.scroll_offset += 1;
+        if state.scroll_offset >= total_items {
+            state.scroll_offset = total_items.saturating_sub(1);
+            break;
+        }
+    }
+}
+
+fn draw_ui(
+    frame: &mut ratatui::Frame,
+    prompt: &ProviderPrompt<'_>,
+    filtered: &[PickerRow],
+    state: &UiState,
+) {
+    let areas = picker_areas(frame.area(), prompt);
+    if let Some(title) = areas.title {
+        frame.render_widget(Paragraph::new(prompt.message), title);
+    }
+    let area = areas.picker;
+    let width = area.width;
+    let chunks = compute_layout(area, prompt.locked.len());
+
+    render_locked(frame, chunks[0], prompt.locked, prompt.locked_label, width);
+    render_additional_header(frame, chunks[2], width);
+    render_search(frame, chunks[3], state);
+    render_instructions(frame, chunks[4]);
+    render_selectable(frame, chunks[6], prompt, filtered, state);
+    if let Some(details) = areas.details {
+        let cursor = match filtered.get(state.cursor) {
+            Some(PickerRow::Provider(provider)) => Some(*provider),
+            _ => None,
+        };
+        render_details(frame, details, prompt, cursor);
+    }
+
+    let summary = selected_summary(prompt.locked, &state.selected);
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled(
+            messages().selected,
+            theme::accent().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(summary),
+    ]));
+    frame.render_widget(footer, chunks[8]);
+}
+
+struct PickerAreas {
+    /// The prompt message, which the alternate screen would otherwise hide.
+    title: Option<Rect>,
+    picker: Rect,
+    /// Skill preview and, full-screen, detection details of the provider under the cursor.
+    details: Option<Rect>,
+}
+
+/// Details to the side on wide terminals, under the picker otherwise.
+fn picker_areas(area: Rect, prompt: &ProviderPrompt<'_>) -> PickerAreas {
+    let (title, area) = if prompt.fullscreen {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(1)])
+            .split(area);
+        (Some(rows[0]), rows[1])
+    } else {
+        (None, area)
+    };
+    if prompt.preview.is_none() && !prompt.fullscreen {
+        return PickerAreas {
+            title,
+            picker: area,
+            details: None,
+        };
+    }
+    let chunks = if area.width >= PREVIEW_SIDE_MIN_WIDTH {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(40), Constraint::Percentage(40)])
+            .split(area)
+    } else {
+        let details = if prompt.fullscreen {
+            Constraint::Percentage(40)
+        } else {
+            Constraint::Length(PREVIEW_BOTTOM_HEIGHT)
+        };
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(10), details])
+            .split(area)
+    };
+    PickerAreas {
+        title,
+        picker: chunks[0],
+        details: Some(chunks[1]),
+    }
+}
+
+fn render_details(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    prompt: &ProviderPrompt<'_>,
+    cursor: Option<ProviderId>,
+) {
+    let side = area.height > PREVIEW_BOTTOM_HEIGHT;
+    let mut lines = Vec::new();
+    if let Some(skill) = prompt.preview {
+        let title = match &skill.version {
+            Some(version) => format!("{} {version}", skill.name),
+            None => skill.name.clone(),
+        };
+        lines.push(Line::from(Span::styled(
+            title,
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        if let Some(description) = &skill.description {
+            lines.push(Line::from(description.trim().to_string()));
+        }
+    }
+
+    if let (true, Some(provider)) = (prompt.fullscreen, cursor) {
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(Span::styled(
+            provider_display_name(provider),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            fill(
+                messages().installs_to,
+                &[("path", &project_path_for(provider))],
+            ),
+            theme::dim(),
+        )));
+        match prompt.detected.iter().find(|d| d.provider == provider) {
+            Some(detected) => {
+                let confidence = detected.confidence.as_str();
+                let summary = match &detected.version {
+                    Some(version) => fill(
+                        messages().detected_version,
+                        &[("confidence", &confidence), ("version", version)],
+                    ),
+                    None => fill(messages().detected, &[("confidence", &confidence)]),
+                };
+                lines.push(Line::from(format!("{summary}: {}", detected.reason)));
+                for evidence in &detected.evidence {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}: {}", evidence.scope.as_str(), evidence.path.display()),
+                        theme::dim(),
+                    )));
+                }
+            }
+            None => lines.push(Line::from(Span::styled(
+                messages().not_detected,
+                theme::dim(),
+            ))),
+        }
+    }
+
+    if let (true, Some(skill)) = (side, prompt.preview) {
+        let body_lines = if prompt.fullscreen {
+            usize::MAX
+        } else {
+            PREVIEW_BODY_LINES
+        };
+        lines.push(Line::default());
+        lines.extend(
+            skill
+                .body
+                .lines()
+                .skip_while(|line| line.trim().is_empty())
+                .take(body_lines)
+                .map(|line| Line::from(Span::styled(line.to_string(), theme::dim()))),
+        );
+    }
+
+    let block = if side {
+        Block::default().borders(Borders::LEFT)
+    } else {
+        Block::default().borders(Borders::TOP)
+    }
+    .border_style(theme::dim());
+    let paragraph = Paragraph::new(lines)
+        .block(block.padding(Padding::horizontal(1)))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+fn render_locked(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    universal_locked: &[ProviderId],
+    locked_label: &str,
+    width: u16,
+) {
+    let lines = if universal_locked.is_empty() {
+        let label = format!("{locked_label} — {}", messages().none);
+        let prefix = "── ";
+        let used = prefix.chars().count() + label.chars().count() + 1;
+        let remaining = (width as usize).saturating_sub(used);
+        let fill = "─".repeat(remaining);
+        vec![Line::from(Span::styled(
+            format!("{}{} {}", prefix, label, fill),
+            theme::dim(),
+        ))]
+    } else {
+        let mut out = Vec::with_capacity(universal_locked.len() + 1);
+        let suffix = format!("— {}", messages().always_included);
+        out.push(make_divider(locked_label, &suffix, width));
+        for provider in universal_locked {
+            out.push(Line::from(vec![
+                Span::styled("  ● ", theme::selected()),
+                Span::raw(provider_display_name(*provider)),
+            ]));
+        }
+        out
+    };
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_additional_header(frame: &mut ratatui::Frame, area: Rect, width: u16) {
+    let divider = make_divider(messages().additional_agents, "", width);
+    frame.render_widget(Paragraph::new(vec![divider]), area);
+}
+
+fn render_search(frame: &mut ratatui::Frame, area: Rect, state: &UiState) {
+    // No cursor while Tab has letters running commands instead of typing.
+    let cursor = if state.navigating { "" } else { "█" };
+    let search = Paragraph::new(Line::from(vec![
+        Span::styled(messages().search, theme::dim()),
+        Span::raw(&state.query),
+        Span::styled(cursor, theme::dim()),
+    ]));
+    frame.render_widget(search, area);
+}
+
+fn render_instructions(frame: &mut ratatui::Frame, area: Rect) {
+    let hint = Paragraph::new(Line::from(Span::styled(
+        messages().picker_hint,
+        theme::dim(),
+    )));
+    frame.render_widget(hint, area);
+}
+
+fn render_selectable(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    prompt: &ProviderPrompt<'_>,
+    filtered: &[PickerRow],
+    state: &UiState,
+) {
+    let presets = prompt.groups;
+    let height = area.height as usize;
+    let mut lines = Vec::new();
+
+    if filtered.is_empty() {
+        lines.push(Line::from(Span::styled(
+            messages().no_matches,
+            theme::dim(),
+        )));
+    } else {
+        let total = filtered.len();
+        let offset = state.scroll_offset;
+        let (has_top, visible) = visible_rows(total, offset, height);
+
+        if has_top {
+            lines.push(Line::from(Span::styled(
+                fill(messages().more_above, &[("count", &offset)]),
+                theme::dim(),
+            )));
+        }
+
+        let end = visible.end;
+        for (i, &row) in filtered.iter().enumerate().take(end).skip(offset) {
+            let is_cursor = i == state.cursor;
+            let provider = match row {
+                PickerRow::Provider(provider) => provider,
+                PickerRow::Preset(index) => {
+                    lines.push(preset_line(&presets[index], is_cursor, state));
+                    continue;
+                }
+                PickerRow::Heading(title) => {
+                    lines.push(Line::from(Span::styled(
+                        title,
+                        theme::accent().add_modifier(Modifier::BOLD),
+                    )));
+                    continue;
+                }
+            };
+            let prefix = if is_cursor { ">" } else { " " };
+            if state.disabled.contains(&provider) {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "{} - {} ({})",
+                        prefix,
+                        provider_display_name(provider),
+                        messages().unsupported
+                    ),
+                    theme::dim(),
+                )));
+                continue;
+            }
+            let is_selected = state.selected.contains(&provider);
+
+            let marker = if is_selected { "●" } else { "○" };
+            let path = project_path_for(provider);
+
+            let mut spans = vec![
+                Span::styled(
+                    format!("{} {} ", prefix, marker),
+                    if is_selected {
+                        theme::selected()
+                    } else {
+                        theme::dim()
+                    },
+                ),
+                Span::styled(provider_display_name(provider), Style::default()),
+                Span::styled(format!(" ({})", path), theme::dim()),
+            ];
+            if let Some(reason) = detection_reason(prompt.detected, provider) {
+                spans.push(Span::styled(format!("  {reason}"), theme::dim()));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        if end < total {
+            let below = total - end;
+            lines.push(Line::from(Span::styled(
+                fill(messages().more_below, &[("count", &below)]),
+                theme::dim(),
+            )));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Whether the "↑ more" marker shows, and which rows fit below it above any "↓ more" marker.
+fn visible_rows(total: usize, offset: usize, height: usize) -> (bool, std::ops::Range<usize>) {
+    let has_top = offset > 0;
+    let items_space = height.saturating_sub(usize::from(has_top));
+    let has_bottom = total.saturating_sub(offset) > items_space;
+    let visible_count = items_space.saturating_sub(usize::from(has_bottom)).max(1);
+    (has_top, offset..(offset + visible_count).min(total))
+}
+
+fn preset_line(preset: &ProviderGroup, is_cursor: bool, state: &UiState) -> Line<'static> {
+    let members = state.preset_members(preset);
+    let chosen = members
+        .iter()
+        .filter(|p| state.selected.contains(p))
+        .count();
+    let marker = if !members.is_empty() && chosen == members.len() {
+        "●"
+    } else if chosen > 0 {
+        "◐"
+    } else {
+        "○"
+    };
+    let prefix = if is_cursor { ">" } else { " " };
+    let members = preset
+        .providers
+        .iter()
+        .map(|p| provider_display_name(*p))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Line::from(vec![
+        Span::styled(
+            format!("{} {} ", prefix, marker),
+            if chosen > 0 {
+                theme::selected()
+            } else {
+                theme::dim()
+            },
+        ),
+        Span::styled(
+            format!("@{}", preset.name),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(" ({})", members), theme::dim()),
+    ])
+}
+
+fn selected_summary(universal_locked: &[ProviderId], selected: &HashSet<ProviderId>) -> String {
+    let mut names = universal_locked
+        .iter()
+        .map(|p| provider_display_name(*p))
+        .collect::<Vec<_>>();
+
+    let mut selected_names = selected
+        .iter()
+        .map(|p| provider_display_name(*p))
+        .collect::<Vec<_>>();
+    selected_names.sort();
+    names.extend(selected_names);
+
+    if names.is_empty() {
+        return messages().nothing_selected.to_string();
+    }
+    if names.len() <= 4 {
+        return names.join(", ");
+    }
+    let more = fill(messages().and_more, &[("count", &(names.len() - 4))]);
+    format!("{} {more}", names[..4].join(", "))
+}
+
+const VIEWPORT_HEIGHT: u16 = 24;
+const PREVIEW_SIDE_MIN_WIDTH: u16 = 100;
+const PREVIEW_BOTTOM_HEIGHT: u16 = 4;
+const PREVIEW_BODY_LINES: usize = 12;
+
+fn compute_layout(area: Rect, locked_count: usize) -> std::rc::Rc<[Rect]> {
+    let locked_len = if locked_count == 0 {
+        1
+    } else {
+        1 + locked_count as u16
+    };
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(locked_len), // 0: locked section
+            Constraint::Length(1),          // 1: spacer
+            Constraint::Length(1),          // 2: additional agents header
+            Constraint::Length(1),          // 3: search
+            Constraint::Length(1),          // 4: instructions
+            Constraint::Length(1),          // 5: spacer
+            Constraint::Min(1),             // 6: selectable list
+            Constraint::Length(1),          // 7: spacer
+            Constraint::Length(1),          // 8: footer
+        ])
+        .split(area)
+}
+
+fn setup_terminal(height: u16) -> io::Result<Terminal<CrosstermBackend<std::io::Stdout>>> {
+    enable_raw_mode()?;
+    let backend = CrosstermBackend::new(io::stdout());
+    Terminal::with_options(
+        backend,
+        TerminalOptions {
+            viewport: Viewport::Inline(height),
+        },
+    )
+}
+
+fn setup_fullscreen_terminal() -> io::Result<Terminal<CrosstermBackend<std::io::Stdout>>> {
+    enable_raw_mode()?;
+    crossterm::execute!(io::stdout(), EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(io::stdout());
+    Terminal::with_options(
+        backend,
+        TerminalOptions {
+            viewport: Viewport::Fullscreen,
+        },
+    )
+}
+
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> io::Result<()> {
+    disable_raw_mode()?;
+    terminal.show_cursor()
+}
+
+fn move_cursor_below_viewport(viewport_bottom: u16) {
+    crossterm::execute!(
+        io::stdout(),
+        crossterm::cursor::MoveTo(0, viewport_bottom),
+        crossterm::cursor::MoveToNextLine(1)
+    )
+    .ok();
+}
+
+// ── Text and confirm prompts ─────────────────────────────────────────────────
+
+type Validator = dyn Fn(&str) -> std::result::Result<(), String>;
+
+/// Reads one line of input; an empty answer takes `default` when there is one.
+///
+/// `validator` rejects an answer with a message, shown before asking again.
+pub fn prompt_text(
+    message: &str,
+    default: Option<&str>,
+    validator: Option<&Validator>,
+) -> Result<String> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(InstallerError::PromptError {
+            message: "interactive input requires a TTY".to_string(),
+        });
+    }
+
+    loop {
+        match default {
+            Some(default) => print!("{message} ({default}): "),
+            None => print!("{message}: "),
+        }
+        io::Write::flush(&mut io::stdout()).map_err(|err| InstallerError::PromptError {
+            message: err.to_string(),
+        })?;
+
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .map_err(|err| InstallerError::PromptError {
+                message: err.to_string(),
+            })?;
+        if read == 0 {
+            return Err(InstallerError::PromptCancelled);
+        }
+        let answer = match (line.trim(), default) {
+            ("", Some(default)) => default,
+            ("", None) => continue,
+            (answer, _) => answer,
+        };
+        match validator.map_or(Ok(()), |validate| validate(answer)) {
+            Ok(()) => return Ok(answer.to_string()),
+            Err(problem) => println!("  {problem}"),
+        }
+    }
+}
+
+/// Yes/No select; `default` picks which one Enter takes.
+pub fn prompt_confirm(message: &str, default: bool) -> Result<bool> {
+    let text = messages();
+    Ok(prompt_select(message, &[text.yes, text.no], if default { 0 } else { 1 })? == 0)
+}
+
+// ── Generic single-select prompt ─────────────────────────────────────────────
+
+pub fn prompt_select(message: &str, options: &[&str], default: usize) -> Result<usize> {
+    select_step(message, options, default, false)
+}
+
+/// `prompt_select`; with `allow_back`, Left and Esc return `PromptBack` instead of cancelling.
+fn select_step(message: &str, options: &[&str], default: usize, allow_back: bool) -> Result<usize> {
+    if options.is_empty() {
+        return Err(InstallerError::PromptError {
+            message: "no options provided".to_string(),
+        });
+    }
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(InstallerError::PromptError {
+            message: "interactive selection requires a TTY".to_string(),
+        });
+    }
+
+    println!("{}", message);
+
+    let mut cursor = default.min(options.len() - 1);
+    // hint + options
+    let viewport_height = 1 + options.len() as u16;
+
+    let mut terminal =
+        setup_terminal(viewport_height).map_err(|err| InstallerError::PromptError {
+            message: err.to_string(),
+        })?;
+
+    let mut viewport_bottom = viewport_height;
+    let result = run_select_loop(
+        &mut terminal,
+        options,
+        allow_back,
+        &mut cursor,
+        &mut viewport_bottom,
+    );
+
+    restore_terminal(&mut terminal).map_err(|err| InstallerError::PromptError {
+        message: err.to_string(),
+    })?;
+    move_cursor_below_viewport(viewport_bottom);
+
+    result
+}
+
+fn run_select_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    options: &[&str],
+    allow_back: bool,
+    cursor: &mut usize,
+    viewport_bottom: &mut u16,
+) -> Result<usize> {
+    loop {
+        let cur = *cursor;
+        let completed = terminal
+            .draw(|frame| draw_select(frame, options, cur, allow_back))
+            .map_err(|err| InstallerError::PromptError {
+                message: err.to_string(),
+            })?;
+        *viewport_bottom = completed.area.bottom();
+
+        let event = event::read().map_err(|err| InstallerError::PromptError {
+            message: err.to_string(),
+        })?;
+
+        let Event::Key(key) = event else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        let page = usize::from(completed.area.height.saturating_sub(1)).max(1);
+        if let Some(result) = handle_select_key(cursor, key, options.len(), page, allow_back) {
+            return result;
+        }
+    }
+}
+
+/// Applies one key press to a single-choice list of `len` options; `Some` once answered.
+fn handle_select_key(
+    cursor: &mut usize,
+    key: KeyEvent,
+    len: usize,
+    page: usize,
+    allow_back: bool,
+) -> Option<Result<usize>> {
+    let last = len.saturating_sub(1);
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => *cursor = cursor.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => *cursor = (*cursor + 1).min(last),
+        KeyCode::PageUp => *cursor = cursor.saturating_sub(page),
+        KeyCode::PageDown => *cursor = (*cursor + page).min(last),
+        KeyCode::Home | KeyCode::Char('g') => *cursor = 0,
+        KeyCode::End | KeyCode::Char('G') => *cursor = last,
+        KeyCode::Enter => return Some(Ok(*cursor)),
+        KeyCode::Left | KeyCode::Esc if allow_back => return Some(Err(InstallerError::PromptBack)),
+        KeyCode::Esc => return Some(Err(InstallerError::PromptCancelled)),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return Some(Err(InstallerError::PromptCancelled))
+        }
+        _ => {}
+    }
+    None
+}
+
+fn draw_select(frame: &mut ratatui::Frame, options: &[&str], cursor: usize, allow_back: bool) {
+    let size = frame.area();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // instructions
+            Constraint::Min(1),    // options
+        ])
+        .split(size);
+
+    let hint = if allow_back {
+        messages().select_back_hint
+    } else {
+        messages().select_hint
+    };
+    let hint = Paragraph::new(Line::from(Span::styled(hint, theme::dim())));
+    frame.render_widget(hint, chunks[0]);
+
+    let mut lines = Vec::with_capacity(options.len());
+    for (idx, label) in options.iter().enumerate() {
+        let is_cursor = idx == cursor;
+        let marker = if is_cursor { "●" } else { "○" };
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", marker),
+                if is_cursor {
+                    theme::selected()
+                } else {
+                    theme::dim()
+                },
+            ),
+            Span::styled(*label, Style::default()),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+}
+
+// ── Provider helpers ─────────────────────────────────────────────────────────
+
+/// Presets, then providers split into detected and other sections when both have matches.
+fn filtered_items(
+    presets: &[ProviderGroup],
+    items: &[ProviderId],
+    detected: &[DetectedProvider],
+    query: &str,
+) -> Vec<PickerRow> {
+    let q = query.trim().to_lowercase();
+    let q = q.trim_start_matches('@');
+    let mut rows = presets
+        .iter()
+        .enumerate()
+        .filter(|(_, g)| g.name.to_lowercase().contains(q))
+        .map(|(index, _)| PickerRow::Preset(index))
+        .collect::<Vec<_>>();
+    let (found, others): (Vec<_>, Vec<_>) = items
+        .iter()
+        .copied()
+        .filter(|p| {
+            q.is_empty()
+                || provider_display_name(*p).to_lowercase().contains(q)
+                || p.as_str().contains(q)
+        })
+        .partition(|p| detected.iter().any(|d| d.provider == *p));
+    if !found.is_empty() {
+        rows.push(PickerRow::Heading(messages().detected_heading));
+        rows.extend(found.into_iter().map(PickerRow::Provider));
+        if !others.is_empty() {
+            rows.push(PickerRow::Heading(messages().others_heading));
+        }
+    }
+    rows.extend(others.into_iter().map(PickerRow::Provider));
+    rows
+}
+
+fn print_prompt_spacing() {
+    // Two-line separation between interactive steps.
+    println!();
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHOICES: &[ProviderId] = &[
+        ProviderId::Aider,
+        ProviderId::ClaudeCode,
+        ProviderId::GeminiCli,
+    ];
+
+    fn prompt<'a>(defaults: &'a [ProviderId]) -> ProviderPrompt<'a> {
+        ProviderPrompt {
+            message: "",
+            locked: &[],
+            locked_label: "",
+            choices: CHOICES,
+            groups: &[],
+            defaults,
+            disabled: &[],
+            preview: None,
+            detected: &[],
+            fullscreen: false,
+        }
+    }
+
+    fn state(prompt: &ProviderPrompt<'_>) -> UiState {
+        UiState {
+            query: String::new(),
+            cursor: 0,
+            selected: prompt.defaults.iter().copied().collect(),
+            disabled: HashSet::new(),
+            scroll_offset: 0,
+            list_area: Rect::default(),
+            navigating: false,
+        }
+    }
+
+    fn rows(prompt: &ProviderPrompt<'_>, state: &UiState) -> Vec<PickerRow> {
+        filtered_items(prompt.groups, prompt.choices, prompt.detected, &state.query)
+    }
+
+    /// Feeds `keys` to the picker the way the event loop does, refiltering between presses.
+    fn press(
+        state: &mut UiState,
+        prompt: &ProviderPrompt<'_>,
+        keys: impl IntoIterator<Item = KeyCode>,
+    ) -> Option<Result<Vec<ProviderId>>> {
+        for key in keys {
+            let filtered = rows(prompt, state);
+            if let Some(result) =
+                handle_picker_key(state, KeyEvent::from(key), prompt, &filtered, 5)
+            {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    fn typed(text: &str) -> impl Iterator<Item = KeyCode> + '_ {
+        text.chars().map(KeyCode::Char)
+    }
+
+    /// Draws the picker on a `width`×`height` test terminal.
+    fn draw(
+        prompt: &ProviderPrompt<'_>,
+        state: &UiState,
+        width: u16,
+        height: u16,
+    ) -> ratatui::buffer::Buffer {
+        let mut terminal =
+            Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        let filtered = rows(prompt, state);
+        terminal
+            .draw(|frame| draw_ui(frame, prompt, &filtered, state))
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    /// The drawn picker's rows as text.
+    fn screen(
+        prompt: &ProviderPrompt<'_>,
+        state: &UiState,
+        width: u16,
+        height: u16,
+    ) -> Vec<String> {
+        draw(prompt, state, width, height)
+            .content()
+            .chunks(usize::from(width))
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect()
+    }
+
+    fn shows(screen: &[String], text: &str) -> bool {
+        screen.iter().any(|row| row.contains(text))
+    }
+
+    fn detected_claude() -> Vec<DetectedProvider> {
+        vec![DetectedProvider {
+            provider: ProviderId::ClaudeCode,
+            reason: "found ~/.claude".to_string(),
+            version: None,
+            confidence: crate::types::DetectionConfidence::High,
+            evidence: Vec::new(),
+        }]
+    }
+
+    fn mouse(kind: MouseEventKind, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: 2,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn typing_gemini_into_an_empty_query_filters_the_list() {
+        let prompt = prompt(&[]);
+        let mut state = state(&prompt);
+        assert!(press(&mut state, &prompt, typed("gemini")).is_none());
+        assert_eq!(state.query, "gemini");
+        assert_eq!(
+            rows(&prompt, &state),
+            [PickerRow::Provider(ProviderId::GeminiCli)]
+        );
+    }
+
+    #[test]
+    fn navigation_mode_moves_vim_style() {
+        let prompt = prompt(&[]);
+        let mut state = state(&prompt);
+        press(&mut state, &prompt, [KeyCode::Tab, KeyCode::Char('G')]);
+        assert_eq!(state.cursor, CHOICES.len() - 1);
+        press(&mut state, &prompt, [KeyCode::Char('k')]);
+        assert_eq!(state.cursor, CHOICES.len() - 2);
+        press(
+            &mut state,
+            &prompt,
+            [KeyCode::Char('g'), KeyCode::Char('j')],
+        );
+        assert_eq!(state.cursor, 1);
+        assert!(state.query.is_empty());
+    }
+
+    #[test]
+    fn letters_search_until_tab_switches_to_commands() {
+        let prompt = prompt(&[ProviderId::ClaudeCode]);
+        let mut state = state(&prompt);
+        assert!(press(&mut state, &prompt, typed("aider")).is_none());
+        assert_eq!(state.query, "aider");
+        assert_eq!(
+            rows(&prompt, &state),
+            [PickerRow::Provider(ProviderId::Aider)]
+        );
+        assert_eq!(
+            state.selected,
+            HashSet::from([ProviderId::ClaudeCode]),
+            "typing must not run commands"
+        );
+
+        let mut state = self::state(&prompt);
+        press(&mut state, &prompt, [KeyCode::Tab, KeyCode::Char('a')]);
+        assert_eq!(state.selected.len(), CHOICES.len());
+        press(&mut state, &prompt, [KeyCode::Char('n')]);
+        assert!(state.selected.is_empty());
+        press(&mut state, &prompt, [KeyCode::Char('i')]);
+        assert_eq!(state.selected.len(), CHOICES.len());
+        assert!(state.query.is_empty());
+
+        press(&mut state, &prompt, [KeyCode::Tab, KeyCode::Char('n')]);
+        assert_eq!(state.query, "n");
+        assert_eq!(state.selected.len(), CHOICES.len());
+    }
+
+    #[test]
+    fn preview_shows_the_skill_beside_or_below_the_picker() {
+        let skill = crate::parser::parse_skill_str(
+            "---\nname: demo-skill\ndescription: Says hello\nversion: \"1.2.0\"\n---\n\n# Demo\n\nFirst body line.\n",
+        )
+        .unwrap();
+        let prompt = ProviderPrompt {
+            preview: Some(&skill),
+            ..prompt(&[])
+        };
+        let state = state(&prompt);
+
+        let wide = screen(&prompt, &state, 120, 20);
+        let areas = picker_areas(Rect::new(0, 0, 120, 20), &prompt);
+        let details = areas.details.unwrap();
+        assert!(details.x > 0, "wide terminals put the preview to the side");
+        assert!(shows(&wide, "demo-skill 1.2.0"));
+        assert!(shows(&wide, "Says hello"));
+        assert!(shows(&wide, "First body line."));
+        assert!(shows(&wide, "Claude Code"));
+
+        let narrow = screen(&prompt, &state, 80, 24);
+        let details = picker_areas(Rect::new(0, 0, 80, 24), &prompt)
+            .details
+            .unwrap();
+        assert_eq!(
+            details.bottom(),
+            24,
+            "narrow terminals put the preview below"
+        );
+        assert!(shows(&narrow, "demo-skill 1.2.0"));
+        assert!(shows(&narrow, "Says hello"));
+        assert!(!shows(&narrow, "First body line."));
+
+        let plain = self::prompt(&[]);
+        assert!(picker_areas(Rect::new(0, 0, 120, 20), &plain)
+            .details
+            .is_none());
+        assert!(!shows(&screen(&plain, &state, 120, 20), "demo-skill"));
+    }
+
+    #[test]
+    fn clicking_a_row_toggles_it_and_the_wheel_moves_the_cursor() {
+        let detected = detected_claude();
+        let prompt = ProviderPrompt {
+            detected: &detected,
+            ..prompt(&[])
+        };
+        let mut state = state(&prompt);
+        let filtered = rows(&prompt, &state);
+        assert_eq!(
+            filtered,
+            [
+                PickerRow::Heading(messages().detected_heading),
+                PickerRow::Provider(ProviderId::ClaudeCode),
+                PickerRow::Heading(messages().others_heading),
+                PickerRow::Provider(ProviderId::Aider),
+                PickerRow::Provider(ProviderId::GeminiCli),
+            ]
+        );
+        state.cursor = 1;
+        state.list_area = Rect::new(0, 10, 40, 8);
+        let left = MouseEventKind::Down(MouseButton::Left);
+
+        handle_picker_mouse(&mut state, mouse(left, 13), &[], &filtered);
+        assert_eq!(state.cursor, 3);
+        assert_eq!(state.selected, HashSet::from([ProviderId::Aider]));
+        handle_picker_mouse(&mut state, mouse(left, 13), &[], &filtered);
+        assert!(state.selected.is_empty());
+
+        // Headings and clicks outside the list do nothing.
+        handle_picker_mouse(&mut state, mouse(left, 10), &[], &filtered);
+        handle_picker_mouse(&mut state, mouse(left, 9), &[], &filtered);
+        handle_picker_mouse(&mut state, mouse(left, 18), &[], &filtered);
+        assert_eq!(state.cursor, 3);
+        assert!(state.selected.is_empty());
+
+        handle_picker_mouse(
+            &mut state,
+            mouse(MouseEventKind::ScrollDown, 0),
+            &[],
+            &filtered,
+        );
+        assert_eq!(state.cursor, 4);
+        handle_picker_mouse(
+            &mut state,
+            mouse(MouseEventKind::ScrollDown, 0),
+            &[],
+            &filtered,
+        );
+        assert_eq!(state.cursor, 4);
+        handle_picker_mouse(
+            &mut state,
+            mouse(MouseEventKind::ScrollUp, 0),
+            &[],
+            &filtered,
+        );
+        handle_picker_mouse(
+            &mut state,
+            mouse(MouseEventKind::ScrollUp, 0),
+            &[],
+            &filtered,
+        );
+        assert_eq!(state.cursor, 1, "the wheel skips headings");
+        assert!(state.selected.is_empty());
+
+        // Past the first page, clicks map through the scroll offset.
+        state.list_area = Rect::new(0, 10, 40, 3);
+        state.scroll_offset = 3;
+        handle_picker_mouse(&mut state, mouse(left, 10), &[], &filtered);
+        assert!(
+            state.selected.is_empty(),
+            "the first line is the scroll marker"
+        );
+        handle_picker_mouse(&mut state, mouse(left, 12), &[], &filtered);
+        assert_eq!(state.cursor, 4);
+        assert_eq!(state.selected, HashSet::from([ProviderId::GeminiCli]));
+    }
+
+    #[test]
+    fn fullscreen_adds_a_title_and_details_for_the_cursor() {
+        let mut detected = detected_claude();
+        detected[0].version = Some("2.0.1".to_string());
+        detected[0].evidence = vec![crate::types::DetectionEvidence {
+            scope: crate::types::Scope::User,
+            path: PathBuf::from("/home/dev/.claude"),
+        }];
+        let prompt = ProviderPrompt {
+            message: "Pick providers",
+            detected: &detected,
+            fullscreen: true,
+            ..prompt(&[])
+        };
+        let mut state = state(&prompt);
+
+        let areas = picker_areas(Rect::new(0, 0, 120, 30), &prompt);
+        assert_eq!(areas.title, Some(Rect::new(0, 0, 120, 2)));
+        assert!(
+            areas.details.is_some(),
+            "details show even without a preview"
+        );
+        let inline = ProviderPrompt {
+            fullscreen: false,
+            ..prompt.clone()
+        };
+        let areas = picker_areas(Rect::new(0, 0, 120, 30), &inline);
+        assert!(areas.title.is_none() && areas.details.is_none());
+
+        state.cursor = 1;
+        let screen = screen(&prompt, &state, 120, 30);
+        let summary = fill(
+            messages().detected_version,
+            &[("confidence", &"high"), ("version", &"2.0.1")],
+        );
+        assert!(shows(&screen, "Pick providers"));
+        assert!(shows(&screen, &format!("{summary}:")));
+        assert!(
+            shows(&screen, "│ found ~/.claude"),
+            "the reason wraps in the details pane"
+        );
+        assert!(shows(&screen, "user: /home/dev/.claude"));
+        assert!(shows(
+            &screen,
+            &fill(
+                messages().installs_to,
+                &[("path", &project_path_for(ProviderId::ClaudeCode))]
+            )
+        ));
+        assert!(!shows(&screen, messages().not_detected));
+
+        state.cursor = 3;
+        let screen = self::screen(&prompt, &state, 120, 30);
+        assert!(shows(&screen, messages().not_detected));
+        assert!(!shows(&screen, "user: /home/dev/.claude"));
+    }
+
+    #[test]
+    fn detected_providers_show_their_reason_dimmed() {
+        theme::set_color_choice(crate::render::ColorChoice::Always);
+        let detected = detected_claude();
+        let prompt = ProviderPrompt {
+            detected: &detected,
+            ..prompt(&[])
+        };
+        let buffer = draw(&prompt, &state(&prompt), 80, 20);
+        let lines = buffer
+            .content()
+            .chunks(80)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>();
+
+        let (y, line) = lines
+            .iter()
+            .enumerate()
+            .find(|(_, line)| line.contains("Claude Code"))
+            .unwrap();
+        let x = line
+            .find("found ~/.claude")
+            .expect("reason next to the provider");
+        let name = line.find("Claude Code").unwrap();
+        assert!(x > name);
+        let dim = theme::Theme::dark().dim;
+        assert_eq!(buffer[(x as u16, y as u16)].fg, dim);
+        assert_ne!(buffer[(name as u16, y as u16)].fg, dim);
+        assert_eq!(lines.iter().filter(|l| l.contains("found ")).count(), 1);
+    }
+
+    #[test]
+    fn detection_reasons_shorten_the_home_directory() {
+        let home = std::env::var("HOME").unwrap_or_default();
+        let detected = vec![DetectedProvider {
+            reason: format!("found {home}/.claude"),
+            ..detected_claude().remove(0)
+        }];
+        let expected = if home.is_empty() {
+            "found /.claude"
+        } else {
+            "found ~/.claude"
+        };
+        assert_eq!(
+            detection_reason(&detected, ProviderId::ClaudeCode).as_deref(),
+            Some(expected)
+        );
+        assert_eq!(detection_reason(&detected, ProviderId::Aider), None);
+    }
+
+    #[test]
+    fn page_and_jump_keys_move_the_provider_cursor() {
+        let choices = crate::providers::supported_providers()
+            .into_iter()
+            .map(|info| info.id)
+            .collect::<Vec<_>>();
+        assert!(choices.len() > 12);
+        let prompt = ProviderPrompt {
+            choices: &choices,
+            ..prompt(&[])
+        };
+        let mut state = state(&prompt);
+        let last = choices.len() - 1;
+
+        press(&mut state, &prompt, [KeyCode::PageDown]);
+        assert_eq!(state.cursor, 5);
+        press(&mut state, &prompt, [KeyCode::PageDown, KeyCode::PageUp]);
+        assert_eq!(state.cursor, 5);
+        press(&mut state, &prompt, [KeyCode::End]);
+        assert_eq!(state.cursor, last);
+        press(&mut state, &prompt, [KeyCode::PageDown]);
+        assert_eq!(state.cursor, last);
+        press(&mut state, &prompt, [KeyCode::PageUp]);
+        assert_eq!(state.cursor, last - 5);
+        press(&mut state, &prompt, [KeyCode::Home]);
+        assert_eq!(state.cursor, 0);
+        press(&mut state, &prompt, [KeyCode::PageUp]);
+        assert_eq!(state.cursor, 0);
+        assert!(state.query.is_empty() && state.selected.is_empty());
+    }
+
+    #[test]
+    fn page_and_jump_keys_move_the_select_cursor() {
+        let mut cursor = 0;
+        let mut press = |key: KeyCode| handle_select_key(&mut cursor, key.into(), 12, 5, false);
+        assert!(press(KeyCode::End).is_none());
+        assert!(press(KeyCode::PageDown).is_none());
+        assert!(matches!(press(KeyCode::Enter), Some(Ok(11))));
+        assert!(press(KeyCode::PageUp).is_none());
+        assert!(matches!(press(KeyCode::Enter), Some(Ok(6))));
+        assert!(press(KeyCode::Home).is_none());
+        assert!(press(KeyCode::PageUp).is_none());
+        assert!(matches!(press(KeyCode::Enter), Some(Ok(0))));
+        assert!(press(KeyCode::PageDown).is_none());
+        assert!(press(KeyCode::PageDown).is_none());
+        assert!(press(KeyCode::PageUp).is_none());
+        assert!(matches!(press(KeyCode::Enter), Some(Ok(5))));
+        assert!(matches!(
+            press(KeyCode::Esc),
+            Some(Err(InstallerError::PromptCancelled))
+        ));
+    }
+}
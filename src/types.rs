@@ -1,7 +1,12 @@
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+use crate::error::InstallerError;
+use crate::observer::InstallObservers;
+use crate::transform::ProviderTransforms;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProviderId {
     Amp,
     Antigravity,
@@ -43,7 +48,14 @@ pub enum ProviderId {
     Neovate,
     Pochi,
     Adal,
+    Zed,
+    Aider,
+    Vscode,
     Universal,
+    /// An explicit `--target-dir` destination rather than a known provider.
+    TargetDir,
+    /// A provider added at runtime with `register_provider`.
+    Custom(&'static str),
 }
 
 impl ProviderId {
@@ -89,11 +101,22 @@ impl ProviderId {
             ProviderId::Neovate => "neovate",
             ProviderId::Pochi => "pochi",
             ProviderId::Adal => "adal",
+            ProviderId::Zed => "zed",
+            ProviderId::Aider => "aider",
+            ProviderId::Vscode => "vscode",
             ProviderId::Universal => "universal",
+            ProviderId::TargetDir => "target-dir",
+            ProviderId::Custom(id) => id,
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(value: &str) -> Option<Self> {
+        Self::builtin(value).or_else(|| crate::providers::registered_provider(value))
+    }
+
+    /// Built-in ids and aliases only; never consults (or loads) registered providers.
+    pub(crate) fn builtin(value: &str) -> Option<Self> {
         Some(match value {
             "amp" => ProviderId::Amp,
             "antigravity" => ProviderId::Antigravity,
@@ -135,96 +158,482 @@ impl ProviderId {
             "neovate" => ProviderId::Neovate,
             "pochi" => ProviderId::Pochi,
             "adal" => ProviderId::Adal,
+            "zed" => ProviderId::Zed,
+            "aider" => ProviderId::Aider,
+            "vscode" => ProviderId::Vscode,
             "universal" => ProviderId::Universal,
+            "claude" => ProviderId::ClaudeCode,
+            "copilot" => ProviderId::GithubCopilot,
+            "gemini" => ProviderId::GeminiCli,
+            "iflow" => ProviderId::IflowCli,
+            "kimi" => ProviderId::KimiCli,
+            "kiro" => ProviderId::KiroCli,
+            "qwen" => ProviderId::QwenCode,
+            "vibe" => ProviderId::MistralVibe,
+            "kilocode" => ProviderId::Kilo,
+            "factory" => ProviderId::Droid,
             _ => return None,
         })
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+impl serde::Serialize for ProviderId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ProviderId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        if value == ProviderId::TargetDir.as_str() {
+            return Ok(ProviderId::TargetDir);
+        }
+        ProviderId::from_str(&value)
+            .ok_or_else(|| serde::de::Error::custom(format!("unsupported provider: {value}")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
 pub enum Scope {
     User,
     Project,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+impl Scope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::User => "user",
+            Scope::Project => "project",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
 pub enum InstallMethod {
     Symlink,
     Copy,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum SymlinkPolicy {
+    /// Copy the files and directories that links point to.
+    #[default]
+    Follow,
+    /// Recreate links in the destination as links.
+    Preserve,
+    /// Follow links, but reject sources whose links resolve outside the skill root.
+    RejectEscaping,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct EmbeddedSkill {
     pub skill_md: String,
     pub files: Vec<(PathBuf, Vec<u8>)>,
 }
 
-#[derive(Debug, Clone)]
+impl EmbeddedSkill {
+    /// Builds a skill from `(relative path, contents)` pairs; one of them must be `SKILL.md`.
+    pub fn from_files<P: AsRef<Path>, B: AsRef<[u8]>>(
+        files: &[(P, B)],
+    ) -> crate::error::Result<Self> {
+        let mut skill_md = None;
+        let mut rest = Vec::new();
+        for (path, bytes) in files {
+            let path = path.as_ref();
+            if path == Path::new("SKILL.md") {
+                let text = std::str::from_utf8(bytes.as_ref()).map_err(|_| {
+                    InstallerError::InvalidUtf8 {
+                        path: path.to_path_buf(),
+                    }
+                })?;
+                skill_md = Some(text.to_string());
+            } else {
+                rest.push((path.to_path_buf(), bytes.as_ref().to_vec()));
+            }
+        }
+        Ok(Self {
+            skill_md: skill_md.ok_or(InstallerError::MissingEmbeddedSkill)?,
+            files: rest,
+        })
+    }
+
+    /// Builds a skill from an `include_dir!` tree rooted at the skill directory.
+    #[cfg(feature = "include-dir")]
+    pub fn from_include_dir(dir: &include_dir::Dir<'_>) -> crate::error::Result<Self> {
+        fn collect<'a>(dir: &'a include_dir::Dir<'a>, out: &mut Vec<&'a include_dir::File<'a>>) {
+            out.extend(dir.files());
+            for child in dir.dirs() {
+                collect(child, out);
+            }
+        }
+
+        let mut files = Vec::new();
+        collect(dir, &mut files);
+        let files = files
+            .into_iter()
+            .map(|file| {
+                let relative = file.path().strip_prefix(dir.path()).unwrap_or(file.path());
+                (relative, file.contents())
+            })
+            .collect::<Vec<_>>();
+        Self::from_files(&files)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum SkillSource {
     LocalPath(PathBuf),
     Embedded(EmbeddedSkill),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct ParsedSkill {
     pub name: String,
+    /// The frontmatter name when it was slugified into `name`.
+    pub display_name: Option<String>,
     pub description: Option<String>,
+    /// `version:` from frontmatter, falling back to a semver `metadata.version`.
+    pub version: Option<semver::Version>,
+    /// SPDX expression or license name from `license:`.
+    pub license: Option<String>,
+    /// From `author:` and/or `authors:`, falling back to `metadata.author`.
+    pub authors: Vec<String>,
+    pub compatibility: Option<String>,
     pub metadata: Option<BTreeMap<String, String>>,
     pub allowed_tools: Option<String>,
+    pub providers: SkillProviders,
+    /// Operating systems the skill supports (`linux`, `macos`, `windows`, or `unix`);
+    /// empty means any.
+    pub platforms: Vec<String>,
+    /// Placeholders used as `{{name}}` in the body, from `variables:` in frontmatter.
+    pub variables: Vec<SkillVariable>,
+    pub hooks: SkillHooks,
     pub body: String,
+    /// Unknown or misspelled frontmatter keys, e.g. `descripton`.
+    pub warnings: Vec<String>,
+    /// Payload files, including `SKILL.md`; empty when parsed from a string.
+    pub files: Vec<SkillFile>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SkillFile {
+    /// Relative to the skill root, `/`-separated.
+    pub path: String,
+    pub size: u64,
+    pub kind: SkillFileKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkillFileKind {
+    Markdown,
+    Text,
+    /// Has a shebang or a script extension such as `.sh` or `.py`.
+    Script,
+    Image,
+    Binary,
+    Symlink,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SkillVariable {
+    pub name: String,
+    pub description: Option<String>,
+    pub default: Option<String>,
+}
+
+/// Providers a skill declares itself compatible with, from `providers:` in frontmatter.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SkillProviders {
+    /// When set, only these providers are supported.
+    pub allow: Option<Vec<ProviderId>>,
+    pub deny: Vec<ProviderId>,
+}
+
+impl ParsedSkill {
+    /// Whether the skill can be installed on the OS this binary runs on.
+    pub fn supports_current_platform(&self) -> bool {
+        self.platforms.is_empty()
+            || self.platforms.iter().any(|platform| {
+                platform == std::env::consts::OS || (platform == "unix" && cfg!(unix))
+            })
+    }
+}
+
+impl SkillProviders {
+    pub fn allows(&self, provider: ProviderId) -> bool {
+        // `--target-dir` destinations are picked by the user, not matched to a provider.
+        if provider == ProviderId::TargetDir {
+            return true;
+        }
+        !self.deny.contains(&provider)
+            && self
+                .allow
+                .as_ref()
+                .is_none_or(|allow| allow.contains(&provider))
+    }
+}
+
+/// Install hook scripts declared under `hooks:` in frontmatter, relative to the skill root.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SkillHooks {
+    pub pre_install: Option<String>,
+    pub post_install: Option<String>,
+}
+
+impl SkillHooks {
+    pub fn is_empty(&self) -> bool {
+        self.pre_install.is_none() && self.post_install.is_none()
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct InstallRequest {
     pub source: SkillSource,
     pub providers: Vec<ProviderId>,
     pub scope: Scope,
     pub project_root: Option<PathBuf>,
     pub method: InstallMethod,
+    #[serde(default)]
     pub force: bool,
+    #[serde(default)]
+    pub options: InstallOptions,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct InstallOptions {
+    /// Copy entries that are skipped by default (`.git`, `.hg`, `.DS_Store`, `node_modules`).
+    pub include_ignored: bool,
+    pub symlinks: SymlinkPolicy,
+    #[serde(skip)]
+    pub transforms: ProviderTransforms,
+    /// Run `pre-install`/`post-install` hook scripts declared by the skill.
+    pub allow_hooks: bool,
+    /// Kill hooks running longer than this; defaults to 60 seconds.
+    pub hook_timeout: Option<Duration>,
+    /// Extra directories to install into (as `<dir>/<skill-name>`), alongside any providers.
+    pub target_dirs: Vec<PathBuf>,
+    /// Replace existing installs only when their receipt records an older version.
+    pub upgrade: bool,
+    /// Install skills whose `platforms:` exclude this OS, with a warning instead of an error.
+    pub ignore_platform: bool,
+    /// Values for the skill's `{{name}}` placeholders; override config and defaults.
+    pub variables: BTreeMap<String, String>,
+    /// Slugify the frontmatter name (`My Skill` → `my-skill`) instead of installing it as is.
+    pub slugify_name: bool,
+    /// Receive `InstallEvent`s as the install progresses.
+    #[serde(skip)]
+    pub observers: InstallObservers,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct InstallTarget {
     pub requested_provider: ProviderId,
     pub target_provider: ProviderId,
     pub target_dir: PathBuf,
+    pub stats: InstallStats,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InstallStats {
+    /// Files available at the target (for symlinked targets, the files behind the link).
+    pub files: usize,
+    pub bytes: u64,
+    /// Whether an existing skill was replaced.
+    pub overwritten: bool,
+    /// Whether the destination already matched the source, so nothing was written.
+    pub up_to_date: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct InstallResult {
     pub skill_name: String,
+    pub skill_version: Option<semver::Version>,
     pub installed_targets: Vec<InstallTarget>,
     pub normalized_providers: Vec<(ProviderId, ProviderId)>,
     pub skipped_duplicates: Vec<PathBuf>,
     pub warnings: Vec<String>,
+    pub elapsed: Duration,
 }
 
-#[derive(Debug, Clone)]
-pub struct DetectedProvider {
-    pub provider: ProviderId,
-    pub reason: String,
+impl InstallResult {
+    pub fn total_files(&self) -> usize {
+        self.installed_targets.iter().map(|t| t.stats.files).sum()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.installed_targets.iter().map(|t| t.stats.bytes).sum()
+    }
 }
 
-#[derive(Debug, Clone, clap::Args)]
-pub struct InstallSkillArgs {
-    /// Providers to target (comma-separated). Use '*' for all.
-    #[arg(long)]
-    pub providers: Option<String>,
+/// What `install` would do for a request, from `plan`; run it with `execute_plan`.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "native"), allow(dead_code))]
+pub struct InstallPlan {
+    pub skill_name: String,
+    pub skill_version: Option<semver::Version>,
+    /// For symlink installs, the shared copy comes first.
+    pub targets: Vec<PlannedTarget>,
+    /// Existing installs that make the install fail without `force` or `upgrade`.
+    pub conflicts: Vec<PathBuf>,
+    pub normalized_providers: Vec<(ProviderId, ProviderId)>,
+    /// Missing parent directories of the destinations, parents before children.
+    pub create_dirs: Vec<PathBuf>,
+    pub warnings: Vec<String>,
+    #[serde(skip)]
+    pub(crate) request: InstallRequest,
+    #[serde(skip)]
+    pub(crate) skill: ParsedSkill,
+    /// The part of `warnings` that execution does not report again.
+    #[serde(skip)]
+    pub(crate) setup_warnings: Vec<String>,
+}
 
-    /// Install scope
-    #[arg(long, value_enum)]
-    pub scope: Option<Scope>,
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PlannedTarget {
+    pub requested_provider: ProviderId,
+    pub target_provider: ProviderId,
+    pub destination: PathBuf,
+    pub action: PlannedAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlannedAction {
+    /// Copy the payload to a new destination.
+    Create,
+    /// Link to the shared copy.
+    Link,
+    /// Replace an existing install.
+    Overwrite,
+    /// Leave an existing install that `upgrade` found at the same or a newer version.
+    Keep,
+    /// An existing install is in the way; see `InstallPlan::conflicts`.
+    Conflict,
+    /// Another provider in the request resolves to the same destination.
+    SkipDuplicate,
+}
 
-    /// Project root; defaults to current directory when scope is project
-    #[arg(long)]
+impl PlannedAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PlannedAction::Create => "create",
+            PlannedAction::Link => "link",
+            PlannedAction::Overwrite => "overwrite",
+            PlannedAction::Keep => "keep",
+            PlannedAction::Conflict => "conflict",
+            PlannedAction::SkipDuplicate => "skip-duplicate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UninstallRequest {
+    pub skill_name: String,
+    pub providers: Vec<ProviderId>,
+    pub scope: Scope,
     pub project_root: Option<PathBuf>,
+}
 
-    /// Installation method
-    #[arg(long, value_enum)]
-    pub method: Option<InstallMethod>,
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UninstallResult {
+    pub skill_name: String,
+    pub removed: Vec<PathBuf>,
+    pub not_found: Vec<PathBuf>,
+    pub warnings: Vec<String>,
+}
 
-    /// Overwrite existing destination skill folders
-    #[arg(long, default_value_t = false)]
-    pub force: bool,
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InstalledSkill {
+    pub provider: ProviderId,
+    pub scope: Scope,
+    /// Skill directory, or the rendered file for flat-layout providers.
+    pub path: PathBuf,
+    pub method: InstallMethod,
+    /// Version recorded in the install receipt, when it is semver.
+    pub version: Option<semver::Version>,
+    /// Where the install came from, when it was downloaded from a registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<RegistryPin>,
+}
+
+/// The registry release an install came from.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegistryPin {
+    /// Index URL or path.
+    pub index: String,
+    pub name: String,
+    /// The version the requirement resolved to.
+    pub version: String,
+    /// What was asked for, e.g. `^1.2`; `None` for a bare name, meaning the latest.
+    pub requirement: Option<String>,
+    /// `sha256:<hex>` of the downloaded archive.
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DetectedProvider {
+    pub provider: ProviderId,
+    pub reason: String,
+    /// Installed agent version, when its CLI reports one.
+    pub version: Option<String>,
+    pub confidence: DetectionConfidence,
+    /// Every marker that matched; `reason` describes the first.
+    pub evidence: Vec<DetectionEvidence>,
+}
+
+/// `High`: global install plus project use or a working CLI; `Medium`: one of
+/// global install or CLI; `Low`: only a project directory, which other tools may create.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum DetectionConfidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl DetectionConfidence {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DetectionConfidence::Low => "low",
+            DetectionConfidence::Medium => "medium",
+            DetectionConfidence::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DetectionEvidence {
+    /// `User` for markers in the home/config directories, `Project` for the project root.
+    pub scope: Scope,
+    pub path: PathBuf,
 }
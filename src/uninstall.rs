@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::error::{InstallerError, Result};
+use crate::flat::{flat_destination, FLAT_MARKER};
+use crate::install::resolve_install_target;
+use crate::parser::skill_dir_name;
+use crate::providers::{normalize_providers, provider_capabilities};
+use crate::receipt::{read_receipt, RECEIPT_FILE_NAME};
+use crate::types::{UninstallRequest, UninstallResult};
+
+pub fn uninstall(request: UninstallRequest) -> Result<UninstallResult> {
+    let (providers, _) = normalize_providers(&request.providers);
+
+    let mut removed = Vec::new();
+    let mut not_found = Vec::new();
+    let mut warnings = Vec::new();
+    let mut seen = HashSet::new();
+
+    for provider in providers {
+        let target =
+            resolve_install_target(provider, request.scope, request.project_root.as_deref())?;
+        if provider_capabilities(provider).flat_layout {
+            let destination = flat_destination(&target.target_dir, &request.skill_name);
+            if seen.insert(destination.clone()) {
+                remove_flat_file(destination, &mut removed, &mut not_found, &mut warnings)?;
+            }
+            continue;
+        }
+
+        let destination = target.target_dir.join(skill_dir_name(&request.skill_name));
+        if !seen.insert(destination.clone()) {
+            continue;
+        }
+
+        let Ok(metadata) = fs::symlink_metadata(&destination) else {
+            not_found.push(destination);
+            continue;
+        };
+
+        if metadata.file_type().is_symlink() {
+            fs::remove_file(&destination).map_err(|err| InstallerError::IoError {
+                path: destination.clone(),
+                message: err.to_string(),
+            })?;
+            removed.push(destination);
+            continue;
+        }
+
+        let Some(receipt) = read_receipt(&destination)? else {
+            warnings.push(format!(
+                "{} has no {RECEIPT_FILE_NAME}; left in place",
+                destination.display()
+            ));
+            continue;
+        };
+
+        let leftover = remove_owned_files(&destination, &receipt.files, &mut warnings)?;
+        if leftover == 0 {
+            removed.push(destination);
+        } else {
+            warnings.push(format!(
+                "kept {leftover} file(s) not installed by skillinstaller in {}",
+                destination.display()
+            ));
+        }
+    }
+
+    Ok(UninstallResult {
+        skill_name: request.skill_name,
+        removed,
+        not_found,
+        warnings,
+    })
+}
+
+fn remove_flat_file(
+    destination: PathBuf,
+    removed: &mut Vec<PathBuf>,
+    not_found: &mut Vec<PathBuf>,
+    warnings: &mut Vec<String>,
+) -> Result<()> {
+    let Ok(content) = fs::read_to_string(&destination) else {
+        not_found.push(destination);
+        return Ok(());
+    };
+    if !content.starts_with(FLAT_MARKER) {
+        warnings.push(format!(
+            "{} was not written by skillinstaller; left in place",
+            destination.display()
+        ));
+        return Ok(());
+    }
+
+    fs::remove_file(&destination).map_err(|err| InstallerError::IoError {
+        path: destination.clone(),
+        message: err.to_string(),
+    })?;
+    removed.push(destination);
+    Ok(())
+}
+
+fn remove_owned_files(
+    skill_dir: &Path,
+    files: &[String],
+    warnings: &mut Vec<String>,
+) -> Result<usize> {
+    for relative in files {
+        let relative_path = Path::new(relative);
+        if relative_path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            warnings.push(format!("ignored unsafe receipt entry '{relative}'"));
+            continue;
+        }
+
+        let path = skill_dir.join(relative_path);
+        match fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(InstallerError::IoError {
+                    path,
+                    message: err.to_string(),
+                })
+            }
+        }
+    }
+
+    let receipt_path = skill_dir.join(RECEIPT_FILE_NAME);
+    fs::remove_file(&receipt_path).map_err(|err| InstallerError::IoError {
+        path: receipt_path,
+        message: err.to_string(),
+    })?;
+
+    // Prune directories emptied above; anything still present belongs to the user.
+    let mut leftover = 0;
+    for entry in WalkDir::new(skill_dir).contents_first(true) {
+        let entry = entry.map_err(|err| InstallerError::IoError {
+            path: skill_dir.to_path_buf(),
+            message: err.to_string(),
+        })?;
+        if entry.file_type().is_dir() {
+            let _ = fs::remove_dir(entry.path());
+        } else {
+            leftover += 1;
+        }
+    }
+
+    Ok(leftover)
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+use crate::error::{InstallerError, Result};
+use crate::parser::split_frontmatter;
+use crate::types::{InstallOptions, ParsedSkill};
+
+static CONFIGURED: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+
+/// Values from the `[variables]` table of the user config, used when `--var` is absent.
+pub(crate) fn set_configured_variables(values: BTreeMap<String, String>) {
+    CONFIGURED
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .extend(values);
+}
+
+pub(crate) fn configured_variable(name: &str) -> Option<String> {
+    crate::providers::load_user_providers_once();
+    CONFIGURED
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .cloned()
+}
+
+/// Resolves every declared variable from the install options, then the user config,
+/// then its default.
+pub(crate) fn resolve_variables(
+    skill: &ParsedSkill,
+    options: &InstallOptions,
+) -> Result<BTreeMap<String, String>> {
+    if skill.variables.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    skill
+        .variables
+        .iter()
+        .map(|variable| {
+            let value = options
+                .variables
+                .get(&variable.name)
+                .cloned()
+                .or_else(|| configured_variable(&variable.name))
+                .or_else(|| variable.default.clone())
+                .ok_or_else(|| InstallerError::MissingVariable {
+                    skill: skill.name.clone(),
+                    name: variable.name.clone(),
+                })?;
+            Ok((variable.name.clone(), value))
+        })
+        .collect()
+}
+
+/// Replaces `{{name}}` (optionally padded, `{{ name }}`) for each resolved variable.
+/// Placeholders for undeclared names are left as written.
+pub(crate) fn render_variables(text: &str, values: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let replaced = after.find("}}").and_then(|end| {
+            values
+                .get(after[..end].trim())
+                .map(|value| (value, &after[end + 2..]))
+        });
+        match replaced {
+            Some((value, tail)) => {
+                out.push_str(value);
+                rest = tail;
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Renders the body of a staged `SKILL.md`, leaving its frontmatter untouched.
+pub(crate) fn render_skill_md(skill_dir: &Path, values: &BTreeMap<String, String>) -> Result<()> {
+    let path = skill_dir.join("SKILL.md");
+    let io_err = |err: std::io::Error| InstallerError::IoError {
+        path: path.clone(),
+        message: err.to_string(),
+    };
+    let content = fs::read_to_string(&path).map_err(io_err)?;
+    let (_, body) = split_frontmatter(&content)?;
+    let head = &content[..content.len() - body.len()];
+    fs::write(&path, format!("{head}{}", render_variables(body, values))).map_err(io_err)
+}
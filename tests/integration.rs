@@ -1,8 +1,22 @@
+#![cfg(feature = "native")]
+
 use std::fs;
+use std::path::Path;
+use std::sync::Arc;
 
 use skillinstaller::{
-    detect_providers, install, parse_skill, InstallMethod, InstallRequest, InstallerError,
-    ProviderId, Scope, SkillSource,
+    detect_installed, detect_provider_version, detect_providers, detect_providers_with_versions,
+    execute_plan, install, install_many, lint_skill, lint_skill_for_providers, list_installed,
+    load_project_config, load_providers_file, parse_provider_path, parse_providers_csv,
+    parse_skill, parse_skill_str, plan, provider_groups, rank_providers, read_receipt,
+    register_provider, register_provider_group, resolve_install_target, semver, set_project_path,
+    skill_files, skill_frontmatter_schema, slugify_skill_name, supported_providers, uninstall,
+    user_dir_env_var, DetectReport, DetectionConfidence, EmbeddedSkill, InstallEvent,
+    InstallMethod, InstallOptions, InstallRequest, InstallResult, InstalledSkill, InstallerError,
+    LogObserver, OutputFormat, ParsedSkill, PlannedAction, ProviderCapabilities, ProviderId,
+    ProviderInfo, ProviderListReport, ProviderTransform, Scope, Severity, SkillFileKind,
+    SkillProviders, SkillSource, SkillVariable, SkipReason, SymlinkPolicy, UninstallRequest,
+    Verbosity, Versioned, SCHEMA_VERSION,
 };
 use tempfile::TempDir;
 
@@ -48,6 +62,7 @@ fn install_copies_full_skill_payload_and_normalizes_agents_providers() {
         project_root: Some(project.path().to_path_buf()),
         method: InstallMethod::Copy,
         force: false,
+        options: InstallOptions::default(),
     })
     .unwrap();
 
@@ -77,15 +92,19 @@ fn install_fails_without_force_if_destination_exists() {
         project_root: Some(project.path().to_path_buf()),
         method: InstallMethod::Copy,
         force: false,
+        options: InstallOptions::default(),
     };
 
     install(request.clone()).unwrap();
     let second = install(request);
 
     match second {
-        Err(InstallerError::AlreadyExists { .. }) => {}
+        Err(err @ InstallerError::AlreadyExists { .. }) => {
+            assert_eq!(err.code(), "E_ALREADY_EXISTS")
+        }
         other => panic!("expected AlreadyExists, got {other:?}"),
     }
+    assert_eq!(InstallerError::MissingName.code(), "E_MISSING_NAME");
 }
 
 #[test]
@@ -100,6 +119,7 @@ fn install_symlink_copies_to_universal_and_links_other_providers() {
         project_root: Some(project.path().to_path_buf()),
         method: InstallMethod::Symlink,
         force: false,
+        options: InstallOptions::default(),
     })
     .unwrap();
 
@@ -128,3 +148,4497 @@ fn detect_providers_returns_empty_in_clean_temp_home() {
     let detected = detect_providers(Some(temp_home.path()));
     assert!(detected.is_empty());
 }
+
+#[test]
+fn install_copy_skips_vcs_and_editor_entries_by_default() {
+    let fixture = make_skill_fixture();
+    let skill_root = fixture.path().join(".skill");
+    fs::create_dir_all(skill_root.join(".git/objects")).unwrap();
+    fs::write(skill_root.join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+    fs::create_dir_all(skill_root.join("scripts/node_modules/dep")).unwrap();
+    fs::write(skill_root.join(".DS_Store"), "").unwrap();
+    let project = TempDir::new().unwrap();
+
+    let mut request = InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    };
+    install(request.clone()).unwrap();
+
+    let claude_skill = project.path().join(".claude/skills/demo-skill");
+    assert!(claude_skill.join("scripts/run.sh").exists());
+    assert!(!claude_skill.join(".git").exists());
+    assert!(!claude_skill.join(".DS_Store").exists());
+    assert!(!claude_skill.join("scripts/node_modules").exists());
+
+    request.force = true;
+    request.options.include_ignored = true;
+    install(request).unwrap();
+    assert!(claude_skill.join(".git/HEAD").exists());
+    assert!(claude_skill.join(".DS_Store").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn install_copy_applies_source_symlink_policy() {
+    let fixture = make_skill_fixture();
+    let skill_root = fixture.path().join(".skill");
+    std::os::unix::fs::symlink("scripts", skill_root.join("bin")).unwrap();
+    let project = TempDir::new().unwrap();
+
+    let mut request = InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: true,
+        options: InstallOptions::default(),
+    };
+    let claude_skill = project.path().join(".claude/skills/demo-skill");
+
+    install(request.clone()).unwrap();
+    assert!(!fs::symlink_metadata(claude_skill.join("bin"))
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert!(claude_skill.join("bin/run.sh").exists());
+
+    request.options.symlinks = SymlinkPolicy::Preserve;
+    install(request.clone()).unwrap();
+    assert_eq!(
+        fs::read_link(claude_skill.join("bin")).unwrap(),
+        std::path::PathBuf::from("scripts")
+    );
+
+    let outside = TempDir::new().unwrap();
+    std::os::unix::fs::symlink(outside.path(), skill_root.join("escape")).unwrap();
+    request.options.symlinks = SymlinkPolicy::RejectEscaping;
+    match install(request) {
+        Err(InstallerError::SymlinkEscapesSource { .. }) => {}
+        other => panic!("expected SymlinkEscapesSource, got {other:?}"),
+    }
+}
+
+#[test]
+fn install_reports_per_target_statistics() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+
+    let mut request = InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force: false,
+        options: InstallOptions::default(),
+    };
+
+    let first = install(request.clone()).unwrap();
+    let target = &first.installed_targets[0];
+    assert_eq!(target.stats.files, 2);
+    assert!(target.stats.bytes > 0);
+    assert!(!target.stats.overwritten);
+    assert_eq!(first.total_files(), 2);
+
+    request.force = true;
+    fs::write(fixture.path().join(".skill/notes.md"), "new").unwrap();
+    let second = install(request).unwrap();
+    assert!(second.installed_targets[0].stats.overwritten);
+    assert_eq!(second.total_files(), 3);
+}
+
+#[test]
+fn forced_install_skips_targets_whose_content_is_unchanged() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+
+    for method in [InstallMethod::Copy, InstallMethod::Symlink] {
+        let request = InstallRequest {
+            source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+            providers: vec![ProviderId::Cursor, ProviderId::ClaudeCode],
+            scope: Scope::Project,
+            project_root: Some(project.path().to_path_buf()),
+            method,
+            force: true,
+            options: InstallOptions::default(),
+        };
+
+        install(request.clone()).unwrap();
+        let again = install(request.clone()).unwrap();
+        assert!(again.installed_targets.iter().all(|t| t.stats.up_to_date));
+        assert!(again.installed_targets.iter().all(|t| !t.stats.overwritten));
+
+        fs::write(fixture.path().join(".skill/scripts/run.sh"), "echo changed").unwrap();
+        let changed = install(request).unwrap();
+        let universal = changed
+            .installed_targets
+            .iter()
+            .find(|t| t.target_provider == ProviderId::Universal)
+            .unwrap();
+        assert!(!universal.stats.up_to_date);
+        assert!(universal.stats.overwritten);
+        fs::write(fixture.path().join(".skill/scripts/run.sh"), "echo hi").unwrap();
+    }
+}
+
+#[test]
+fn install_writes_receipt_next_to_installed_skill() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+
+    let request = InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: true,
+        options: InstallOptions::default(),
+    };
+    install(request.clone()).unwrap();
+
+    let claude_skill = project.path().join(".claude/skills/demo-skill");
+    let receipt = read_receipt(&claude_skill).unwrap().unwrap();
+    assert_eq!(receipt.name, "demo-skill");
+    assert!(receipt.hash.starts_with("sha256:"));
+    assert_eq!(receipt.installer_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(receipt.files, vec!["SKILL.md", "scripts/run.sh"]);
+
+    let again = install(request).unwrap();
+    assert!(again.installed_targets[0].stats.up_to_date);
+    assert_eq!(
+        read_receipt(&claude_skill).unwrap().unwrap().hash,
+        receipt.hash
+    );
+}
+
+#[test]
+fn uninstall_removes_only_files_listed_in_receipt() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+
+    install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![
+            ProviderId::Cursor,
+            ProviderId::ClaudeCode,
+            ProviderId::Goose,
+        ],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+
+    let universal_skill = project.path().join(".agents/skills/demo-skill");
+    fs::write(universal_skill.join("scripts/notes.txt"), "mine").unwrap();
+
+    let result = uninstall(UninstallRequest {
+        skill_name: "demo-skill".to_string(),
+        providers: vec![ProviderId::Cursor, ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+    })
+    .unwrap();
+
+    let claude_skill = project.path().join(".claude/skills/demo-skill");
+    assert!(fs::symlink_metadata(&claude_skill).is_err());
+    assert_eq!(result.removed, vec![claude_skill]);
+    assert!(!universal_skill.join("SKILL.md").exists());
+    assert!(!universal_skill.join("scripts/run.sh").exists());
+    assert!(universal_skill.join("scripts/notes.txt").exists());
+    assert_eq!(result.warnings.len(), 1);
+    assert!(project.path().join(".goose/skills/demo-skill").exists());
+}
+
+struct RenameSkillMd;
+
+impl ProviderTransform for RenameSkillMd {
+    fn transform(
+        &self,
+        _provider: ProviderId,
+        skill_dir: &Path,
+        skill: &ParsedSkill,
+    ) -> skillinstaller::Result<()> {
+        fs::rename(skill_dir.join("SKILL.md"), skill_dir.join("RULES.md")).unwrap();
+        fs::write(skill_dir.join("NAME"), &skill.name).unwrap();
+        Ok(())
+    }
+}
+
+#[test]
+fn provider_transform_adapts_provider_copy_and_keeps_universal_canonical() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let mut options = InstallOptions::default();
+    options
+        .transforms
+        .insert(ProviderId::ClaudeCode, Arc::new(RenameSkillMd));
+
+    install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode, ProviderId::Goose],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force: false,
+        options,
+    })
+    .unwrap();
+
+    let universal_skill = project.path().join(".agents/skills/demo-skill");
+    let claude_skill = project.path().join(".claude/skills/demo-skill");
+    let goose_skill = project.path().join(".goose/skills/demo-skill");
+
+    assert!(universal_skill.join("SKILL.md").exists());
+    assert!(!fs::symlink_metadata(&claude_skill)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert!(claude_skill.join("RULES.md").exists());
+    assert!(!claude_skill.join("SKILL.md").exists());
+    assert_eq!(
+        fs::read_to_string(claude_skill.join("NAME")).unwrap(),
+        "demo-skill"
+    );
+    assert!(fs::symlink_metadata(&goose_skill)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    let receipt = read_receipt(&claude_skill).unwrap().unwrap();
+    assert!(receipt.files.contains(&"RULES.md".to_string()));
+}
+
+#[cfg(unix)]
+#[test]
+fn install_hooks_run_only_when_allowed() {
+    let fixture = TempDir::new().unwrap();
+    let skill_root = fixture.path().join(".skill");
+    fs::create_dir_all(skill_root.join("hooks")).unwrap();
+    fs::write(
+        skill_root.join("SKILL.md"),
+        "---\nname: hooked\nhooks:\n  pre-install: hooks/pre.sh\n  post-install: hooks/post.sh\n---\nBody",
+    )
+    .unwrap();
+    fs::write(
+        skill_root.join("hooks/pre.sh"),
+        "echo \"$SKILL_INSTALLER_DESTINATION\" > pre.txt",
+    )
+    .unwrap();
+    fs::write(
+        skill_root.join("hooks/post.sh"),
+        "echo \"$SKILL_INSTALLER_PROVIDER\" > post.txt",
+    )
+    .unwrap();
+    let project = TempDir::new().unwrap();
+
+    let mut request = InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: true,
+        options: InstallOptions::default(),
+    };
+    let claude_skill = project.path().join(".claude/skills/hooked");
+
+    let skipped = install(request.clone()).unwrap();
+    assert!(!claude_skill.join("pre.txt").exists());
+    assert!(skipped.warnings.iter().any(|w| w.contains("--allow-hooks")));
+
+    request.options.allow_hooks = true;
+    fs::write(skill_root.join("notes.md"), "changed").unwrap();
+    install(request.clone()).unwrap();
+    assert_eq!(
+        fs::read_to_string(claude_skill.join("pre.txt"))
+            .unwrap()
+            .trim(),
+        claude_skill.display().to_string()
+    );
+    assert_eq!(
+        fs::read_to_string(claude_skill.join("post.txt"))
+            .unwrap()
+            .trim(),
+        "claude-code"
+    );
+
+    fs::write(skill_root.join("hooks/pre.sh"), "exit 3").unwrap();
+    match install(request) {
+        Err(InstallerError::HookFailed { hook, .. }) => assert_eq!(hook, "pre-install"),
+        other => panic!("expected HookFailed, got {other:?}"),
+    }
+    assert!(claude_skill.join("post.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn install_hooks_are_sandboxed_and_time_limited() {
+    let fixture = TempDir::new().unwrap();
+    let skill_root = fixture.path().join(".skill");
+    fs::create_dir_all(skill_root.join("hooks")).unwrap();
+    fs::write(
+        skill_root.join("SKILL.md"),
+        "---\nname: sandboxed\nhooks:\n  post-install: hooks/post.sh\n---\nBody",
+    )
+    .unwrap();
+    fs::write(
+        skill_root.join("hooks/post.sh"),
+        "sleep 30 &\necho \"cwd=$(pwd)\"\necho \"manifest=${CARGO_MANIFEST_DIR:-cleared}\"",
+    )
+    .unwrap();
+    let project = TempDir::new().unwrap();
+
+    let mut request = InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: true,
+        options: InstallOptions {
+            allow_hooks: true,
+            hook_timeout: Some(std::time::Duration::from_secs(2)),
+            ..InstallOptions::default()
+        },
+    };
+
+    let started = std::time::Instant::now();
+    let result = install(request.clone()).unwrap();
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    let claude_skill = fs::canonicalize(project.path().join(".claude/skills/sandboxed")).unwrap();
+    assert!(result.warnings.contains(&format!(
+        "post-install hook: cwd={}",
+        claude_skill.display()
+    )));
+    assert!(result
+        .warnings
+        .contains(&"post-install hook: manifest=cleared".to_string()));
+
+    fs::write(skill_root.join("hooks/post.sh"), "sleep 30").unwrap();
+    let started = std::time::Instant::now();
+    let result = install(request.clone()).unwrap();
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    assert!(result.warnings.iter().any(|w| w.contains("timed out")));
+
+    // A descendant in its own session survives the group kill and keeps stdout open.
+    if std::process::Command::new("setsid")
+        .arg("true")
+        .status()
+        .is_ok()
+    {
+        fs::write(
+            skill_root.join("hooks/post.sh"),
+            "setsid sleep 30 &\necho done",
+        )
+        .unwrap();
+        let started = std::time::Instant::now();
+        let result = install(request.clone()).unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        assert!(result
+            .warnings
+            .contains(&"post-install hook: done".to_string()));
+    }
+
+    request.options.allow_hooks = false;
+    install(request).unwrap();
+}
+
+#[test]
+fn install_many_dedupes_destinations_across_requests() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let missing = TempDir::new().unwrap();
+
+    let request = |source: &Path, providers: Vec<ProviderId>, method| InstallRequest {
+        source: SkillSource::LocalPath(source.to_path_buf()),
+        providers,
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method,
+        force: false,
+        options: InstallOptions::default(),
+    };
+
+    let results = install_many(vec![
+        request(
+            fixture.path(),
+            vec![ProviderId::ClaudeCode, ProviderId::Cursor],
+            InstallMethod::Copy,
+        ),
+        request(
+            missing.path(),
+            vec![ProviderId::ClaudeCode],
+            InstallMethod::Copy,
+        ),
+        request(
+            fixture.path(),
+            vec![ProviderId::Cursor, ProviderId::Goose],
+            InstallMethod::Symlink,
+        ),
+    ]);
+    assert_eq!(results.len(), 3);
+
+    let first = results[0].as_ref().unwrap();
+    assert_eq!(first.installed_targets.len(), 2);
+    assert!(results[1].is_err());
+
+    let universal_skill = project.path().join(".agents/skills/demo-skill");
+    let third = results[2].as_ref().unwrap();
+    assert_eq!(third.skipped_duplicates, vec![universal_skill.clone()]);
+    assert!(third
+        .warnings
+        .iter()
+        .any(|w| w.contains("earlier request in this batch")));
+    assert_eq!(
+        fs::read_link(project.path().join(".goose/skills/demo-skill")).unwrap(),
+        universal_skill
+    );
+}
+
+#[test]
+fn install_into_custom_target_dirs() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let custom = TempDir::new().unwrap();
+    let custom_dir = custom.path().join("my-agent/skills");
+
+    let result = install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions {
+            target_dirs: vec![custom_dir.clone()],
+            ..InstallOptions::default()
+        },
+    })
+    .unwrap();
+
+    assert_eq!(result.installed_targets.len(), 2);
+    let custom_target = &result.installed_targets[1];
+    assert_eq!(custom_target.requested_provider, ProviderId::TargetDir);
+    assert_eq!(custom_target.target_dir, custom_dir.join("demo-skill"));
+    assert!(custom_dir.join("demo-skill/scripts/run.sh").exists());
+    assert!(read_receipt(&custom_dir.join("demo-skill"))
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn install_rejects_case_insensitive_name_collisions() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let existing = project.path().join(".claude/skills/Demo-Skill");
+    fs::create_dir_all(&existing).unwrap();
+    fs::write(existing.join("SKILL.md"), "---\nname: Demo-Skill\n---\n").unwrap();
+
+    let err = install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: true,
+        options: InstallOptions::default(),
+    })
+    .unwrap_err();
+
+    assert!(matches!(err, InstallerError::CaseCollision { .. }));
+    assert!(existing.join("SKILL.md").exists());
+}
+
+#[test]
+fn registered_custom_provider_installs_like_builtins() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let in_house = ProviderId::Custom("in-house-agent");
+
+    register_provider(ProviderInfo {
+        id: in_house,
+        display_name: "In-House Agent",
+        uses_agents_dir: false,
+        project_path: ".in-house/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
+    })
+    .unwrap();
+    assert!(register_provider(ProviderInfo {
+        id: ProviderId::Custom("cursor"),
+        display_name: "Not Cursor",
+        uses_agents_dir: false,
+        project_path: ".not-cursor/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
+    })
+    .is_err());
+
+    assert_eq!(
+        parse_providers_csv("in-house-agent").unwrap(),
+        vec![in_house]
+    );
+    assert_eq!(
+        serde_json::to_string(&in_house).unwrap(),
+        "\"in-house-agent\""
+    );
+
+    install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![in_house],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+
+    assert!(project
+        .path()
+        .join(".in-house/skills/demo-skill/SKILL.md")
+        .exists());
+}
+
+#[test]
+fn providers_config_file_registers_custom_providers() {
+    let config = TempDir::new().unwrap();
+    let path = config.path().join("providers.toml");
+    fs::write(
+        &path,
+        r#"
+[[provider]]
+id = "acme-agent"
+display-name = "Acme Agent"
+project-path = ".acme/skills"
+user-path = ".acme/global-skills"
+detect-markers = [".acme"]
+"#,
+    )
+    .unwrap();
+
+    let ids = load_providers_file(&path).unwrap();
+    assert_eq!(ids, vec![ProviderId::Custom("acme-agent")]);
+    let info = supported_providers()
+        .into_iter()
+        .find(|p| p.id.as_str() == "acme-agent")
+        .unwrap();
+    assert_eq!(info.display_name, "Acme Agent");
+    assert_eq!(info.user_path, Some(".acme/global-skills"));
+
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".acme/skills")).unwrap();
+    assert!(detect_providers(Some(project.path()))
+        .iter()
+        .any(|d| d.provider == ProviderId::Custom("acme-agent")));
+
+    fs::write(
+        &path,
+        "[[provider]]\nid = \"Bad Id\"\nproject-path = \"x\"\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        load_providers_file(&path),
+        Err(InstallerError::ConfigError { .. })
+    ));
+}
+
+#[test]
+fn provider_aliases_and_typo_suggestions() {
+    assert_eq!(
+        parse_providers_csv("claude,copilot,gemini").unwrap(),
+        vec![
+            ProviderId::ClaudeCode,
+            ProviderId::GithubCopilot,
+            ProviderId::GeminiCli
+        ]
+    );
+
+    let err = parse_providers_csv("codx").unwrap_err();
+    assert!(matches!(
+        &err,
+        InstallerError::UnsupportedProvider { suggestion: Some(s), .. } if s == "codex"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "unsupported provider: codx (did you mean `codex`?)"
+    );
+    assert!(matches!(
+        parse_providers_csv("definitely-not-a-provider").unwrap_err(),
+        InstallerError::UnsupportedProvider {
+            suggestion: None,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn provider_user_dir_can_be_overridden_from_env() {
+    let relocated = TempDir::new().unwrap();
+    let var = user_dir_env_var(ProviderId::Adal);
+    assert_eq!(var, "SKILL_INSTALLER_ADAL_DIR");
+
+    std::env::set_var(&var, relocated.path());
+    let target = resolve_install_target(ProviderId::Adal, Scope::User, None).unwrap();
+    std::env::remove_var(&var);
+
+    assert_eq!(target.target_dir, relocated.path());
+    let project = TempDir::new().unwrap();
+    let target = resolve_install_target(ProviderId::Adal, Scope::Project, Some(project.path()));
+    assert_eq!(
+        target.unwrap().target_dir,
+        project.path().join(".adal/skills")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn provider_version_is_read_from_cli() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bin = TempDir::new().unwrap();
+    let qwen = bin.path().join("qwen");
+    fs::write(&qwen, "#!/bin/sh\necho 'Qwen Code v0.4.1 (build abc)'\n").unwrap();
+    fs::set_permissions(&qwen, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let original = std::env::var_os("PATH").unwrap_or_default();
+    let mut paths = vec![bin.path().to_path_buf()];
+    paths.extend(std::env::split_paths(&original));
+    std::env::set_var("PATH", std::env::join_paths(paths).unwrap());
+    let version = detect_provider_version(ProviderId::QwenCode);
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".qwen/skills")).unwrap();
+    let qwen_in = |detected: Vec<skillinstaller::DetectedProvider>| {
+        detected
+            .into_iter()
+            .find(|d| d.provider == ProviderId::QwenCode)
+            .unwrap()
+    };
+    let plain = qwen_in(detect_providers(Some(project.path())));
+    let probed = qwen_in(detect_providers_with_versions(Some(project.path())));
+    std::env::set_var("PATH", original);
+
+    assert_eq!(version.as_deref(), Some("0.4.1"));
+    assert_eq!(plain.version, None);
+    assert_eq!(plain.confidence, DetectionConfidence::Low);
+    assert_eq!(probed.version.as_deref(), Some("0.4.1"));
+    assert_eq!(probed.confidence, DetectionConfidence::Medium);
+    assert_eq!(detect_provider_version(ProviderId::Adal), None);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn install_args_exclude_providers() {
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--providers",
+        "*",
+        "--exclude",
+        "cursor,trae-cn",
+    ]);
+    let excluded = cli.args.excluded_providers().unwrap();
+    assert_eq!(excluded, vec![ProviderId::Cursor, ProviderId::TraeCn]);
+
+    let mut providers = parse_providers_csv(cli.args.providers.as_deref().unwrap()).unwrap();
+    providers.retain(|p| !excluded.contains(p));
+    assert!(!providers.contains(&ProviderId::TraeCn));
+    assert!(providers.contains(&ProviderId::Trae));
+}
+
+#[test]
+fn provider_groups_expand_in_provider_lists() {
+    let config = TempDir::new().unwrap();
+    let path = config.path().join("providers.toml");
+    fs::write(
+        &path,
+        "[groups]\nwork = [\"claude-code\", \"cursor\", \"codex\"]\n",
+    )
+    .unwrap();
+    load_providers_file(&path).unwrap();
+
+    assert_eq!(
+        parse_providers_csv("@work,goose").unwrap(),
+        vec![
+            ProviderId::ClaudeCode,
+            ProviderId::Cursor,
+            ProviderId::Codex,
+            ProviderId::Goose
+        ]
+    );
+
+    register_provider_group("solo", vec![ProviderId::Roo]).unwrap();
+    assert!(provider_groups()
+        .iter()
+        .any(|g| g.name == "solo" && g.providers == vec![ProviderId::Roo]));
+    assert!(matches!(
+        parse_providers_csv("@missing"),
+        Err(InstallerError::UnsupportedProvider { .. })
+    ));
+
+    fs::write(&path, "[groups]\nbroken = [\"not-a-provider\"]\n").unwrap();
+    assert!(matches!(
+        load_providers_file(&path),
+        Err(InstallerError::ConfigError { .. })
+    ));
+}
+
+#[test]
+fn deprecated_providers_are_hidden_from_wildcard_and_warn() {
+    assert!(!parse_providers_csv("*")
+        .unwrap()
+        .contains(&ProviderId::TraeCn));
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let result = install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::TraeCn],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+
+    assert!(result
+        .warnings
+        .contains(&"provider 'trae-cn' is deprecated; use 'trae' instead".to_string()));
+}
+
+#[test]
+fn provider_project_path_can_be_overridden() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+
+    let (provider, path) = parse_provider_path("mux=.mux/agents/skills").unwrap();
+    assert_eq!(provider, ProviderId::Mux);
+    set_project_path(provider, &path).unwrap();
+    assert!(set_project_path(ProviderId::Mux, "/abs/skills").is_err());
+    for escaping in ["../../x", ".mux/../../x", "./.mux", ""] {
+        assert!(
+            set_project_path(ProviderId::Mux, escaping).is_err(),
+            "{escaping}"
+        );
+    }
+    assert!(parse_provider_path("mux").is_err());
+
+    let mux_path = || {
+        supported_providers()
+            .into_iter()
+            .find(|p| p.id == ProviderId::Mux)
+            .unwrap()
+            .project_path
+    };
+    let first = mux_path();
+    set_project_path(provider, &path).unwrap();
+    assert_eq!(first, ".mux/agents/skills");
+    assert!(std::ptr::eq(first, mux_path()));
+
+    install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::Mux],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+
+    assert!(project
+        .path()
+        .join(".mux/agents/skills/demo-skill/SKILL.md")
+        .exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn install_adapts_to_provider_capabilities() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let no_links = ProviderId::Custom("no-links-agent");
+
+    register_provider(ProviderInfo {
+        id: no_links,
+        display_name: "No Links",
+        uses_agents_dir: false,
+        project_path: ".no-links/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities {
+            supports_symlink: false,
+            ..ProviderCapabilities::STANDARD
+        },
+    })
+    .unwrap();
+
+    let result = install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![no_links],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+
+    let copied = project.path().join(".no-links/skills/demo-skill");
+    assert!(!fs::symlink_metadata(&copied)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert!(copied.join("SKILL.md").exists());
+    assert!(result.warnings.is_empty());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn scope_restricted_providers_are_hidden_from_expansion_and_rejected_explicitly() {
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let user_only = ProviderId::Custom("user-only-agent");
+    register_provider(ProviderInfo {
+        id: user_only,
+        display_name: "User Only",
+        uses_agents_dir: false,
+        project_path: ".user-only/skills",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities {
+            supports_project_scope: false,
+            ..ProviderCapabilities::STANDARD
+        },
+    })
+    .unwrap();
+
+    let wildcard = <Cli as clap::Parser>::parse_from(["install-skill", "--providers", "*"]);
+    let expanded = wildcard.args.selected_providers(Scope::Project).unwrap();
+    assert!(expanded.contains(&ProviderId::ClaudeCode));
+    assert!(!expanded.contains(&user_only));
+    assert!(wildcard
+        .args
+        .selected_providers(Scope::User)
+        .unwrap()
+        .contains(&user_only));
+
+    let explicit = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--providers",
+        "claude-code,user-only-agent",
+    ]);
+    let providers = explicit.args.selected_providers(Scope::Project).unwrap();
+    assert_eq!(providers, vec![ProviderId::ClaudeCode, user_only]);
+
+    let err = install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers,
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap_err();
+    assert!(matches!(err, InstallerError::UnsupportedScope { .. }));
+    assert!(!project.path().join(".claude").exists());
+
+    assert!(!wildcard
+        .args
+        .selected_providers(Scope::User)
+        .unwrap()
+        .contains(&ProviderId::Replit));
+    let err = install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::Replit],
+        scope: Scope::User,
+        project_root: None,
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap_err();
+    assert!(matches!(err, InstallerError::UnsupportedScope { .. }));
+}
+
+#[test]
+fn flat_layout_providers_get_a_single_rendered_file() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let flat = ProviderId::Custom("flat-rules-agent");
+
+    register_provider(ProviderInfo {
+        id: flat,
+        display_name: "Flat Rules",
+        uses_agents_dir: false,
+        project_path: ".flat/rules",
+        user_path: None,
+        detect_markers: &[],
+        deprecated: None,
+        capabilities: ProviderCapabilities {
+            flat_layout: true,
+            ..ProviderCapabilities::STANDARD
+        },
+    })
+    .unwrap();
+
+    let request = InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![flat],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force: true,
+        options: InstallOptions::default(),
+    };
+    let result = install(request.clone()).unwrap();
+
+    let rules = project.path().join(".flat/rules/demo-skill.md");
+    let content = fs::read_to_string(&rules).unwrap();
+    assert!(content.contains("# demo-skill\n\nDemo\n\nUse this skill."));
+    assert_eq!(result.installed_targets[0].target_dir, rules);
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w.contains("only the SKILL.md content")));
+
+    let again = install(request).unwrap();
+    assert!(again.installed_targets[0].stats.up_to_date);
+
+    let removed = uninstall(UninstallRequest {
+        skill_name: "demo-skill".to_string(),
+        providers: vec![flat],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+    })
+    .unwrap();
+    assert!(removed.removed.contains(&rules));
+    assert!(!rules.exists());
+}
+
+#[test]
+fn detection_reports_evidence_and_confidence() {
+    let global = TempDir::new().unwrap();
+    let marker: &'static str = Box::leak(
+        global
+            .path()
+            .join(".evidence-agent")
+            .to_string_lossy()
+            .into_owned()
+            .into_boxed_str(),
+    );
+    let provider = ProviderId::Custom("evidence-agent");
+    register_provider(ProviderInfo {
+        id: provider,
+        display_name: "Evidence Agent",
+        uses_agents_dir: false,
+        project_path: ".evidence/skills",
+        user_path: None,
+        detect_markers: Box::leak(vec![marker].into_boxed_slice()),
+        deprecated: None,
+        capabilities: ProviderCapabilities::STANDARD,
+    })
+    .unwrap();
+
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".evidence/skills")).unwrap();
+    let find = || {
+        detect_providers(Some(project.path()))
+            .into_iter()
+            .find(|d| d.provider == provider)
+    };
+
+    let weak = find().unwrap();
+    assert_eq!(weak.confidence, DetectionConfidence::Low);
+    assert_eq!(weak.evidence.len(), 1);
+    assert_eq!(weak.evidence[0].scope, Scope::Project);
+
+    fs::create_dir_all(marker).unwrap();
+    let strong = find().unwrap();
+    assert_eq!(strong.confidence, DetectionConfidence::High);
+    assert_eq!(strong.evidence.len(), 2);
+    assert_eq!(strong.evidence[0].scope, Scope::User);
+    assert_eq!(strong.reason, format!("found {marker}"));
+
+    fs::remove_dir_all(project.path().join(".evidence")).unwrap();
+    assert_eq!(find().unwrap().confidence, DetectionConfidence::Medium);
+}
+
+#[test]
+fn detect_installed_reports_provider_scope_and_method() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+
+    install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+
+    let installed: Vec<InstalledSkill> = detect_installed("demo-skill", Some(project.path()))
+        .unwrap()
+        .into_iter()
+        .filter(|found| found.scope == Scope::Project)
+        .collect();
+
+    let claude = installed
+        .iter()
+        .find(|found| found.provider == ProviderId::ClaudeCode)
+        .unwrap();
+    assert_eq!(claude.method, InstallMethod::Symlink);
+    assert_eq!(
+        claude.path,
+        project.path().join(".claude/skills/demo-skill")
+    );
+
+    let cursor = installed
+        .iter()
+        .find(|found| found.provider == ProviderId::Cursor)
+        .unwrap();
+    assert_eq!(cursor.method, InstallMethod::Copy);
+    assert_eq!(
+        cursor.path,
+        project.path().join(".agents/skills/demo-skill")
+    );
+
+    assert!(!installed
+        .iter()
+        .any(|found| found.provider == ProviderId::Windsurf));
+    assert!(detect_installed("missing-skill", Some(project.path()))
+        .unwrap()
+        .iter()
+        .all(|found| found.scope != Scope::Project));
+}
+
+#[test]
+fn providers_rank_favorites_then_detected_then_alphabetical() {
+    let config = TempDir::new().unwrap();
+    let path = config.path().join("providers.toml");
+    fs::write(&path, "favorites = [\"cursor\", \"windsurf\"]\n").unwrap();
+    load_providers_file(&path).unwrap();
+
+    let mut providers = vec![
+        ProviderId::ClaudeCode,
+        ProviderId::Amp,
+        ProviderId::Windsurf,
+        ProviderId::Augment,
+        ProviderId::Cursor,
+    ];
+    rank_providers(&mut providers, &[ProviderId::ClaudeCode]);
+    assert_eq!(
+        providers,
+        vec![
+            ProviderId::Cursor,
+            ProviderId::Windsurf,
+            ProviderId::ClaudeCode,
+            ProviderId::Amp,
+            ProviderId::Augment,
+        ]
+    );
+
+    let all = parse_providers_csv("*").unwrap();
+    assert_eq!(&all[..2], &[ProviderId::Cursor, ProviderId::Windsurf]);
+
+    fs::write(&path, "favorites = [\"not-an-agent\"]\n").unwrap();
+    assert!(matches!(
+        load_providers_file(&path),
+        Err(InstallerError::ConfigError { .. })
+    ));
+}
+
+#[test]
+fn zed_is_a_provider_specific_target() {
+    let project = TempDir::new().unwrap();
+    assert_eq!(ProviderId::from_str("zed"), Some(ProviderId::Zed));
+
+    let target =
+        resolve_install_target(ProviderId::Zed, Scope::Project, Some(project.path())).unwrap();
+    assert_eq!(target.target_provider, ProviderId::Zed);
+    assert_eq!(target.target_dir, project.path().join(".zed/skills"));
+}
+
+#[test]
+fn aider_is_detected_from_its_project_directory() {
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".aider")).unwrap();
+
+    let aider = detect_providers(Some(project.path()))
+        .into_iter()
+        .find(|d| d.provider == ProviderId::Aider)
+        .unwrap();
+    assert!(aider
+        .evidence
+        .iter()
+        .any(|e| e.scope == Scope::Project && e.path == project.path().join(".aider")));
+
+    let target =
+        resolve_install_target(ProviderId::Aider, Scope::Project, Some(project.path())).unwrap();
+    assert_eq!(target.target_dir, project.path().join(".aider/skills"));
+}
+
+#[test]
+fn vscode_project_path_can_follow_github_instructions_convention() {
+    let project = TempDir::new().unwrap();
+    let target =
+        resolve_install_target(ProviderId::Vscode, Scope::Project, Some(project.path())).unwrap();
+    assert_eq!(target.target_dir, project.path().join(".vscode/skills"));
+
+    let (provider, path) = parse_provider_path("vscode=.github/instructions").unwrap();
+    assert_eq!(provider, ProviderId::Vscode);
+    assert_eq!(path, ".github/instructions");
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn vscode_is_not_detected_from_the_editor_alone() {
+    let home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(home.path().join(".vscode/extensions")).unwrap();
+    let vscode = || {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+            .args(["--format", "json", "detect", "--project-root"])
+            .arg(project.path())
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("SKILL_INSTALLER_VERSIONS")
+            .output()
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        report["detected"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["provider"] == "vscode")
+            .cloned()
+    };
+
+    assert!(vscode().is_none());
+
+    fs::create_dir_all(project.path().join(".github/instructions")).unwrap();
+    assert_eq!(vscode().unwrap()["confidence"], "low");
+
+    fs::create_dir_all(home.path().join(".vscode/skills")).unwrap();
+    assert_eq!(vscode().unwrap()["confidence"], "high");
+}
+
+#[test]
+fn frontmatter_text_fields_accept_scalars_and_name_the_field_otherwise() {
+    let parsed =
+        parse_skill_str("---\nname: numeric-skill\ndescription: 42\nlicense: true\n---\nBody")
+            .unwrap();
+    assert_eq!(parsed.description.as_deref(), Some("42"));
+    assert_eq!(parsed.license.as_deref(), Some("true"));
+
+    let err = parse_skill_str("---\nname: list-skill\ndescription: [a, b]\n---\nBody").unwrap_err();
+    assert!(matches!(err, InstallerError::InvalidFrontmatter { .. }));
+    assert!(err.to_string().contains("'description'"), "{err}");
+}
+
+#[test]
+fn frontmatter_version_must_be_a_quoted_string() {
+    let err = parse_skill_str("---\nname: float-skill\nversion: 1.0\n---\nBody").unwrap_err();
+    assert!(matches!(err, InstallerError::InvalidFrontmatter { .. }));
+    assert!(
+        err.to_string()
+            .contains("'version' must be a quoted semver string"),
+        "{err}"
+    );
+    assert!(err.to_string().contains("got 1.0"), "{err}");
+    let parsed = parse_skill_str("---\nname: quoted-skill\nversion: \"1.0.0\"\n---\nBody").unwrap();
+    assert_eq!(parsed.version, Some(semver::Version::new(1, 0, 0)));
+}
+
+#[test]
+fn parse_skill_warns_about_unknown_frontmatter_fields() {
+    let dir = TempDir::new().unwrap();
+    let skill_root = dir.path().join(".skill");
+    fs::create_dir_all(&skill_root).unwrap();
+    fs::write(
+        skill_root.join("SKILL.md"),
+        "---\nname: typo-skill\ndescripton: Oops\nlicense: MIT\nhooks:\n  post-instal: hooks/post\nflavour: x\n---\nBody",
+    )
+    .unwrap();
+
+    let parsed = parse_skill(&SkillSource::LocalPath(dir.path().to_path_buf())).unwrap();
+    assert_eq!(parsed.description, None);
+    assert_eq!(parsed.license.as_deref(), Some("MIT"));
+    assert_eq!(
+        parsed.warnings,
+        vec![
+            "unknown frontmatter field 'descripton' (did you mean 'description'?)".to_string(),
+            "unknown frontmatter field 'flavour'".to_string(),
+            "unknown frontmatter field 'hooks.post-instal' (did you mean 'hooks.post-install'?)"
+                .to_string(),
+        ]
+    );
+
+    let clean = make_skill_fixture();
+    let parsed = parse_skill(&SkillSource::LocalPath(clean.path().to_path_buf())).unwrap();
+    assert!(parsed.warnings.is_empty());
+}
+
+#[test]
+fn upgrade_replaces_only_older_installed_versions() {
+    let fixture = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    let skill_md = fixture.path().join(".skill/SKILL.md");
+    fs::create_dir_all(skill_md.parent().unwrap()).unwrap();
+    let write_version = |version: &str| {
+        fs::write(
+            &skill_md,
+            format!("---\nname: versioned-skill\nversion: {version}\n---\nv{version}"),
+        )
+        .unwrap();
+    };
+    let run = |force: bool| {
+        install(InstallRequest {
+            source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+            providers: vec![ProviderId::ClaudeCode],
+            scope: Scope::Project,
+            project_root: Some(project.path().to_path_buf()),
+            method: InstallMethod::Copy,
+            force,
+            options: InstallOptions {
+                upgrade: true,
+                ..InstallOptions::default()
+            },
+        })
+    };
+    let installed = project.path().join(".claude/skills/versioned-skill");
+
+    write_version("1.2.0");
+    let result = run(false).unwrap();
+    assert_eq!(result.skill_version, Some(semver::Version::new(1, 2, 0)));
+
+    write_version("1.1.9");
+    let result = run(false).unwrap();
+    assert!(result.installed_targets[0].stats.up_to_date);
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w.contains("is already at 1.2.0; not replaced by 1.1.9")));
+    assert!(fs::read_to_string(installed.join("SKILL.md"))
+        .unwrap()
+        .contains("v1.2.0"));
+
+    write_version("1.10.0");
+    let result = run(false).unwrap();
+    assert!(result.installed_targets[0].stats.overwritten);
+    let found = detect_installed("versioned-skill", Some(project.path()))
+        .unwrap()
+        .into_iter()
+        .find(|found| found.provider == ProviderId::ClaudeCode)
+        .unwrap();
+    assert_eq!(found.version, Some(semver::Version::new(1, 10, 0)));
+
+    write_version("not-a-version");
+    assert!(matches!(
+        run(true),
+        Err(InstallerError::InvalidFrontmatter { .. })
+    ));
+}
+
+#[test]
+fn parse_skill_reads_license_and_authors() {
+    let fixture = make_skill_fixture();
+    let parsed = parse_skill(&SkillSource::LocalPath(fixture.path().to_path_buf())).unwrap();
+    assert_eq!(parsed.authors, vec!["acme".to_string()]);
+    assert_eq!(parsed.license, None);
+
+    let dir = TempDir::new().unwrap();
+    let skill_root = dir.path().join(".skill");
+    fs::create_dir_all(&skill_root).unwrap();
+    fs::write(
+        skill_root.join("SKILL.md"),
+        "---\nname: credited\nlicense: Apache-2.0\nauthor: Ada\nauthors:\n  - Grace\n  - Linus\nmetadata:\n  author: ignored\n---\nBody",
+    )
+    .unwrap();
+    let parsed = parse_skill(&SkillSource::LocalPath(dir.path().to_path_buf())).unwrap();
+    assert_eq!(parsed.license.as_deref(), Some("Apache-2.0"));
+    assert_eq!(parsed.authors, vec!["Ada", "Grace", "Linus"]);
+    assert!(parsed.warnings.is_empty());
+}
+
+#[test]
+fn frontmatter_providers_limit_install_targets() {
+    let dir = TempDir::new().unwrap();
+    let skill_root = dir.path().join(".skill");
+    fs::create_dir_all(&skill_root).unwrap();
+    fs::write(
+        skill_root.join("SKILL.md"),
+        "---\nname: picky-skill\nproviders:\n  deny: [cursor, windsurf, mystery-agent]\n---\nBody",
+    )
+    .unwrap();
+    let source = SkillSource::LocalPath(dir.path().to_path_buf());
+
+    let parsed = parse_skill(&source).unwrap();
+    assert_eq!(
+        parsed.providers,
+        SkillProviders {
+            allow: None,
+            deny: vec![ProviderId::Cursor, ProviderId::Windsurf],
+        }
+    );
+    assert!(parsed.providers.allows(ProviderId::ClaudeCode));
+    assert!(parsed.warnings[0].contains("mystery-agent"));
+
+    let project = TempDir::new().unwrap();
+    let request = |providers: Vec<ProviderId>| InstallRequest {
+        source: source.clone(),
+        providers,
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    };
+
+    let result = install(request(vec![ProviderId::ClaudeCode, ProviderId::Windsurf])).unwrap();
+    assert_eq!(result.installed_targets.len(), 1);
+    assert!(result.warnings[0].contains("declares provider 'windsurf' unsupported"));
+    assert!(!project.path().join(".windsurf").exists());
+
+    assert!(matches!(
+        install(request(vec![ProviderId::Cursor])),
+        Err(InstallerError::IncompatibleProviders { .. })
+    ));
+
+    fs::write(
+        skill_root.join("SKILL.md"),
+        "---\nname: picky-skill\nproviders: [claude-code]\n---\nBody",
+    )
+    .unwrap();
+    let parsed = parse_skill(&source).unwrap();
+    assert!(parsed.providers.allows(ProviderId::ClaudeCode));
+    assert!(!parsed.providers.allows(ProviderId::Codex));
+}
+
+#[test]
+fn frontmatter_allowlist_of_only_unknown_providers_is_an_error() {
+    for frontmatter in [
+        "providers: [mystery-agent, other-agent]",
+        "providers:\n  allow: []",
+    ] {
+        let err = parse_skill_str(&format!("---\nname: picky-skill\n{frontmatter}\n---\nBody"))
+            .unwrap_err();
+        assert!(matches!(err, InstallerError::InvalidFrontmatter { .. }));
+        assert!(err.to_string().contains("names no known provider"), "{err}");
+    }
+
+    let parsed =
+        parse_skill_str("---\nname: picky-skill\nproviders: [mystery-agent, codex]\n---\nBody")
+            .unwrap();
+    assert_eq!(parsed.providers.allow, Some(vec![ProviderId::Codex]));
+}
+
+#[test]
+fn skills_declaring_other_platforms_need_ignore_platform() {
+    let dir = TempDir::new().unwrap();
+    let skill_root = dir.path().join(".skill");
+    fs::create_dir_all(&skill_root).unwrap();
+    let other = if cfg!(windows) { "linux" } else { "windows" };
+    fs::write(
+        skill_root.join("SKILL.md"),
+        format!("---\nname: os-bound\nplatforms: [{other}, beos]\n---\nBody"),
+    )
+    .unwrap();
+    let source = SkillSource::LocalPath(dir.path().to_path_buf());
+
+    let parsed = parse_skill(&source).unwrap();
+    assert_eq!(parsed.platforms, vec![other.to_string()]);
+    assert!(!parsed.supports_current_platform());
+    assert!(parsed.warnings[0].contains("unknown platform 'beos'"));
+
+    let project = TempDir::new().unwrap();
+    let request = |ignore_platform: bool| InstallRequest {
+        source: source.clone(),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions {
+            ignore_platform,
+            ..InstallOptions::default()
+        },
+    };
+
+    assert!(matches!(
+        install(request(false)),
+        Err(InstallerError::UnsupportedPlatform { .. })
+    ));
+    let result = install(request(true)).unwrap();
+    assert!(result.warnings[0].contains("installed anyway"));
+    assert!(project.path().join(".claude/skills/os-bound").exists());
+}
+
+#[test]
+fn parse_skill_handles_frontmatter_delimiter_variants() {
+    let parse = |skill_md: &str| {
+        parse_skill(&SkillSource::Embedded(EmbeddedSkill {
+            skill_md: skill_md.to_string(),
+            files: Vec::new(),
+        }))
+    };
+
+    let at_eof = parse("---\nname: eof\n---").unwrap();
+    assert_eq!(at_eof.name, "eof");
+    assert_eq!(at_eof.body, "");
+
+    let padded = parse("--- \r\nname: padded\r\n---\t\r\nBody\r\n").unwrap();
+    assert_eq!(padded.name, "padded");
+    assert_eq!(padded.body, "Body\r\n");
+
+    let rule = parse("---\nname: rule\n---\n---\nAfter the rule\n---\n").unwrap();
+    assert_eq!(rule.name, "rule");
+    assert_eq!(rule.body, "---\nAfter the rule\n---\n");
+
+    assert!(matches!(
+        parse("---\nname: open"),
+        Err(InstallerError::InvalidFrontmatter { .. })
+    ));
+    assert!(matches!(
+        parse("----\nname: dashes\n---\n"),
+        Err(InstallerError::InvalidFrontmatter { .. })
+    ));
+}
+
+#[test]
+fn install_substitutes_declared_template_variables() {
+    let dir = TempDir::new().unwrap();
+    let skill_root = dir.path().join(".skill");
+    fs::create_dir_all(&skill_root).unwrap();
+    fs::write(
+        skill_root.join("SKILL.md"),
+        "---\nname: templated\nvariables:\n  team:\n    description: Owning team\n  port: 8080\n---\nAsk {{ team }} on port {{port}}; {{other}} stays.\n",
+    )
+    .unwrap();
+    let source = SkillSource::LocalPath(dir.path().to_path_buf());
+
+    let parsed = parse_skill(&source).unwrap();
+    assert_eq!(
+        parsed.variables,
+        vec![
+            SkillVariable {
+                name: "port".to_string(),
+                description: None,
+                default: Some("8080".to_string()),
+            },
+            SkillVariable {
+                name: "team".to_string(),
+                description: Some("Owning team".to_string()),
+                default: None,
+            },
+        ]
+    );
+
+    let project = TempDir::new().unwrap();
+    let request = |variables: &[(&str, &str)]| InstallRequest {
+        source: source.clone(),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force: true,
+        options: InstallOptions {
+            variables: variables
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..InstallOptions::default()
+        },
+    };
+
+    assert!(matches!(
+        install(request(&[])),
+        Err(InstallerError::MissingVariable { .. })
+    ));
+
+    install(request(&[("team", "platform")])).unwrap();
+    let installed =
+        fs::read_to_string(project.path().join(".claude/skills/templated/SKILL.md")).unwrap();
+    assert!(installed.starts_with("---\nname: templated\n"));
+    assert!(installed.ends_with("Ask platform on port 8080; {{other}} stays.\n"));
+    assert!(fs::read_to_string(skill_root.join("SKILL.md"))
+        .unwrap()
+        .contains("{{ team }}"));
+}
+
+#[test]
+fn parse_skill_flags_broken_payload_references() {
+    let fixture = make_skill_fixture();
+    let skill_root = fixture.path().join(".skill");
+    fs::create_dir_all(skill_root.join("templates")).unwrap();
+    fs::write(skill_root.join("templates/x.md"), "x").unwrap();
+    fs::write(
+        skill_root.join("SKILL.md"),
+        "---\nname: linked\n---\nRun `scripts/run.sh`, then `scripts/gone.sh`.\n\
+         See [the template](./templates/x.md \"Template\"), [docs](https://example.com/a/b),\n\
+         [top](#usage), [notes](notes/missing.md#intro) and ./templates/other.md.\n\
+         Edit `src/main.rs` in your project, clone `acme/tools`, read references/gone.md.\n",
+    )
+    .unwrap();
+
+    let parsed = parse_skill(&SkillSource::LocalPath(fixture.path().to_path_buf())).unwrap();
+    assert_eq!(
+        parsed.warnings,
+        vec![
+            "SKILL.md references 'notes/missing.md', which is not in the skill".to_string(),
+            "SKILL.md references 'scripts/gone.sh', which is not in the skill".to_string(),
+            "SKILL.md references 'templates/other.md', which is not in the skill".to_string(),
+            "SKILL.md references 'references/gone.md', which is not in the skill".to_string(),
+        ]
+    );
+
+    let embedded = parse_skill(&SkillSource::Embedded(EmbeddedSkill {
+        skill_md: "---\nname: embedded\n---\nUse `scripts/run.sh` and `scripts/nope.sh`."
+            .to_string(),
+        files: vec![("scripts/run.sh".into(), b"echo".to_vec())],
+    }))
+    .unwrap();
+    assert_eq!(
+        embedded.warnings,
+        vec!["SKILL.md references 'scripts/nope.sh', which is not in the skill".to_string()]
+    );
+}
+
+#[test]
+fn lint_skill_reports_structured_diagnostics() {
+    let skill_md = format!(
+        "---\nname: My_Skill\ndescription: {}\n---\n{}",
+        "x".repeat(1100),
+        "line\n".repeat(501)
+    );
+    let parsed = parse_skill(&SkillSource::Embedded(EmbeddedSkill {
+        skill_md: skill_md.clone(),
+        files: Vec::new(),
+    }))
+    .unwrap();
+
+    let diagnostics = lint_skill(&parsed, skill_md.as_bytes());
+    let rules = diagnostics.iter().map(|d| d.rule).collect::<Vec<_>>();
+    assert_eq!(
+        rules,
+        vec![
+            "name-not-kebab-case",
+            "description-too-long",
+            "body-too-long"
+        ]
+    );
+    let name = diagnostics[0].span.unwrap();
+    assert_eq!(name.line, 2);
+    assert_eq!(&skill_md[name.start..name.end], "name: My_Skill");
+    assert_eq!(diagnostics[2].span.unwrap().line, 5);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+
+    let fixture = make_skill_fixture();
+    let clean = parse_skill(&SkillSource::LocalPath(fixture.path().to_path_buf())).unwrap();
+    assert!(lint_skill(&clean, b"").is_empty());
+
+    let bare = "---\nname: bare\n---\nBody";
+    let parsed = parse_skill(&SkillSource::Embedded(EmbeddedSkill {
+        skill_md: bare.to_string(),
+        files: Vec::new(),
+    }))
+    .unwrap();
+    let diagnostics = lint_skill(&parsed, bare.as_bytes());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "missing-description");
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].span, None);
+}
+
+#[test]
+fn frontmatter_schema_covers_parsed_fields() {
+    let schema = skill_frontmatter_schema();
+    assert_eq!(schema["required"], serde_json::json!(["name"]));
+    assert_eq!(schema["additionalProperties"], false);
+
+    let properties = schema["properties"].as_object().unwrap();
+    for field in [
+        "name",
+        "description",
+        "version",
+        "license",
+        "author",
+        "authors",
+        "compatibility",
+        "allowed-tools",
+        "providers",
+        "platforms",
+        "variables",
+        "metadata",
+        "hooks",
+    ] {
+        assert!(properties.contains_key(field), "missing {field}");
+    }
+    assert_eq!(properties.len(), 13);
+    assert_eq!(
+        properties["providers"]["oneOf"].as_array().unwrap().len(),
+        2
+    );
+    assert_eq!(
+        properties["hooks"]["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["post-install", "pre-install"]
+    );
+}
+
+#[test]
+fn frontmatter_schema_accepts_what_the_parser_accepts() {
+    let validator = jsonschema::validator_for(&skill_frontmatter_schema()).unwrap();
+    let frontmatter = r#"name: acme/every-field
+description: 42
+version: "1.10.0"
+license: MIT
+author: true
+authors: [Ada, Grace]
+compatibility: 3.5
+allowed-tools: Bash Read
+providers:
+  allow: [claude-code]
+  deny: [cursor]
+platforms: [linux, macos]
+variables:
+  team: core
+  region:
+    description: Deploy region
+    default: eu
+metadata:
+  tags: pdf
+hooks:
+  pre-install: scripts/pre.sh
+  post-install: scripts/post.sh
+"#;
+    let parsed = parse_skill_str(&format!("---\n{frontmatter}---\nBody.\n")).unwrap();
+    assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+    assert_eq!(parsed.description.as_deref(), Some("42"));
+
+    let yaml: serde_yaml::Value = serde_yaml::from_str(frontmatter).unwrap();
+    let instance: serde_json::Value = serde_json::to_value(yaml).unwrap();
+    let errors: Vec<_> = validator
+        .iter_errors(&instance)
+        .map(|e| e.to_string())
+        .collect();
+    assert!(errors.is_empty(), "{errors:?}");
+
+    // The schema and the parser both reject an unquoted version.
+    let unquoted = frontmatter.replace("\"1.10.0\"", "1.10");
+    assert!(parse_skill_str(&format!("---\n{unquoted}---\nBody.\n")).is_err());
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&unquoted).unwrap();
+    assert!(!validator.is_valid(&serde_json::to_value(yaml).unwrap()));
+}
+
+#[test]
+fn frontmatter_schema_accepts_namespaced_names() {
+    let validator = jsonschema::validator_for(&skill_frontmatter_schema()).unwrap();
+    for name in ["pdf-tools", "acme/pdf-tools"] {
+        assert!(
+            validator.is_valid(&serde_json::json!({ "name": name })),
+            "{name}"
+        );
+    }
+    for name in ["", "acme/", "/pdf-tools", "acme/pdf/tools", "pdf:tools"] {
+        assert!(
+            !validator.is_valid(&serde_json::json!({ "name": name })),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn parse_skill_str_parses_in_memory_content() {
+    let parsed = parse_skill_str(
+        "---\nname: in-memory\ndescription: From a string\nversion: 1.2.0\n---\nSee [guide](docs/guide.md).\n",
+    )
+    .unwrap();
+    assert_eq!(parsed.name, "in-memory");
+    assert_eq!(parsed.description.as_deref(), Some("From a string"));
+    assert_eq!(parsed.version, Some(semver::Version::new(1, 2, 0)));
+    assert!(parsed.warnings.is_empty());
+
+    let err = parse_skill_str("no frontmatter").unwrap_err();
+    assert!(matches!(err, InstallerError::InvalidFrontmatter { .. }));
+}
+
+#[test]
+fn slugify_name_installs_under_slug_and_keeps_display_name() {
+    assert_eq!(slugify_skill_name("My Cool Skill!"), "my-cool-skill");
+    assert_eq!(slugify_skill_name("  API / Docs v2 "), "api-docs-v2");
+    assert_eq!(slugify_skill_name("already-kebab"), "already-kebab");
+
+    let source = SkillSource::Embedded(EmbeddedSkill {
+        skill_md: "---\nname: \"My Cool Skill?\"\n---\nBody".to_string(),
+        files: Vec::new(),
+    });
+    assert!(matches!(
+        parse_skill(&source).unwrap_err(),
+        InstallerError::InvalidName { .. }
+    ));
+
+    let project = tempfile::tempdir().unwrap();
+    let result = install(InstallRequest {
+        source,
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions {
+            slugify_name: true,
+            ..InstallOptions::default()
+        },
+    })
+    .unwrap();
+    assert_eq!(result.skill_name, "my-cool-skill");
+    let receipt = read_receipt(&project.path().join(".claude/skills/my-cool-skill"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(receipt.name, "my-cool-skill");
+    assert_eq!(receipt.display_name.as_deref(), Some("My Cool Skill?"));
+}
+
+#[test]
+fn provider_limits_warn_for_selected_targets() {
+    let skill_md = format!(
+        "---\nname: long-description\ndescription: {}\n---\nBody\n",
+        "x".repeat(600)
+    );
+    let parsed = parse_skill_str(&skill_md).unwrap();
+
+    let diagnostics =
+        lint_skill_for_providers(&parsed, skill_md.as_bytes(), &[ProviderId::ClaudeCode]);
+    assert!(diagnostics.is_empty());
+    let diagnostics = lint_skill_for_providers(&parsed, skill_md.as_bytes(), &[ProviderId::Codex]);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "exceeds-provider-limit");
+    assert_eq!(diagnostics[0].span.unwrap().line, 3);
+
+    let project = tempfile::tempdir().unwrap();
+    let result = install(InstallRequest {
+        source: SkillSource::Embedded(EmbeddedSkill {
+            skill_md,
+            files: Vec::new(),
+        }),
+        providers: vec![ProviderId::ClaudeCode, ProviderId::Codex],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+    assert_eq!(
+        result.warnings[0],
+        "description is 600 characters; Codex truncates it to 500"
+    );
+    assert!(!result.warnings.iter().any(|w| w.contains("Claude Code")));
+}
+
+#[test]
+fn parsed_skill_lists_payload_files_with_kinds() {
+    let fixture = make_skill_fixture();
+    let skill_root = fixture.path().join(".skill");
+    fs::create_dir_all(skill_root.join("assets")).unwrap();
+    fs::create_dir_all(skill_root.join(".git")).unwrap();
+    fs::write(
+        skill_root.join("assets/logo.png"),
+        [0x89, b'P', b'N', b'G', 0],
+    )
+    .unwrap();
+    fs::write(
+        skill_root.join("assets/tool"),
+        [0x7f, b'E', b'L', b'F', 0, 1],
+    )
+    .unwrap();
+    fs::write(skill_root.join("assets/notes.txt"), "plain").unwrap();
+    fs::write(skill_root.join("setup"), "#!/bin/sh\necho setup").unwrap();
+    fs::write(skill_root.join(".git/config"), "[core]").unwrap();
+
+    let parsed = parse_skill(&SkillSource::LocalPath(fixture.path().to_path_buf())).unwrap();
+    let files = parsed
+        .files
+        .iter()
+        .map(|f| (f.path.as_str(), f.size, f.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        files,
+        vec![
+            ("SKILL.md", 83, SkillFileKind::Markdown),
+            ("assets/logo.png", 5, SkillFileKind::Image),
+            ("assets/notes.txt", 5, SkillFileKind::Text),
+            ("assets/tool", 6, SkillFileKind::Binary),
+            ("scripts/run.sh", 7, SkillFileKind::Script),
+            ("setup", 20, SkillFileKind::Script),
+        ]
+    );
+
+    let with_ignored = skill_files(&SkillSource::LocalPath(fixture.path().to_path_buf()), true)
+        .unwrap()
+        .into_iter()
+        .map(|f| f.path)
+        .collect::<Vec<_>>();
+    assert!(with_ignored.contains(&".git/config".to_string()));
+    assert_eq!(with_ignored.len(), files.len() + 1);
+
+    let embedded = parse_skill(&SkillSource::Embedded(EmbeddedSkill {
+        skill_md: "---\nname: e\n---\n".to_string(),
+        files: vec![(
+            std::path::PathBuf::from("docs/guide.md"),
+            b"# Guide".to_vec(),
+        )],
+    }))
+    .unwrap();
+    assert_eq!(embedded.files.len(), 2);
+    assert_eq!(embedded.files[1].kind, SkillFileKind::Markdown);
+    assert!(parse_skill_str("---\nname: s\n---\n")
+        .unwrap()
+        .files
+        .is_empty());
+}
+
+#[test]
+fn observers_receive_install_lifecycle_events() {
+    let fixture = make_skill_fixture();
+    let project = tempfile::tempdir().unwrap();
+    let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let mut options = InstallOptions::default();
+    options
+        .observers
+        .push(Arc::new(move |event: &InstallEvent| {
+            sink.lock().unwrap().push(event.clone())
+        }));
+    let request = InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: true,
+        options,
+    };
+    install(request.clone()).unwrap();
+
+    let destination = project.path().join(".claude/skills/demo-skill");
+    let recorded = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(recorded.len(), 5);
+    assert!(matches!(
+        &recorded[0],
+        InstallEvent::SourceResolved { skill, files: 2, .. } if skill == "demo-skill"
+    ));
+    assert_eq!(
+        recorded[1],
+        InstallEvent::ProviderResolved {
+            provider: ProviderId::ClaudeCode,
+            destination: destination.clone(),
+        }
+    );
+    let copied = recorded[2..4]
+        .iter()
+        .map(|event| match event {
+            InstallEvent::Copying { file, .. } => file.to_string_lossy().replace('\\', "/"),
+            other => panic!("unexpected {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert!(copied.contains(&"SKILL.md".to_string()));
+    assert!(copied.contains(&"scripts/run.sh".to_string()));
+    assert!(matches!(
+        recorded[4],
+        InstallEvent::Completed { targets: 1, .. }
+    ));
+
+    install(request).unwrap();
+    let recorded = events.lock().unwrap();
+    assert_eq!(
+        recorded[2],
+        InstallEvent::Skipped {
+            provider: ProviderId::ClaudeCode,
+            destination,
+            reason: SkipReason::UpToDate,
+        }
+    );
+}
+
+#[test]
+fn public_types_round_trip_through_kebab_case_json() {
+    let fixture = make_skill_fixture();
+    let project = tempfile::tempdir().unwrap();
+    let json = serde_json::json!({
+        "source": { "local-path": fixture.path() },
+        "providers": ["claude-code"],
+        "scope": "project",
+        "project-root": project.path(),
+        "method": "copy",
+        "options": { "symlinks": "reject-escaping", "slugify-name": true }
+    });
+    let request: InstallRequest = serde_json::from_value(json).unwrap();
+    assert!(!request.force);
+    assert_eq!(request.options.symlinks, SymlinkPolicy::RejectEscaping);
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["options"]["include-ignored"],
+        false
+    );
+
+    let result = install(request).unwrap();
+    let value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value["skill-name"], "demo-skill");
+    assert_eq!(
+        value["installed-targets"][0]["requested-provider"],
+        "claude-code"
+    );
+    assert_eq!(value["installed-targets"][0]["stats"]["up-to-date"], false);
+    let back: InstallResult = serde_json::from_value(value).unwrap();
+    assert_eq!(
+        back.installed_targets[0].target_dir,
+        result.installed_targets[0].target_dir
+    );
+
+    let parsed = parse_skill(&SkillSource::LocalPath(fixture.path().to_path_buf())).unwrap();
+    let value = serde_json::to_value(&parsed).unwrap();
+    assert_eq!(value["allowed-tools"], serde_json::Value::Null);
+    assert_eq!(value["files"][0]["kind"], "markdown");
+    let back: ParsedSkill = serde_json::from_value(value).unwrap();
+    assert_eq!(back.name, parsed.name);
+
+    assert_eq!(serde_json::to_value(Scope::User).unwrap(), "user");
+    assert_eq!(
+        serde_json::to_value(DetectionConfidence::High).unwrap(),
+        "high"
+    );
+}
+
+#[test]
+fn install_result_renders_text_json_and_yaml() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let result = install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+
+    let text = result.render(OutputFormat::Text);
+    assert!(text.starts_with("installed skill: demo-skill\n"));
+    assert!(text.contains("  claude-code -> claude-code ("));
+
+    let json: serde_json::Value = serde_json::from_str(&result.render(OutputFormat::Json)).unwrap();
+    assert_eq!(json["schema-version"], SCHEMA_VERSION);
+    assert_eq!(json["skill-name"], "demo-skill");
+
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&result.render(OutputFormat::Yaml)).unwrap();
+    assert_eq!(yaml["schema-version"], serde_yaml::Value::from(1));
+    assert_eq!(
+        yaml["installed-targets"][0]["target-provider"],
+        "claude-code"
+    );
+}
+
+#[test]
+fn machine_reports_carry_schema_version() {
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".cursor")).unwrap();
+
+    let detect = Versioned::new(DetectReport {
+        detected: detect_providers(Some(project.path())),
+    });
+    let json: serde_json::Value = serde_json::from_str(&detect.to_json()).unwrap();
+    assert_eq!(json["schema-version"], SCHEMA_VERSION);
+    assert!(json["detected"].is_array());
+
+    let providers = Versioned::new(ProviderListReport {
+        providers: supported_providers().iter().map(Into::into).collect(),
+    });
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&providers.to_yaml()).unwrap();
+    assert_eq!(
+        yaml["schema-version"],
+        serde_yaml::Value::from(SCHEMA_VERSION)
+    );
+    assert_eq!(yaml["providers"][0]["id"], "amp");
+
+    let back: Versioned<ProviderListReport> = serde_json::from_str(&providers.to_json()).unwrap();
+    assert_eq!(back.schema_version, SCHEMA_VERSION);
+    assert_eq!(back.report.providers.len(), supported_providers().len());
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_interactive_with_uses_custom_prompter() {
+    use skillinstaller::{install_interactive_with, Prompter, ProviderPrompt};
+
+    #[derive(Default)]
+    struct Scripted {
+        asked: Vec<String>,
+    }
+
+    impl Prompter for Scripted {
+        fn select(&mut self, message: &str, _: &[&str], _: usize) -> skillinstaller::Result<usize> {
+            self.asked.push(message.to_string());
+            // Project scope, then copy.
+            Ok(self.asked.len() - 2)
+        }
+
+        fn multi_select(
+            &mut self,
+            prompt: &ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            self.asked.push(prompt.message.to_string());
+            assert!(prompt.choices.contains(&ProviderId::ClaudeCode));
+            assert!(!prompt.choices.contains(&ProviderId::Universal));
+            assert_eq!(
+                prompt.preview.map(|skill| skill.name.as_str()),
+                Some("demo-skill")
+            );
+            Ok(vec![ProviderId::ClaudeCode])
+        }
+
+        fn confirm(&mut self, message: &str, default: bool) -> skillinstaller::Result<bool> {
+            // Nothing to overwrite, so only the final summary asks.
+            assert!(default);
+            self.asked.push(message.to_string());
+            Ok(true)
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!("no variables")
+        }
+    }
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--no-remember",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+
+    let mut prompter = Scripted::default();
+    let result = install_interactive_with(
+        SkillSource::LocalPath(fixture.path().to_path_buf()),
+        &cli.args,
+        &mut prompter,
+    )
+    .unwrap();
+
+    assert_eq!(prompter.asked.len(), 4);
+    assert!(prompter.asked[1].contains("Installation scope"));
+    assert!(prompter.asked[3].contains("Install demo-skill"));
+    assert!(prompter.asked[3].contains(".claude/skills/demo-skill"));
+    // `.agents` providers stay locked in through `universal`.
+    let requested = result
+        .installed_targets
+        .iter()
+        .map(|t| t.requested_provider)
+        .collect::<Vec<_>>();
+    assert_eq!(requested, [ProviderId::ClaudeCode, ProviderId::Universal]);
+    let installed = project.path().join(".claude/skills/demo-skill");
+    assert!(!fs::symlink_metadata(&installed)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_interactive_summary_confirms_or_cancels() {
+    use skillinstaller::{install_interactive_with, Prompter, ProviderPrompt};
+
+    struct Scripted {
+        answer: bool,
+        selects: usize,
+        summaries: Vec<String>,
+    }
+
+    impl Prompter for Scripted {
+        fn select(&mut self, _: &str, _: &[&str], _: usize) -> skillinstaller::Result<usize> {
+            // Project scope, then copy.
+            self.selects += 1;
+            Ok(self.selects - 1)
+        }
+
+        fn multi_select(
+            &mut self,
+            _: &ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            Ok(vec![ProviderId::ClaudeCode])
+        }
+
+        fn confirm(&mut self, message: &str, _: bool) -> skillinstaller::Result<bool> {
+            self.summaries.push(message.to_string());
+            Ok(self.answer)
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!("no variables")
+        }
+    }
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--no-remember",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+    let source = SkillSource::LocalPath(fixture.path().to_path_buf());
+    let installed = project.path().join(".claude/skills/demo-skill");
+
+    let mut cancel = Scripted {
+        answer: false,
+        selects: 0,
+        summaries: Vec::new(),
+    };
+    let err = install_interactive_with(source.clone(), &cli.args, &mut cancel).unwrap_err();
+    assert!(matches!(err, InstallerError::PromptCancelled));
+    assert_eq!(cancel.summaries.len(), 1);
+    assert!(cancel.summaries[0].contains("Install demo-skill"));
+    assert!(!project.path().join(".claude").exists());
+    assert!(!project.path().join(".agents").exists());
+
+    let mut confirm = Scripted {
+        answer: true,
+        selects: 0,
+        summaries: Vec::new(),
+    };
+    install_interactive_with(source, &cli.args, &mut confirm).unwrap();
+    assert_eq!(confirm.summaries, cancel.summaries);
+    assert!(confirm.summaries[0].contains(&installed.display().to_string()));
+    assert!(installed.join("SKILL.md").is_file());
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_interactive_without_tty_reads_env_defaults() {
+    use std::io::IsTerminal;
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    if std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+        return;
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--no-remember",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+    let source = SkillSource::LocalPath(fixture.path().to_path_buf());
+
+    let err = skillinstaller::install_interactive(source.clone(), &cli.args).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("--providers (or SKILL_INSTALLER_PROVIDERS)"));
+
+    std::env::set_var("SKILL_INSTALLER_PROVIDERS", "claude-code");
+    std::env::set_var("SKILL_INSTALLER_SCOPE", "project");
+    std::env::set_var("SKILL_INSTALLER_METHOD", "Copy");
+    let result = skillinstaller::install_interactive(source.clone(), &cli.args).unwrap();
+    assert_eq!(result.installed_targets.len(), 1);
+    assert!(project
+        .path()
+        .join(".claude/skills/demo-skill/SKILL.md")
+        .is_file());
+
+    // Overwriting needs confirmation, which a headless run cannot give.
+    let err = skillinstaller::install_interactive(source, &cli.args).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("no TTY to answer 'Skill already exists at"));
+    for name in ["PROVIDERS", "SCOPE", "METHOD"] {
+        std::env::remove_var(format!("SKILL_INSTALLER_{name}"));
+    }
+}
+
+#[test]
+fn plan_lists_targets_conflicts_and_directories_before_installing() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let request = || InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode, ProviderId::Codex],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force: false,
+        options: InstallOptions::default(),
+    };
+
+    let first = plan(request()).unwrap();
+    let actions = first
+        .targets
+        .iter()
+        .map(|t| (t.requested_provider, t.action))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        actions,
+        [
+            (ProviderId::Universal, PlannedAction::Create),
+            (ProviderId::ClaudeCode, PlannedAction::Link),
+        ]
+    );
+    assert_eq!(
+        first.normalized_providers,
+        [(ProviderId::Codex, ProviderId::Universal)]
+    );
+    assert!(first.conflicts.is_empty());
+    assert!(first
+        .create_dirs
+        .contains(&project.path().join(".agents/skills")));
+    assert!(first.create_dirs.contains(&project.path().join(".claude")));
+    assert!(!project.path().join(".agents").exists());
+
+    let result = execute_plan(first).unwrap();
+    assert_eq!(result.installed_targets.len(), 2);
+
+    let second = plan(request()).unwrap();
+    assert!(second
+        .targets
+        .iter()
+        .all(|t| t.action == PlannedAction::Conflict));
+    assert_eq!(second.conflicts.len(), 2);
+    assert!(second.create_dirs.is_empty());
+    let json = serde_json::to_value(&second).unwrap();
+    assert_eq!(json["targets"][1]["action"], "conflict");
+    assert!(matches!(
+        execute_plan(second),
+        Err(InstallerError::AlreadyExists { .. })
+    ));
+}
+
+#[test]
+fn execute_plan_installs_the_targets_as_edited() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let request = |method| InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode, ProviderId::Windsurf],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method,
+        force: false,
+        options: InstallOptions::default(),
+    };
+    let claude = project.path().join(".claude/skills/demo-skill");
+    let windsurf = project.path().join(".windsurf/skills/demo-skill");
+
+    let mut edited = plan(request(InstallMethod::Copy)).unwrap();
+    assert_eq!(edited.targets.len(), 2);
+    edited
+        .targets
+        .retain(|t| t.requested_provider != ProviderId::Windsurf);
+    let result = execute_plan(edited).unwrap();
+    assert_eq!(result.installed_targets.len(), 1);
+    assert!(claude.join("SKILL.md").is_file());
+    assert!(!windsurf.exists());
+
+    // A conflict the caller approves is taken over without `force`; the payload is the
+    // same, so nothing is rewritten.
+    let mut edited = plan(request(InstallMethod::Copy)).unwrap();
+    edited
+        .targets
+        .retain(|t| t.requested_provider == ProviderId::ClaudeCode);
+    assert_eq!(edited.targets[0].action, PlannedAction::Conflict);
+    edited.targets[0].action = PlannedAction::Overwrite;
+    let result = execute_plan(edited).unwrap();
+    assert!(result.installed_targets[0].stats.up_to_date);
+
+    // Without the shared copy, a symlink plan copies into each remaining target.
+    let mut edited = plan(request(InstallMethod::Symlink)).unwrap();
+    assert_eq!(edited.targets[0].requested_provider, ProviderId::Universal);
+    edited.targets.remove(0);
+    edited
+        .targets
+        .retain(|t| t.requested_provider == ProviderId::Windsurf);
+    let result = execute_plan(edited).unwrap();
+    assert_eq!(result.installed_targets.len(), 1);
+    assert!(!fs::symlink_metadata(&windsurf).unwrap().is_symlink());
+    assert!(windsurf.join("SKILL.md").is_file());
+    assert!(!project.path().join(".agents").exists());
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn provider_selection_options_builder_limits_choices_to_preselected() {
+    use skillinstaller::{
+        prompt_provider_selection_with, InteractiveProviderSelectionOptions, Prompter,
+        ProviderPrompt,
+    };
+
+    struct Inspect;
+
+    impl Prompter for Inspect {
+        fn select(&mut self, _: &str, _: &[&str], _: usize) -> skillinstaller::Result<usize> {
+            unreachable!()
+        }
+
+        fn multi_select(
+            &mut self,
+            prompt: &ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            assert_eq!(prompt.locked_label, "Shared");
+            assert_eq!(prompt.choices, [ProviderId::ClaudeCode]);
+            assert_eq!(prompt.defaults, [ProviderId::ClaudeCode]);
+            Ok(prompt.defaults.to_vec())
+        }
+
+        fn confirm(&mut self, _: &str, _: bool) -> skillinstaller::Result<bool> {
+            unreachable!()
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!()
+        }
+    }
+
+    let project = TempDir::new().unwrap();
+    let options = InteractiveProviderSelectionOptions::new()
+        .project_root(project.path())
+        .candidates(vec![
+            ProviderId::ClaudeCode,
+            ProviderId::Cursor,
+            ProviderId::Codex,
+        ])
+        .defaults(vec![ProviderId::ClaudeCode])
+        .exclude(vec![ProviderId::Cursor])
+        .preselected_only(true)
+        .locked_label("Shared");
+    let selection = prompt_provider_selection_with(&mut Inspect, options).unwrap();
+
+    assert_eq!(selection.universal_locked, [ProviderId::Codex]);
+    assert_eq!(
+        selection.selected,
+        [ProviderId::ClaudeCode, ProviderId::Universal]
+    );
+}
+
+#[cfg(feature = "interactive")]
+#[test]
+fn try_load_embedded_skill_reports_missing_skill_md() {
+    use skillinstaller::{try_load_embedded_skill, Embed};
+
+    #[derive(Embed)]
+    #[folder = "tests/fixtures/embedded-skill/"]
+    struct Assets;
+
+    #[derive(Embed)]
+    #[folder = "tests/fixtures/embedded-skill/scripts/"]
+    struct NoSkill;
+
+    let SkillSource::Embedded(embedded) = try_load_embedded_skill::<Assets>().unwrap() else {
+        panic!("expected an embedded source");
+    };
+    assert!(embedded.skill_md.contains("name: embedded-demo"));
+    assert_eq!(
+        embedded.files,
+        [(
+            Path::new("scripts/run.sh").to_path_buf(),
+            b"echo hi\n".to_vec()
+        )]
+    );
+
+    let err = try_load_embedded_skill::<NoSkill>().unwrap_err();
+    assert!(matches!(err, InstallerError::MissingEmbeddedSkill));
+    assert_eq!(err.code(), "E_MISSING_EMBEDDED_SKILL");
+}
+
+#[cfg(feature = "interactive")]
+#[test]
+fn load_embedded_skills_discovers_each_skill_directory() {
+    use skillinstaller::{load_embedded_skills, Embed};
+
+    #[derive(Embed)]
+    #[folder = "tests/fixtures/embedded-bundle/"]
+    struct Bundle;
+
+    let skills = load_embedded_skills::<Bundle>().unwrap();
+    let parsed = skills
+        .iter()
+        .map(|source| parse_skill(source).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(parsed[0].name, "alpha");
+    assert_eq!(parsed[1].name, "beta");
+    assert_eq!(skills.len(), 2);
+
+    let SkillSource::Embedded(alpha) = &skills[0] else {
+        panic!("expected an embedded source");
+    };
+    assert_eq!(
+        alpha.files,
+        [(
+            Path::new("references/guide.md").to_path_buf(),
+            b"# Guide\n".to_vec()
+        )]
+    );
+    let SkillSource::Embedded(beta) = &skills[1] else {
+        panic!("expected an embedded source");
+    };
+    assert!(beta.files.is_empty());
+}
+
+#[test]
+fn embedded_skill_from_files_without_an_embed_crate() {
+    const FILES: &[(&str, &[u8])] = &[
+        (
+            "SKILL.md",
+            b"---\nname: static-skill\ndescription: From a slice\n---\n\nBody\n",
+        ),
+        ("scripts/run.sh", b"echo hi\n"),
+    ];
+    let embedded = EmbeddedSkill::from_files(FILES).unwrap();
+    assert_eq!(embedded.files.len(), 1);
+    let parsed = parse_skill(&SkillSource::Embedded(embedded)).unwrap();
+    assert_eq!(parsed.name, "static-skill");
+
+    let missing = EmbeddedSkill::from_files(&FILES[1..]).unwrap_err();
+    assert!(matches!(missing, InstallerError::MissingEmbeddedSkill));
+    let invalid = EmbeddedSkill::from_files(&[("SKILL.md", [0xff, 0xfe].as_slice())]).unwrap_err();
+    assert_eq!(invalid.code(), "E_INVALID_UTF8");
+}
+
+#[cfg(feature = "include-dir")]
+#[test]
+fn embedded_skill_from_include_dir() {
+    static BUNDLE: include_dir::Dir<'_> =
+        include_dir::include_dir!("$CARGO_MANIFEST_DIR/tests/fixtures/embedded-bundle");
+
+    let alpha = EmbeddedSkill::from_include_dir(BUNDLE.get_dir("alpha").unwrap()).unwrap();
+    assert!(alpha.skill_md.contains("name: alpha"));
+    assert_eq!(
+        alpha.files,
+        [(
+            Path::new("references/guide.md").to_path_buf(),
+            b"# Guide\n".to_vec()
+        )]
+    );
+}
+
+#[test]
+fn validate_embedded_dir_fails_on_lint_errors() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let parsed =
+        skillinstaller::build::validate_embedded_dir(fixtures.join("embedded-skill")).unwrap();
+    assert_eq!(parsed.name, "embedded-demo");
+    assert_eq!(parsed.files.len(), 2);
+
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("SKILL.md"),
+        "---\nname: no-description\n---\n\nBody\n",
+    )
+    .unwrap();
+    let err = skillinstaller::build::validate_embedded_dir(temp.path()).unwrap_err();
+    assert_eq!(err.code(), "E_LINT_FAILED");
+    assert!(err.to_string().contains("missing-description"));
+
+    let err =
+        skillinstaller::build::validate_embedded_dir(fixtures.join("embedded-bundle")).unwrap_err();
+    assert!(matches!(err, InstallerError::MissingEmbeddedSkill));
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_interactive_with_options_customizes_and_hides_steps() {
+    use skillinstaller::{
+        install_interactive_with_options, InteractiveInstallOptions, Prompter, ProviderPrompt,
+    };
+
+    #[derive(Default)]
+    struct Scripted {
+        asked: Vec<(String, Vec<String>, usize)>,
+    }
+
+    impl Prompter for Scripted {
+        fn select(
+            &mut self,
+            message: &str,
+            options: &[&str],
+            default: usize,
+        ) -> skillinstaller::Result<usize> {
+            let options = options.iter().map(|o| o.to_string()).collect();
+            self.asked.push((message.to_string(), options, default));
+            Ok(default)
+        }
+
+        fn multi_select(
+            &mut self,
+            prompt: &ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            assert!(prompt.fullscreen);
+            self.asked.push((prompt.message.to_string(), Vec::new(), 0));
+            Ok(vec![ProviderId::ClaudeCode])
+        }
+
+        fn confirm(&mut self, _: &str, _: bool) -> skillinstaller::Result<bool> {
+            unreachable!("nothing to overwrite")
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!("no variables")
+        }
+    }
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--no-remember",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+
+    let options = InteractiveInstallOptions::new()
+        .providers_message("Pick agents")
+        .ask_scope(false)
+        .method_message("How?")
+        .method_labels("Link", "Copy")
+        .default_method(InstallMethod::Copy)
+        .confirm_summary(false)
+        .fullscreen(true);
+    let mut prompter = Scripted::default();
+    install_interactive_with_options(
+        SkillSource::LocalPath(fixture.path().to_path_buf()),
+        &cli.args,
+        &options,
+        Some(&mut prompter),
+    )
+    .unwrap();
+
+    let messages = prompter
+        .asked
+        .iter()
+        .map(|(message, _, _)| message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(messages, ["Pick agents", "How?"]);
+    assert_eq!(prompter.asked[1].1, ["Link", "Copy"]);
+    assert_eq!(prompter.asked[1].2, 1);
+    let installed = project.path().join(".claude/skills/demo-skill");
+    assert!(!fs::symlink_metadata(&installed)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_interactive_goes_back_to_previous_steps() {
+    use skillinstaller::{install_interactive_with, Prompter, ProviderPrompt};
+
+    #[derive(Default)]
+    struct Scripted {
+        asked: Vec<String>,
+        backs: usize,
+    }
+
+    impl Prompter for Scripted {
+        fn select(&mut self, message: &str, _: &[&str], _: usize) -> skillinstaller::Result<usize> {
+            self.asked.push(message.to_string());
+            // Back from the method prompt, then back again from the scope prompt.
+            if self.backs < 2 && self.asked.len() >= 3 {
+                self.backs += 1;
+                return Err(InstallerError::PromptBack);
+            }
+            Ok(0)
+        }
+
+        fn multi_select(
+            &mut self,
+            prompt: &ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            self.asked.push(prompt.message.to_string());
+            if self.backs > 0 {
+                assert_eq!(prompt.defaults, [ProviderId::ClaudeCode]);
+            }
+            Ok(vec![ProviderId::ClaudeCode])
+        }
+
+        fn confirm(&mut self, _: &str, _: bool) -> skillinstaller::Result<bool> {
+            Ok(true)
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!("no variables")
+        }
+    }
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--no-remember",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+
+    let mut prompter = Scripted::default();
+    install_interactive_with(
+        SkillSource::LocalPath(fixture.path().to_path_buf()),
+        &cli.args,
+        &mut prompter,
+    )
+    .unwrap();
+
+    let steps = prompter
+        .asked
+        .iter()
+        .map(|message| message.trim_start_matches('◆').trim())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        steps,
+        [
+            "Select providers to install to",
+            "Installation scope",
+            "Installation method",
+            "Installation scope",
+            "Select providers to install to",
+            "Installation scope",
+            "Installation method",
+        ]
+    );
+    assert!(project.path().join(".claude/skills/demo-skill").exists());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn color_flag_parses_and_always_or_never_ignore_the_terminal() {
+    assert!(skillinstaller::ColorChoice::Always.enabled());
+    assert!(!skillinstaller::ColorChoice::Never.enabled());
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+    let cli = <Cli as clap::Parser>::parse_from(["install-skill"]);
+    assert_eq!(cli.args.color, skillinstaller::ColorChoice::Auto);
+    let cli = <Cli as clap::Parser>::parse_from(["install-skill", "--color", "never"]);
+    assert_eq!(cli.args.color, skillinstaller::ColorChoice::Never);
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_interactive_remembers_last_answers() {
+    use skillinstaller::{install_interactive_with, last_used_path, Prompter, ProviderPrompt};
+
+    /// Picks Claude Code and copy on the first run, then accepts every default.
+    struct Scripted {
+        first_run: bool,
+        defaults: Vec<ProviderId>,
+        method_default: usize,
+    }
+
+    impl Prompter for Scripted {
+        fn select(
+            &mut self,
+            message: &str,
+            _: &[&str],
+            default: usize,
+        ) -> skillinstaller::Result<usize> {
+            if !message.contains("method") {
+                return Ok(0);
+            }
+            self.method_default = default;
+            Ok(if self.first_run { 1 } else { default })
+        }
+
+        fn multi_select(
+            &mut self,
+            prompt: &ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            self.defaults = prompt.defaults.to_vec();
+            Ok(if self.first_run {
+                vec![ProviderId::ClaudeCode]
+            } else {
+                prompt.defaults.to_vec()
+            })
+        }
+
+        fn confirm(&mut self, _: &str, _: bool) -> skillinstaller::Result<bool> {
+            Ok(true)
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!("no variables")
+        }
+    }
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let state = TempDir::new().unwrap();
+    std::env::set_var("XDG_STATE_HOME", state.path());
+    let fixture = make_skill_fixture();
+    let run = |first_run: bool, extra: &[&str]| {
+        let project = TempDir::new().unwrap();
+        let mut argv = vec![
+            "install-skill",
+            "--project-root",
+            project.path().to_str().unwrap(),
+        ];
+        argv.extend(extra);
+        let cli = <Cli as clap::Parser>::parse_from(argv);
+        let mut prompter = Scripted {
+            first_run,
+            defaults: Vec::new(),
+            method_default: 0,
+        };
+        install_interactive_with(
+            SkillSource::LocalPath(fixture.path().to_path_buf()),
+            &cli.args,
+            &mut prompter,
+        )
+        .unwrap();
+        prompter
+    };
+
+    run(true, &[]);
+    assert!(last_used_path().unwrap().starts_with(state.path()));
+    assert!(last_used_path().unwrap().exists());
+
+    let second = run(false, &[]);
+    assert_eq!(second.defaults, [ProviderId::ClaudeCode]);
+    assert_eq!(second.method_default, 1);
+
+    let forgetful = run(false, &["--no-remember"]);
+    assert_eq!(forgetful.method_default, 0);
+    std::env::remove_var("XDG_STATE_HOME");
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_interactive_reports_progress_to_prompter() {
+    use std::sync::{Arc, Mutex};
+
+    use skillinstaller::{
+        install_interactive_with, InstallEvent, InstallObserver, PlannedTarget, Prompter,
+        ProviderPrompt,
+    };
+
+    #[derive(Default)]
+    struct Progress {
+        targets: Vec<ProviderId>,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Prompter for Progress {
+        fn select(&mut self, _: &str, _: &[&str], _: usize) -> skillinstaller::Result<usize> {
+            unreachable!("all answers passed as flags")
+        }
+
+        fn multi_select(
+            &mut self,
+            _: &ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            unreachable!("all answers passed as flags")
+        }
+
+        fn confirm(&mut self, _: &str, _: bool) -> skillinstaller::Result<bool> {
+            Ok(true)
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!("no variables")
+        }
+
+        fn progress(
+            &mut self,
+            targets: &[PlannedTarget],
+            install: &mut dyn FnMut(
+                Option<Arc<dyn InstallObserver>>,
+            ) -> skillinstaller::Result<InstallResult>,
+        ) -> skillinstaller::Result<InstallResult> {
+            self.targets = targets.iter().map(|t| t.requested_provider).collect();
+            let events = self.events.clone();
+            install(Some(Arc::new(move |event: &InstallEvent| {
+                if let InstallEvent::ProviderResolved { provider, .. } = event {
+                    events.lock().unwrap().push(provider.as_str().to_string());
+                }
+            })))
+        }
+    }
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--providers",
+        "claude-code",
+        "--scope",
+        "project",
+        "--method",
+        "copy",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+
+    let mut prompter = Progress::default();
+    install_interactive_with(
+        SkillSource::LocalPath(fixture.path().to_path_buf()),
+        &cli.args,
+        &mut prompter,
+    )
+    .unwrap();
+
+    assert_eq!(prompter.targets, [ProviderId::ClaudeCode]);
+    assert_eq!(*prompter.events.lock().unwrap(), ["claude-code"]);
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn line_prompter_answers_install_prompts_from_text_input() {
+    use skillinstaller::{install_interactive_with, LinePrompter};
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--no-remember",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+
+    // An unknown answer is asked again; `n` keeps only the universal providers.
+    let input = std::io::Cursor::new("x\nn\n1\n2\ny\n");
+    let mut prompter = LinePrompter::new(input, Vec::new());
+    install_interactive_with(
+        SkillSource::LocalPath(fixture.path().to_path_buf()),
+        &cli.args,
+        &mut prompter,
+    )
+    .unwrap();
+
+    let (_, output) = prompter.into_inner();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Unknown choice 'x'."));
+    assert!(output.contains(" * 1) Project"));
+    let installed = project.path().join(".agents/skills/demo-skill");
+    assert!(!fs::symlink_metadata(&installed).unwrap().is_symlink());
+
+    let mut prompter = LinePrompter::new(std::io::Cursor::new("n\n"), Vec::new());
+    let err = install_interactive_with(
+        SkillSource::LocalPath(fixture.path().to_path_buf()),
+        &cli.args,
+        &mut prompter,
+    )
+    .unwrap_err();
+    assert!(matches!(err, InstallerError::PromptCancelled));
+}
+
+#[cfg(feature = "interactive")]
+#[test]
+fn prompt_primitives_require_a_terminal() {
+    use skillinstaller::{prompt_confirm, prompt_text};
+    use std::io::IsTerminal;
+
+    if std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+        return;
+    }
+
+    let no_spaces = |answer: &str| {
+        if answer.contains(' ') {
+            Err("no spaces".to_string())
+        } else {
+            Ok(())
+        }
+    };
+    let err = prompt_text("Team", Some("platform"), Some(&no_spaces)).unwrap_err();
+    assert_eq!(err.code(), "E_PROMPT");
+    let err = prompt_confirm("Continue?", true).unwrap_err();
+    assert_eq!(err.code(), "E_PROMPT");
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn provider_picker_lists_detected_providers_in_their_own_section() {
+    use skillinstaller::{install_interactive_with, LinePrompter};
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".roo/skills")).unwrap();
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--no-remember",
+        "--scope",
+        "project",
+        "--method",
+        "copy",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+
+    let mut prompter = LinePrompter::new(std::io::Cursor::new("n\ny\n"), Vec::new());
+    install_interactive_with(
+        SkillSource::LocalPath(fixture.path().to_path_buf()),
+        &cli.args,
+        &mut prompter,
+    )
+    .unwrap();
+
+    let (_, output) = prompter.into_inner();
+    let output = String::from_utf8(output).unwrap();
+    let detected = output.find("Detected on this machine").unwrap();
+    let others = output.find("Other agents").unwrap();
+    let roo = output.find(") Roo Code").unwrap();
+    assert!(detected < roo && roo < others, "{output}");
+    let reason = format!(
+        ") Roo Code (found {})",
+        project.path().join(".roo/skills").display()
+    );
+    assert!(output.contains(&reason), "{output}");
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_interactive_resolves_each_existing_destination() {
+    use skillinstaller::{install_interactive_with, Prompter, ProviderPrompt};
+
+    /// Skips Claude Code's copy and backs up every other one.
+    struct Scripted;
+
+    impl Prompter for Scripted {
+        fn select(
+            &mut self,
+            message: &str,
+            options: &[&str],
+            _: usize,
+        ) -> skillinstaller::Result<usize> {
+            assert!(message.contains("already exists at"), "{message}");
+            let wanted = if message.contains(".claude") {
+                "Skip"
+            } else {
+                "Back up, then overwrite"
+            };
+            Ok(options.iter().position(|o| *o == wanted).unwrap())
+        }
+
+        fn multi_select(
+            &mut self,
+            _: &ProviderPrompt<'_>,
+        ) -> skillinstaller::Result<Vec<ProviderId>> {
+            unreachable!("providers are preset")
+        }
+
+        fn confirm(&mut self, _: &str, _: bool) -> skillinstaller::Result<bool> {
+            Ok(true)
+        }
+
+        fn text(&mut self, _: &str, _: Option<&str>) -> skillinstaller::Result<String> {
+            unreachable!("no variables")
+        }
+    }
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: skillinstaller::InstallSkillArgs,
+    }
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    install(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode, ProviderId::Windsurf],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+    let claude = project.path().join(".claude/skills/demo-skill");
+    let windsurf = project.path().join(".windsurf/skills/demo-skill");
+    fs::write(claude.join("local.md"), "keep").unwrap();
+    fs::write(windsurf.join("local.md"), "keep").unwrap();
+
+    let cli = <Cli as clap::Parser>::parse_from([
+        "install-skill",
+        "--no-remember",
+        "--providers",
+        "claude-code,windsurf",
+        "--scope",
+        "project",
+        "--method",
+        "copy",
+        "--project-root",
+        project.path().to_str().unwrap(),
+    ]);
+    let result = install_interactive_with(
+        SkillSource::LocalPath(fixture.path().to_path_buf()),
+        &cli.args,
+        &mut Scripted,
+    )
+    .unwrap();
+
+    assert_eq!(result.installed_targets.len(), 1);
+    assert!(claude.join("local.md").exists());
+    assert!(!windsurf.join("local.md").exists());
+    let backup = project.path().join(".windsurf/skills/demo-skill.bak");
+    assert_eq!(fs::read_to_string(backup.join("local.md")).unwrap(), "keep");
+    assert!(result.warnings.iter().any(|w| w.starts_with("backed up")));
+}
+
+#[cfg(feature = "prompts")]
+#[test]
+fn install_options_take_their_wording_from_the_locale() {
+    use skillinstaller::{InteractiveInstallOptions, Locale, Messages};
+
+    let options = InteractiveInstallOptions::new().locale(Locale::De);
+    assert_eq!(options.scope_message, Messages::DE.scope_prompt);
+    assert_eq!(options.locale, Some(Locale::De));
+
+    static PIRATE: Messages = Messages {
+        user_scope: "The seven seas",
+        ..Messages::EN
+    };
+    let options = InteractiveInstallOptions::new()
+        .locale(Locale::Custom(&PIRATE))
+        .scope_labels("Ship", "Fleet");
+    assert_eq!(options.project_scope_label, "Ship");
+    assert_eq!(options.method_message, Messages::EN.method_prompt);
+    assert_eq!(
+        Locale::Custom(&PIRATE).messages().user_scope,
+        "The seven seas"
+    );
+    assert_eq!(Locale::default().messages(), &Messages::EN);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_format_json_prints_a_versioned_report_on_stdout() {
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".roo/skills")).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .args(["detect", "--format", "json", "--project-root"])
+        .arg(project.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["schema-version"], SCHEMA_VERSION);
+    assert!(report["detected"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|d| d["provider"] == "roo"));
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .args(["--format", "json", "providers"])
+        .output()
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["providers"].as_array().unwrap().len() > 1);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_dry_run_prints_the_plan_without_installing() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .args(["install", "--dry-run", "--no-remember"])
+        .args(["--providers", "cursor,claude-code", "--scope", "project"])
+        .args(["--method", "copy", "--format", "json", "--source"])
+        .arg(fixture.path())
+        .arg("--project-root")
+        .arg(project.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan["skill-name"], "demo-skill");
+    assert_eq!(plan["targets"].as_array().unwrap().len(), 2);
+    assert_eq!(plan["normalized-providers"][0][1], "universal");
+    assert!(!project.path().join(".agents").exists());
+    assert!(!project.path().join(".claude").exists());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_yes_installs_without_prompting_and_overwrites() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let existing = project.path().join(".windsurf/skills/demo-skill");
+    fs::create_dir_all(&existing).unwrap();
+    fs::write(existing.join("local.md"), "old").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .args([
+            "install",
+            "--yes",
+            "--no-remember",
+            "--providers",
+            "windsurf",
+        ])
+        .arg("--source")
+        .arg(fixture.path())
+        .arg("--project-root")
+        .arg(project.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(fs::symlink_metadata(&existing)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert!(project
+        .path()
+        .join(".agents/skills/demo-skill/SKILL.md")
+        .exists());
+}
+
+#[test]
+fn log_observer_lines_follow_the_verbosity() {
+    assert_eq!(Verbosity::from_flags(true, 2), Verbosity::Quiet);
+    assert_eq!(Verbosity::from_flags(false, 0), Verbosity::Normal);
+    assert_eq!(Verbosity::from_flags(false, 1), Verbosity::Verbose);
+    assert_eq!(Verbosity::from_flags(false, 3), Verbosity::Debug);
+
+    let resolved = InstallEvent::ProviderResolved {
+        provider: ProviderId::ClaudeCode,
+        destination: "/p/.claude/skills/demo".into(),
+    };
+    let copying = InstallEvent::Copying {
+        provider: ProviderId::ClaudeCode,
+        file: "SKILL.md".into(),
+        bytes: 12,
+    };
+    let verbose = LogObserver::new(Verbosity::Verbose);
+    assert_eq!(
+        verbose.line(&resolved).as_deref(),
+        Some("claude-code: /p/.claude/skills/demo")
+    );
+    assert_eq!(verbose.line(&copying), None);
+    assert_eq!(
+        LogObserver::new(Verbosity::Debug).line(&copying).as_deref(),
+        Some("claude-code: copy SKILL.md")
+    );
+    assert_eq!(LogObserver::new(Verbosity::Normal).line(&resolved), None);
+}
+
+#[test]
+fn installer_errors_map_to_documented_exit_codes() {
+    let exists = InstallerError::AlreadyExists {
+        path: "/p/.claude/skills/demo".into(),
+    };
+    assert_eq!(exists.exit_code(), 3);
+    assert_eq!(
+        InstallerError::InvalidSource { path: "/p".into() }.exit_code(),
+        2
+    );
+    assert_eq!(
+        InstallerError::UnsupportedProvider {
+            provider: "nope".into(),
+            suggestion: None,
+        }
+        .exit_code(),
+        4
+    );
+    assert_eq!(InstallerError::PromptCancelled.exit_code(), 5);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_exits_with_the_error_category() {
+    let empty = TempDir::new().unwrap();
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .args([
+            "install",
+            "--yes",
+            "--no-remember",
+            "--providers",
+            "claude-code",
+        ])
+        .arg("--source")
+        .arg(empty.path())
+        .arg("--project-root")
+        .arg(empty.path())
+        .output()
+        .unwrap()
+        .status;
+    assert_eq!(status.code(), Some(2));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_takes_defaults_from_env_then_project_then_user_config() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    fs::create_dir_all(config_home.path().join("skill-installer")).unwrap();
+    fs::write(
+        config_home.path().join("skill-installer/config.toml"),
+        "[defaults]\nproviders = [\"claude-code\"]\nscope = \"user\"\nmethod = \"copy\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project.path().join(".skill-installer.toml"),
+        "[defaults]\nscope = \"project\"\n",
+    )
+    .unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .current_dir(project.path())
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .env("SKILL_INSTALLER_METHOD", "symlink")
+        .args(["install", "--dry-run", "--no-remember", "--format", "json"])
+        .arg("--source")
+        .arg(fixture.path())
+        .arg("--project-root")
+        .arg(project.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let targets = plan["targets"].as_array().unwrap();
+    let link = targets
+        .iter()
+        .find(|t| t["requested-provider"] == "claude-code")
+        .unwrap();
+    assert_eq!(link["action"], "link");
+    assert!(link["destination"]
+        .as_str()
+        .unwrap()
+        .starts_with(project.path().to_str().unwrap()));
+}
+
+#[test]
+fn project_config_only_sets_defaults_and_variables() {
+    let project = TempDir::new().unwrap();
+    let config = project.path().join(".skill-installer.toml");
+    assert!(load_project_config(project.path()).is_ok());
+
+    for content in [
+        "[[provider]]\nid = \"repo-agent\"\nproject-path = \".repo/skills\"\n",
+        "[project-paths]\nclaude-code = \"elsewhere/skills\"\n",
+    ] {
+        fs::write(&config, content).unwrap();
+        let err = load_project_config(project.path()).unwrap_err();
+        assert!(matches!(err, InstallerError::ConfigError { .. }), "{err}");
+    }
+    assert!(!supported_providers()
+        .iter()
+        .any(|p| p.id.as_str() == "repo-agent"));
+
+    fs::write(
+        &config,
+        "[defaults]\nscope = \"project\"\n\n[variables]\nproject-config-owner = \"platform\"\n",
+    )
+    .unwrap();
+    load_project_config(project.path()).unwrap();
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_loads_a_config_that_refers_to_its_own_providers() {
+    let config_home = TempDir::new().unwrap();
+    fs::create_dir_all(config_home.path().join("skill-installer")).unwrap();
+    fs::write(
+        config_home.path().join("skill-installer/providers.toml"),
+        "favorites = [\"house-agent\"]\n\n[[provider]]\nid = \"house-agent\"\nproject-path = \".house/skills\"\n\n[groups]\nhouse = [\"house-agent\", \"claude\"]\n\n[defaults]\nproviders = [\"@house\"]\n",
+    )
+    .unwrap();
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .args(["providers", "--format", "json"])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let started = std::time::Instant::now();
+    while child.try_wait().unwrap().is_none() {
+        if started.elapsed() > std::time::Duration::from_secs(20) {
+            child.kill().unwrap();
+            panic!("install-skill hung while loading providers.toml");
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("house-agent"));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_installs_every_source_and_reports_each() {
+    let first = make_skill_fixture();
+    let second = TempDir::new().unwrap();
+    fs::create_dir_all(second.path().join(".skill")).unwrap();
+    fs::write(
+        second.path().join(".skill/SKILL.md"),
+        "---\nname: other-skill\ndescription: Other\n---\nBody.",
+    )
+    .unwrap();
+    let missing = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .args([
+            "install",
+            "--yes",
+            "--no-remember",
+            "--providers",
+            "claude-code",
+        ])
+        .args(["--method", "copy", "--format", "json", "--project-root"])
+        .arg(project.path())
+        .arg("--source")
+        .arg(first.path())
+        .arg("--source")
+        .arg(missing.path())
+        .arg("--source")
+        .arg(second.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names = report["installed"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["skill-name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["demo-skill", "other-skill"]);
+    assert_eq!(report["failed"][0]["code"], "E_INVALID_SOURCE");
+    assert!(project.path().join(".claude/skills/other-skill").exists());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_flags_can_come_from_the_environment() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .arg("install")
+        .env("SKILL_INSTALLER_SOURCE", fixture.path())
+        .env("SKILL_INSTALLER_PROJECT_ROOT", project.path())
+        .env("SKILL_INSTALLER_PROVIDERS", "claude-code")
+        .env("SKILL_INSTALLER_SCOPE", "project")
+        .env("SKILL_INSTALLER_METHOD", "copy")
+        .env("SKILL_INSTALLER_DRY_RUN", "true")
+        .env("SKILL_INSTALLER_NO_REMEMBER", "true")
+        .env("SKILL_INSTALLER_FORMAT", "json")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan["targets"][0]["requested-provider"], "claude-code");
+    assert!(!project.path().join(".claude").exists());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_list_environment_variables_take_several_values() {
+    let fixture = TempDir::new().unwrap();
+    fs::create_dir_all(fixture.path().join(".skill")).unwrap();
+    fs::write(
+        fixture.path().join(".skill/SKILL.md"),
+        "---\nname: templated\nvariables:\n  team: {}\n  port: {}\n---\nAsk {{team}} on port {{port}}.\n",
+    )
+    .unwrap();
+    let project = TempDir::new().unwrap();
+    let (first, second) = (project.path().join("first"), project.path().join("second"));
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .arg("install")
+        .env("SKILL_INSTALLER_SOURCE", fixture.path())
+        .env("SKILL_INSTALLER_PROJECT_ROOT", project.path())
+        .env(
+            "SKILL_INSTALLER_TARGET_DIR",
+            format!("{};{}", first.display(), second.display()),
+        )
+        .env("SKILL_INSTALLER_VAR", "team=platform,port=8080")
+        .env("SKILL_INSTALLER_SCOPE", "project")
+        .env("SKILL_INSTALLER_METHOD", "copy")
+        .env("SKILL_INSTALLER_YES", "true")
+        .env("SKILL_INSTALLER_NO_REMEMBER", "true")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    for dir in [first, second] {
+        let installed = fs::read_to_string(dir.join("templated/SKILL.md")).unwrap();
+        assert!(
+            installed.ends_with("Ask platform on port 8080.\n"),
+            "{installed}"
+        );
+    }
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_color_flag_colors_plain_output_and_honors_no_color() {
+    let run = |args: &[&str], no_color: bool| {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"));
+        command.args(args).env_remove("CLICOLOR_FORCE");
+        if no_color {
+            command.env("NO_COLOR", "1");
+        } else {
+            command.env_remove("NO_COLOR");
+        }
+        String::from_utf8(command.output().unwrap().stdout).unwrap()
+    };
+
+    assert!(run(&["providers", "--color", "always"], true).contains("\x1b[1mclaude-code\x1b[0m"));
+    assert!(!run(&["providers", "--color", "never"], false).contains('\x1b'));
+    assert!(!run(&["providers"], true).contains('\x1b'));
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let plan = plan(InstallRequest {
+        source: SkillSource::LocalPath(fixture.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Copy,
+        force: false,
+        options: InstallOptions::default(),
+    })
+    .unwrap();
+    assert!(!plan.render(OutputFormat::Text).contains('\x1b'));
+    assert!(plan
+        .render_styled(OutputFormat::Text, true)
+        .contains("\x1b[1mdemo-skill\x1b[0m"));
+}
+
+#[test]
+fn progress_observer_counts_files_and_bytes_per_destination() {
+    use skillinstaller::{InstallObserver, ProgressObserver};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<std::sync::Mutex<Vec<u8>>>);
+    impl std::io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let out = Shared::default();
+    let progress = ProgressObserver::with_writer(Box::new(out.clone()));
+    progress.on_event(&InstallEvent::SourceResolved {
+        skill: "demo-skill".into(),
+        version: None,
+        files: 2,
+    });
+    for (provider, bytes) in [
+        (ProviderId::ClaudeCode, 1000),
+        (ProviderId::ClaudeCode, 2048),
+        (ProviderId::Cursor, 10),
+    ] {
+        progress.on_event(&InstallEvent::Copying {
+            provider,
+            file: "SKILL.md".into(),
+            bytes,
+        });
+    }
+    progress.on_event(&InstallEvent::Completed {
+        skill: "demo-skill".into(),
+        targets: 2,
+        elapsed: std::time::Duration::from_millis(5),
+    });
+
+    let text = String::from_utf8(out.0.lock().unwrap().clone()).unwrap();
+    let lines = text
+        .split('\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| line.rsplit("\x1b[2K").next().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        ["claude-code: 2/2 files, 3.0 KiB", "cursor: 1/2 files, 10 B"]
+    );
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_doctor_reports_config_errors_and_broken_links() {
+    let home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    let doctor = || {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+            .args(["--format", "json", "doctor", "--project-root"])
+            .arg(project.path())
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("SKILL_INSTALLER_REGISTRY")
+            .output()
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let statuses = report["checks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| {
+                (
+                    c["check"].as_str().unwrap().to_string(),
+                    c["status"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        (output.status.code(), statuses, report)
+    };
+
+    let (code, statuses, _) = doctor();
+    assert_eq!(code, Some(0));
+    assert!(statuses.contains(&("config".to_string(), "ok".to_string())));
+    assert!(statuses.contains(&("links".to_string(), "ok".to_string())));
+    assert!(statuses.contains(&("providers".to_string(), "warning".to_string())));
+
+    let skills = project.path().join(".claude/skills");
+    fs::create_dir_all(&skills).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(project.path().join("gone"), skills.join("gone")).unwrap();
+    let config = home.path().join(".config/skill-installer");
+    fs::create_dir_all(&config).unwrap();
+    fs::write(config.join("config.toml"), "favorites = 3\n").unwrap();
+
+    let (code, statuses, report) = doctor();
+    assert_eq!(code, Some(1), "{report}");
+    assert!(statuses.contains(&("config".to_string(), "error".to_string())));
+    assert!(statuses.contains(&("providers".to_string(), "ok".to_string())));
+    #[cfg(unix)]
+    assert!(report["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|c| c["check"] == "links"
+            && c["status"] == "error"
+            && c["message"].as_str().unwrap().contains("gone")));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_without_a_command_prints_help_when_there_is_no_terminal() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    let help = String::from_utf8(output.stderr).unwrap();
+    assert!(help.contains("Usage: install-skill [OPTIONS] [COMMAND]"));
+    assert!(help.contains("uninstall"));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_providers_prints_an_aligned_table_with_wide_columns() {
+    let run = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+            .args(args)
+            .env("NO_COLOR", "1")
+            .env("HOME", "/home/tester")
+            .env_remove("CLAUDE_CONFIG_DIR")
+            .env_remove("SKILL_INSTALLER_CLAUDE_CODE_DIR")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let table = run(&["providers"]);
+    let mut lines = table.lines();
+    let header = lines.next().unwrap();
+    assert!(header.starts_with("PROVIDER "));
+    let user_column = header.find("USER PATH").unwrap();
+    let claude = table
+        .lines()
+        .find(|line| line.starts_with("claude-code "))
+        .unwrap();
+    assert_eq!(
+        &claude[user_column..][.."~/.claude/skills".len()],
+        "~/.claude/skills"
+    );
+    assert!(!table.contains('\t'));
+    assert!(!header.contains("SYMLINKS"));
+
+    let wide = run(&["providers", "--wide"]);
+    assert!(wide.lines().next().unwrap().contains("USER PATH VARIABLE"));
+    assert!(wide.contains("SKILL_INSTALLER_CLAUDE_CODE_DIR"));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_reads_sources_from_a_file_or_stdin() {
+    use std::io::Write;
+
+    let first = make_skill_fixture();
+    let second = TempDir::new().unwrap();
+    fs::create_dir_all(second.path().join(".skill")).unwrap();
+    fs::write(
+        second.path().join(".skill/SKILL.md"),
+        "---\nname: other-skill\ndescription: Another skill\n---\nBody\n",
+    )
+    .unwrap();
+    let listed = format!(
+        "# skills for this repo\n{}\n\n  {}  \n",
+        first.path().display(),
+        second.path().display()
+    );
+
+    let install = |from_file: &str, stdin: Option<&str>| {
+        let project = TempDir::new().unwrap();
+        let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+            .args(["install", "--from-file", from_file])
+            .args(["--providers", "claude-code", "--scope", "project"])
+            .args(["--method", "copy", "--no-remember", "--project-root"])
+            .arg(project.path())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut pipe = child.stdin.take().unwrap();
+        if let Some(stdin) = stdin {
+            pipe.write_all(stdin.as_bytes()).unwrap();
+        }
+        drop(pipe);
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success(), "{output:?}");
+        let skills = project.path().join(".claude/skills");
+        assert!(skills.join("demo-skill/SKILL.md").exists());
+        assert!(skills.join("other-skill/SKILL.md").exists());
+    };
+
+    let list = TempDir::new().unwrap();
+    let list_path = list.path().join("sources.txt");
+    fs::write(&list_path, &listed).unwrap();
+    install(list_path.to_str().unwrap(), None);
+    install("-", Some(&listed));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli_log_file_records_the_run_as_json_lines() {
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    let log = project.path().join("run.log");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .arg("--log-file")
+        .arg(&log)
+        .args([
+            "install",
+            "--providers",
+            "claude-code",
+            "--scope",
+            "project",
+        ])
+        .args(["--method", "copy", "--no-remember", "--source"])
+        .arg(fixture.path())
+        .arg("--project-root")
+        .arg(project.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    let lines = fs::read_to_string(&log)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    let events = lines
+        .iter()
+        .map(|line| line["event"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(events.first(), Some(&"run-started"));
+    assert_eq!(events.last(), Some(&"run-finished"));
+    assert_eq!(lines.last().unwrap()["exit-code"], 0);
+    let resolved = lines
+        .iter()
+        .find(|line| line["event"] == "provider-resolved")
+        .unwrap();
+    assert_eq!(
+        resolved["destination"],
+        project
+            .path()
+            .join(".claude/skills/demo-skill")
+            .to_str()
+            .unwrap()
+    );
+    assert!(lines
+        .iter()
+        .any(|line| line["event"] == "copying" && line["file"] == "SKILL.md"));
+}
+
+/// Packs the skill fixture into `dir` once per version and writes an `index.json` listing them.
+#[cfg(feature = "registry")]
+fn make_registry_fixture(dir: &Path, versions: &[&str]) -> std::path::PathBuf {
+    use sha2::{Digest, Sha256};
+
+    let fixture = make_skill_fixture();
+    let skill_root = fixture.path().join(".skill");
+    let mut skills = Vec::new();
+    for version in versions {
+        fs::write(
+            skill_root.join("SKILL.md"),
+            format!("---\nname: demo-skill\ndescription: Demo\nversion: {version}\n---\nUse this skill."),
+        )
+        .unwrap();
+        let archive = format!("demo-skill-{version}.tar.gz");
+        let gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut tar = tar::Builder::new(gz);
+        tar.append_dir_all(".", &skill_root).unwrap();
+        let bytes = tar.into_inner().unwrap().finish().unwrap();
+        let hash = Sha256::digest(&bytes)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        fs::write(dir.join(&archive), bytes).unwrap();
+        skills.push(serde_json::json!({
+            "name": "demo-skill",
+            "version": version,
+            "description": "Demo",
+            "tags": ["testing"],
+            "url": archive,
+            "hash": format!("sha256:{hash}"),
+        }));
+    }
+    let index = dir.join("index.json");
+    fs::write(
+        &index,
+        serde_json::json!({ "schema-version": 1, "skills": skills }).to_string(),
+    )
+    .unwrap();
+    index
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn registry_client_resolves_versions_and_verifies_downloads() {
+    use skillinstaller::{parse_skill_spec, RegistryClient};
+
+    let registry = TempDir::new().unwrap();
+    let index_path = make_registry_fixture(registry.path(), &["1.0.0", "1.2.0", "2.0.0"]);
+    let client = RegistryClient::new(index_path.to_str().unwrap());
+    let mut index = client.fetch_index().unwrap();
+
+    let found = index.search("TEST");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].version.to_string(), "2.0.0");
+    assert!(index.search("nothing-like-this").is_empty());
+
+    let (name, req) = parse_skill_spec("demo-skill@^1").unwrap();
+    assert_eq!(
+        index
+            .resolve(&name, req.as_ref())
+            .unwrap()
+            .version
+            .to_string(),
+        "1.2.0"
+    );
+    let (name, req) = parse_skill_spec("demo-skill@1.0.0").unwrap();
+    assert_eq!(
+        index
+            .resolve(&name, req.as_ref())
+            .unwrap()
+            .version
+            .to_string(),
+        "1.0.0"
+    );
+    assert!(parse_skill_spec("demo-skill@not a version").is_err());
+
+    let unpacked = TempDir::new().unwrap();
+    let entry = index.resolve("demo-skill", None).unwrap().clone();
+    let dir = client
+        .download_to(&entry, None, &unpacked.path().join("demo"))
+        .unwrap();
+    assert!(dir.join(".skill/SKILL.md").is_file());
+    assert!(dir.join(".skill/scripts/run.sh").is_file());
+
+    // Versions that differ only in the last component don't share a staging dir.
+    let sibling = unpacked.path().join("2.0.partial");
+    fs::create_dir_all(&sibling).unwrap();
+    fs::write(sibling.join("keep"), "").unwrap();
+    client
+        .download_to(&entry, None, &unpacked.path().join("2.0.0"))
+        .unwrap();
+    assert!(sibling.join("keep").is_file());
+    assert!(!unpacked.path().join("2.0.0.partial").exists());
+
+    index.skills[0].hash = format!("sha256:{}", "0".repeat(64));
+    let err = client
+        .download_to(&index.skills[0], None, &unpacked.path().join("bad"))
+        .unwrap_err();
+    assert!(matches!(err, InstallerError::RegistryError { .. }), "{err}");
+    assert_eq!(err.exit_code(), 9);
+    assert!(!unpacked.path().join("bad").exists());
+
+    let raw = fs::read_to_string(&index_path).unwrap();
+    fs::write(
+        &index_path,
+        raw.replacen("\"demo-skill\"", "\"../escape\"", 1),
+    )
+    .unwrap();
+    let err = client.fetch_index().unwrap_err();
+    assert!(matches!(err, InstallerError::RegistryError { .. }), "{err}");
+    assert!(err.to_string().contains("../escape"), "{err}");
+
+    // A remote index can only point at archives on its own scheme.
+    let remote = RegistryClient::new("https://skills.example.com/v1/index.json");
+    assert_eq!(
+        remote.resolve_url("demo-1.0.0.tar.gz").unwrap(),
+        "https://skills.example.com/v1/demo-1.0.0.tar.gz"
+    );
+    assert_eq!(
+        remote
+            .resolve_url("https://cdn.example.com/demo.tar.gz")
+            .unwrap(),
+        "https://cdn.example.com/demo.tar.gz"
+    );
+    for url in [
+        "/etc/passwd",
+        "file:///etc/passwd",
+        "FILE:///etc/passwd",
+        "http://cdn.example.com/demo.tar.gz",
+    ] {
+        let err = remote.resolve_url(url).unwrap_err();
+        assert!(matches!(err, InstallerError::RegistryError { .. }), "{url}");
+    }
+    let mut entry = entry.clone();
+    entry.url = index_path
+        .with_file_name("demo-skill-1.0.0.tar.gz")
+        .display()
+        .to_string();
+    assert!(remote
+        .download_to(&entry, None, &unpacked.path().join("remote"))
+        .is_err());
+    assert!(!unpacked.path().join("remote").exists());
+    // A local index may name local archives.
+    assert_eq!(client.resolve_url(&entry.url).unwrap(), entry.url);
+    assert!(client.resolve_url(&format!("file://{}", entry.url)).is_ok());
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn registry_fetches_need_https_and_a_bounded_size() {
+    use skillinstaller::RegistryClient;
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let index_url = format!("http://{}/index.json", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        while line != "\r\n" {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+        }
+        let body = r#"{"schema-version":1,"skills":[]}"#;
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+    });
+
+    let err = RegistryClient::new(&index_url).fetch_index().unwrap_err();
+    assert!(matches!(err, InstallerError::RegistryError { .. }), "{err}");
+    assert!(err.to_string().contains("https"), "{err}");
+    let index = RegistryClient::new(&index_url)
+        .allow_http(true)
+        .fetch_index()
+        .unwrap();
+    assert!(index.skills.is_empty());
+    server.join().unwrap();
+
+    let registry = TempDir::new().unwrap();
+    let huge = registry.path().join("index.json");
+    fs::File::create(&huge)
+        .unwrap()
+        .set_len(64 * 1024 * 1024 + 1)
+        .unwrap();
+    let err = RegistryClient::new(huge.to_str().unwrap())
+        .fetch_index()
+        .unwrap_err();
+    assert!(err.to_string().contains("larger than 64 MiB"), "{err}");
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn cli_installs_and_searches_registry_skills() {
+    let registry = TempDir::new().unwrap();
+    let index = make_registry_fixture(registry.path(), &["1.0.0"]);
+    let home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+            .args(args)
+            .arg("--registry")
+            .arg(&index)
+            .current_dir(project.path())
+            .env("HOME", home.path())
+            .env("XDG_CACHE_HOME", home.path().join("cache"))
+            .env_remove("XDG_CONFIG_HOME")
+            .stdin(std::process::Stdio::null())
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&[
+        "install",
+        "demo-skill",
+        "--providers",
+        "claude-code",
+        "--scope",
+        "project",
+        "--method",
+        "copy",
+        "--no-remember",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let installed = project.path().join(".claude/skills/demo-skill");
+    assert!(installed.join("SKILL.md").is_file());
+    assert!(home
+        .path()
+        .join("cache/skill-installer/registry/demo-skill/1.0.0/.skill/SKILL.md")
+        .is_file());
+
+    make_registry_fixture(registry.path(), &["1.0.0", "1.1.0"]);
+    let output = run(&["--format", "json", "search", "demo"]);
+    assert!(output.status.success(), "{output:?}");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["skills"][0]["version"], "1.1.0");
+
+    let output = run(&["--format", "json", "update", "--all"]);
+    assert!(output.status.success(), "{output:?}");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["updated"][0]["skill-version"], "1.1.0");
+    assert!(fs::read_to_string(installed.join("SKILL.md"))
+        .unwrap()
+        .contains("version: 1.1.0"));
+
+    let output = run(&["update", "demo-skill"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "demo-skill is up to date\n"
+    );
+
+    let output = run(&["install", "demo-skill@3", "--yes"]);
+    assert_eq!(output.status.code(), Some(9), "{output:?}");
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn packed_skills_round_trip_through_an_index_and_upload() {
+    use skillinstaller::{pack_skill, RegistryClient};
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let fixture = make_skill_fixture();
+    assert!(matches!(
+        pack_skill(fixture.path()),
+        Err(InstallerError::LintFailed { .. })
+    ));
+    fs::write(
+        fixture.path().join(".skill/SKILL.md"),
+        "---\nname: demo-skill\ndescription: Demo\nversion: 0.3.0\nmetadata:\n  tags: pdf, forms\n---\nUse this skill.",
+    )
+    .unwrap();
+    let packed = pack_skill(fixture.path()).unwrap();
+    assert_eq!(packed.entry.url, "demo-skill-0.3.0.tar.gz");
+    assert_eq!(packed.entry.tags, ["pdf", "forms"]);
+    assert_eq!(pack_skill(fixture.path()).unwrap().archive, packed.archive);
+
+    let registry = TempDir::new().unwrap();
+    let index = packed.add_to_index(registry.path()).unwrap();
+    assert!(matches!(
+        packed.add_to_index(registry.path()),
+        Err(InstallerError::RegistryError { .. })
+    ));
+    let client = RegistryClient::new(index.to_str().unwrap());
+    let entry = client.fetch_index().unwrap().skills.remove(0);
+    let unpacked = TempDir::new().unwrap();
+    let dir = client
+        .download_to(&entry, None, &unpacked.path().join("demo"))
+        .unwrap();
+    assert!(dir.join(".skill/scripts/run.sh").is_file());
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}/skills", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut head = Vec::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+            head.push(line);
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 201 Created\r\ncontent-length: 0\r\n\r\n")
+            .unwrap();
+        (head, body)
+    });
+    let url = packed.upload(&endpoint, Some("secret")).unwrap();
+    assert_eq!(url, format!("{endpoint}/demo-skill/0.3.0"));
+    let (head, body) = server.join().unwrap();
+    assert_eq!(head[0], "PUT /skills/demo-skill/0.3.0 HTTP/1.1\r\n");
+    assert!(head
+        .iter()
+        .any(|h| h.trim() == "authorization: Bearer secret"));
+    assert!(head
+        .iter()
+        .any(|h| h.trim() == format!("x-skill-hash: {}", packed.entry.hash)));
+    assert_eq!(body, packed.archive);
+
+    // A namespaced name stays one path segment.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}/skills", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 201 Created\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+            .unwrap();
+        request_line
+    });
+    let mut namespaced = packed.clone();
+    namespaced.entry.name = "acme/demo skill".to_string();
+    let url = namespaced.upload(&endpoint, None).unwrap();
+    assert_eq!(url, format!("{endpoint}/acme%2Fdemo%20skill/0.3.0"));
+    assert_eq!(
+        server.join().unwrap(),
+        "PUT /skills/acme%2Fdemo%20skill/0.3.0 HTTP/1.1\r\n"
+    );
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn cli_publish_pushes_a_branch_to_a_git_index() {
+    let fixture = make_skill_fixture();
+    fs::write(
+        fixture.path().join(".skill/SKILL.md"),
+        "---\nname: demo-skill\ndescription: Demo\nversion: 1.0.0\n---\nUse this skill.",
+    )
+    .unwrap();
+    let repos = TempDir::new().unwrap();
+    let origin = repos.path().join("origin.git");
+    let checkout = repos.path().join("checkout");
+    let git = |dir: &Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+    git(repos.path(), &["init", "-q", "--bare", "origin.git"]);
+    git(repos.path(), &["clone", "-q", "origin.git", "checkout"]);
+    git(&checkout, &["commit", "-q", "--allow-empty", "-m", "init"]);
+    git(&checkout, &["push", "-q", "origin", "HEAD"]);
+    let base = git(&checkout, &["rev-parse", "HEAD"]);
+    // A local branch ahead of the remote must not leak into the published branch.
+    git(&checkout, &["switch", "-q", "-c", "wip"]);
+    git(&checkout, &["commit", "-q", "--allow-empty", "-m", "wip"]);
+
+    let publish = |extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+            .args(["--format", "json", "publish"])
+            .arg(fixture.path())
+            .arg("--git")
+            .arg(&checkout)
+            .args(extra)
+            .env("GIT_AUTHOR_NAME", "test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .stdin(std::process::Stdio::null())
+            .output()
+            .unwrap()
+    };
+
+    // Without a terminal to confirm on, nothing is pushed and the checkout is restored.
+    let output = publish(&[]);
+    assert!(!output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--yes"));
+    assert_eq!(git(&checkout, &["branch", "--show-current"]).trim(), "wip");
+    assert!(git(&checkout, &["branch", "--list", "publish/*"]).is_empty());
+    assert!(git(&checkout, &["status", "--porcelain"]).is_empty());
+    assert!(git(&origin, &["branch", "--list", "publish/*"]).is_empty());
+
+    let output = publish(&["--yes"]);
+    assert!(output.status.success(), "{output:?}");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["branch"], "publish/demo-skill-1.0.0");
+    assert_eq!(
+        git(&origin, &["rev-parse", "publish/demo-skill-1.0.0^"]),
+        base
+    );
+
+    let index = git(&origin, &["show", "publish/demo-skill-1.0.0:index.json"]);
+    let index: serde_json::Value = serde_json::from_str(&index).unwrap();
+    assert_eq!(index["skills"][0]["hash"], report["skill"]["hash"]);
+    git(
+        &origin,
+        &[
+            "cat-file",
+            "-e",
+            "publish/demo-skill-1.0.0:demo-skill-1.0.0.tar.gz",
+        ],
+    );
+
+    // Once the version is on the default branch, publishing it again fails cleanly.
+    let default_branch = git(&origin, &["symbolic-ref", "--short", "HEAD"]);
+    git(
+        &checkout,
+        &[
+            "push",
+            "-q",
+            "origin",
+            &format!("publish/demo-skill-1.0.0:{}", default_branch.trim()),
+        ],
+    );
+    git(&checkout, &["switch", "-q", "wip"]);
+    git(
+        &checkout,
+        &["branch", "-q", "-D", "publish/demo-skill-1.0.0"],
+    );
+    let output = publish(&["--yes"]);
+    assert!(!output.status.success(), "{output:?}");
+    assert_eq!(git(&checkout, &["branch", "--show-current"]).trim(), "wip");
+    assert!(git(&checkout, &["branch", "--list", "publish/*"]).is_empty());
+    assert!(git(&checkout, &["status", "--porcelain"]).is_empty());
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn registry_installs_record_the_resolved_version_in_the_receipt() {
+    let registry = TempDir::new().unwrap();
+    let index = make_registry_fixture(registry.path(), &["1.0.0", "1.2.3", "2.0.0"]);
+    let home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+        .args(["install", "demo-skill@^1.2", "--registry"])
+        .arg(&index)
+        .args(["--providers", "claude-code", "--scope", "project"])
+        .args(["--method", "copy", "--no-remember"])
+        .current_dir(project.path())
+        .env("HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    let receipt = read_receipt(&project.path().join(".claude/skills/demo-skill"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(receipt.version.as_deref(), Some("1.2.3"));
+    let pin = receipt.registry.unwrap();
+    assert_eq!(pin.name, "demo-skill");
+    assert_eq!(pin.version, "1.2.3");
+    assert_eq!(pin.requirement.as_deref(), Some("^1.2"));
+    assert_eq!(pin.index, index.to_str().unwrap());
+    assert!(pin.hash.starts_with("sha256:"));
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn cli_list_flags_outdated_skills_and_update_all_respects_ranges() {
+    let registry = TempDir::new().unwrap();
+    let index = make_registry_fixture(registry.path(), &["1.0.0"]);
+    let home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_install-skill"))
+            .args(["--format", "json"])
+            .args(args)
+            .arg("--registry")
+            .arg(&index)
+            .current_dir(project.path())
+            .env("HOME", home.path())
+            .env("XDG_CACHE_HOME", home.path().join("cache"))
+            .stdin(std::process::Stdio::null())
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+    run(&[
+        "install",
+        "demo-skill@^1",
+        "--providers",
+        "claude-code",
+        "--scope",
+        "project",
+        "--method",
+        "copy",
+        "--no-remember",
+    ]);
+
+    let report = run(&["list", "--outdated"]);
+    assert_eq!(report["skills"][0]["skill-name"], "demo-skill");
+    assert_eq!(report["skills"][0]["installed"][0]["version"], "1.0.0");
+    assert!(report.get("updates").is_none());
+
+    make_registry_fixture(registry.path(), &["1.0.0", "1.4.0", "2.0.0"]);
+    let report = run(&["status", "--outdated"]);
+    assert_eq!(report["updates"][0]["current"], "1.0.0");
+    assert_eq!(report["updates"][0]["wanted"], "1.4.0");
+    assert_eq!(report["updates"][0]["latest"], "2.0.0");
+
+    let report = run(&["update", "--all"]);
+    assert_eq!(report["updated"][0]["skill-version"], "1.4.0");
+    let receipt = read_receipt(&project.path().join(".claude/skills/demo-skill"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(receipt.registry.unwrap().requirement.as_deref(), Some("^1"));
+
+    let report = run(&["list", "--outdated"]);
+    assert_eq!(report["updates"][0]["wanted"], serde_json::Value::Null);
+    assert_eq!(report["updates"][0]["latest"], "2.0.0");
+    let report = run(&["update", "--all"]);
+    assert_eq!(report["up-to-date"][0], "demo-skill");
+}
+
+#[test]
+fn namespaced_skills_install_side_by_side_and_reject_collisions() {
+    let project = TempDir::new().unwrap();
+    let skill = |name: &str| {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".skill")).unwrap();
+        fs::write(
+            dir.path().join(".skill/SKILL.md"),
+            format!("---\nname: {name}\ndescription: Review code\n---\nReview it."),
+        )
+        .unwrap();
+        dir
+    };
+    let request = |source: &TempDir, force: bool| InstallRequest {
+        source: SkillSource::LocalPath(source.path().to_path_buf()),
+        providers: vec![ProviderId::ClaudeCode, ProviderId::Cursor],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+        method: InstallMethod::Symlink,
+        force,
+        options: InstallOptions::default(),
+    };
+
+    for name in [
+        "a--b/review",
+        "/review",
+        "acme/",
+        "acme/code/review",
+        "acme/..",
+    ] {
+        let err =
+            parse_skill_str(&format!("---\nname: {name}\ndescription: x\n---\n")).unwrap_err();
+        assert!(matches!(err, InstallerError::InvalidName { .. }), "{name}");
+    }
+    let parsed = parse_skill_str("---\nname: acme/code-review\ndescription: x\n---\n").unwrap();
+    assert!(lint_skill(
+        &parsed,
+        b"---\nname: acme/code-review\ndescription: x\n---\n"
+    )
+    .iter()
+    .all(|d| d.rule != "name-not-kebab-case"));
+
+    let acme = skill("acme/code-review");
+    let globex = skill("globex/code-review");
+    install(request(&acme, false)).unwrap();
+    install(request(&globex, false)).unwrap();
+    for dir in ["acme--code-review", "globex--code-review"] {
+        assert!(project
+            .path()
+            .join(".claude/skills")
+            .join(dir)
+            .join("SKILL.md")
+            .is_file());
+        assert!(project
+            .path()
+            .join(".agents/skills")
+            .join(dir)
+            .join("SKILL.md")
+            .is_file());
+    }
+
+    let installed = detect_installed("acme/code-review", Some(project.path())).unwrap();
+    assert!(installed
+        .iter()
+        .any(|i| i.provider == ProviderId::ClaudeCode && i.method == InstallMethod::Symlink));
+    let names = list_installed(Some(project.path()))
+        .unwrap()
+        .into_iter()
+        .map(|report| report.skill_name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["acme/code-review", "globex/code-review"]);
+
+    let impostor = skill("acme--code-review");
+    let err = install(request(&impostor, true)).unwrap_err();
+    assert!(matches!(err, InstallerError::NameCollision { .. }), "{err}");
+    assert_eq!(err.exit_code(), 3);
+
+    let result = uninstall(UninstallRequest {
+        skill_name: "globex/code-review".to_string(),
+        providers: vec![ProviderId::ClaudeCode, ProviderId::Cursor],
+        scope: Scope::Project,
+        project_root: Some(project.path().to_path_buf()),
+    })
+    .unwrap();
+    assert!(!result.removed.is_empty());
+    assert!(!project
+        .path()
+        .join(".claude/skills/globex--code-review")
+        .exists());
+    assert!(project
+        .path()
+        .join(".claude/skills/acme--code-review")
+        .exists());
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn python_module_exports_parse_detect_and_install() {
+    use pyo3::prelude::*;
+    use skillinstaller::python_module;
+
+    let fixture = make_skill_fixture();
+    let project = TempDir::new().unwrap();
+    pyo3::append_to_inittab!(python_module);
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let locals = pyo3::types::PyDict::new(py);
+        locals.set_item("source", fixture.path()).unwrap();
+        locals.set_item("project", project.path()).unwrap();
+        let script = c"
+import skillinstaller
+skill = skillinstaller.parse_skill(source)
+assert skill['name'] == 'demo-skill', skill
+assert 'detected' in skillinstaller.detect_providers(project)
+result = skillinstaller.install(source, ['claude'], project_root=project, method='copy')
+assert result['skill-name'] == 'demo-skill', result
+try:
+    skillinstaller.install(source, ['claude'], project_root=project, method='copy')
+    raise AssertionError('second install should fail')
+except skillinstaller.SkillInstallerError as err:
+    assert err.args[0] == 'E_ALREADY_EXISTS', err.args
+";
+        py.run(script, None, Some(&locals)).unwrap();
+    });
+    assert!(project
+        .path()
+        .join(".claude/skills/demo-skill/SKILL.md")
+        .exists());
+}
@@ -47,6 +47,9 @@ export type ProviderId =
   | 'neovate'
   | 'pochi'
   | 'adal'
+  | 'zed'
+  | 'aider'
+  | 'vscode'
   | 'universal';
 
 export interface ProviderInfo {
@@ -145,6 +148,9 @@ const providers: ProviderInfo[] = [
   { id: 'neovate', displayName: 'Neovate', usesAgentsDir: false, projectPath: '.neovate/skills' },
   { id: 'pochi', displayName: 'Pochi', usesAgentsDir: false, projectPath: '.pochi/skills' },
   { id: 'adal', displayName: 'AdaL', usesAgentsDir: false, projectPath: '.adal/skills' },
+  { id: 'zed', displayName: 'Zed', usesAgentsDir: false, projectPath: '.zed/skills' },
+  { id: 'aider', displayName: 'Aider', usesAgentsDir: false, projectPath: '.aider/skills' },
+  { id: 'vscode', displayName: 'VS Code', usesAgentsDir: false, projectPath: '.vscode/skills' },
   { id: 'universal', displayName: 'Universal', usesAgentsDir: true, projectPath: '.agents/skills' }
 ];
 
@@ -509,6 +515,12 @@ function resolveProviderDir(provider: ProviderId, scope: Scope, projectRoot?: st
       return join(home, '.pochi/skills');
     case 'adal':
       return join(home, '.adal/skills');
+    case 'zed':
+      return join(configHome, 'zed/skills');
+    case 'aider':
+      return join(home, '.aider/skills');
+    case 'vscode':
+      return join(home, '.vscode/skills');
   }
 }
 
@@ -575,6 +587,8 @@ function markerPath(provider: ProviderId, home: string, projectRoot?: string): s
       return join(home, '.snowflake/cortex');
     case 'windsurf':
       return join(home, '.codeium/windsurf');
+    case 'zed':
+      return join(configHome, 'zed');
     default: {
       const info = providers.find((p) => p.id === provider);
       if (!info) return undefined;